@@ -0,0 +1,122 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::{
+    codec::{NBTCodec, NBTCodecTrait},
+    error::{NBTError, Result},
+    extract::FromValue,
+    path::NbtPath,
+    value::Value,
+};
+
+pub const DIGP_PREFIX: &[u8] = b"digp";
+pub const ACTOR_PREFIX: &[u8] = b"actorprefix";
+
+pub fn digp_key(x: i32, z: i32, dimension: i32) -> Vec<u8> {
+    let mut key = Vec::with_capacity(16);
+    key.extend_from_slice(DIGP_PREFIX);
+    key.extend_from_slice(&x.to_le_bytes());
+    key.extend_from_slice(&z.to_le_bytes());
+    if dimension != 0 {
+        key.extend_from_slice(&dimension.to_le_bytes());
+    }
+    key
+}
+
+pub fn parse_digp_key(key: &[u8]) -> Option<(i32, i32, i32)> {
+    let coords = key.strip_prefix(DIGP_PREFIX)?;
+    let read = |i: usize| Some(i32::from_le_bytes(coords.get(i..i + 4)?.try_into().ok()?));
+
+    match coords.len() {
+        8 => Some((read(0)?, read(4)?, 0)),
+        12 => Some((read(0)?, read(4)?, read(8)?)),
+        _ => None,
+    }
+}
+
+pub fn decode_digp(data: &[u8]) -> Result<Vec<i64>> {
+    if !data.len().is_multiple_of(8) {
+        return Err(NBTError::custom_msg(format!(
+            "digp record length {} is not a multiple of 8",
+            data.len()
+        )));
+    }
+
+    Ok(data
+        .chunks_exact(8)
+        .map(|id| i64::from_le_bytes(id.try_into().unwrap()))
+        .collect())
+}
+
+pub fn encode_digp(ids: &[i64]) -> Vec<u8> {
+    ids.iter().flat_map(|id| id.to_le_bytes()).collect()
+}
+
+pub fn actor_key(id: i64) -> Vec<u8> {
+    let mut key = Vec::with_capacity(ACTOR_PREFIX.len() + 8);
+    key.extend_from_slice(ACTOR_PREFIX);
+    key.extend_from_slice(&id.to_le_bytes());
+    key
+}
+
+pub fn parse_actor_key(key: &[u8]) -> Option<i64> {
+    let id = key.strip_prefix(ACTOR_PREFIX)?;
+    Some(i64::from_le_bytes(id.try_into().ok()?))
+}
+
+pub fn read_actor(data: &[u8]) -> Result<Value<'static>> {
+    let codec = NBTCodec::little_endian();
+    let (_, value) = codec.read_tag(&mut &data[..])?;
+    Ok(value.into_owned())
+}
+
+pub fn write_actor(value: &Value<'_>) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    NBTCodec::little_endian().write_tag(&mut data, None::<Cow<'_, str>>, value)?;
+    Ok(data)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActorLocation {
+    pub chunk: Option<(i32, i32, i32)>,
+    pub key: Vec<u8>,
+}
+
+pub fn find_entity<I, K, V>(records: I, unique_id: i64) -> Result<Option<ActorLocation>>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let codec = NBTCodec::little_endian();
+    let paths = [NbtPath::root().key("UniqueID")];
+    let mut chunks = HashMap::new();
+    let mut found = None;
+
+    for (key, data) in records {
+        let (key, data) = (key.as_ref(), data.as_ref());
+        if let Some(chunk) = parse_digp_key(key) {
+            for id in decode_digp(data)? {
+                chunks.insert(id, chunk);
+            }
+        } else if let Some(id) = parse_actor_key(key) {
+            let (_, actor) = codec.read_projection(&mut &data[..], &paths)?;
+            if actor.get("UniqueID").and_then(i64::from_value) == Some(unique_id) {
+                found = Some(id);
+            }
+        }
+
+        if let Some(id) = found
+            && let Some(&chunk) = chunks.get(&id)
+        {
+            return Ok(Some(ActorLocation {
+                chunk: Some(chunk),
+                key: actor_key(id),
+            }));
+        }
+    }
+
+    Ok(found.map(|id| ActorLocation {
+        chunk: None,
+        key: actor_key(id),
+    }))
+}
@@ -0,0 +1,105 @@
+use std::{
+    fs::{self, File},
+    io::{Read, Seek},
+    path::Path,
+};
+
+use zip::{ZipArchive, result::ZipError};
+
+use crate::{
+    error::{NBTError, Result},
+    file::NbtFile,
+};
+
+const MAX_PREALLOC: u64 = 1024 * 1024;
+
+pub struct WorldArchive<R: Read + Seek> {
+    zip: ZipArchive<R>,
+    root: String,
+}
+
+impl WorldArchive<File> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> WorldArchive<R> {
+    pub fn from_reader(reader: R) -> Result<Self> {
+        let zip = ZipArchive::new(reader).map_err(std::io::Error::from)?;
+
+        let root = zip
+            .file_names()
+            .filter_map(|name| name.strip_suffix("level.dat"))
+            .filter(|prefix| prefix.is_empty() || prefix.ends_with('/'))
+            .min_by_key(|prefix| prefix.len())
+            .map(str::to_owned)
+            .ok_or_else(|| NBTError::custom_msg("world archive: level.dat not found"))?;
+
+        Ok(Self { zip, root })
+    }
+
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    pub fn level_dat(&mut self) -> Result<NbtFile> {
+        NbtFile::from_bytes(&self.read_entry("level.dat")?)
+    }
+
+    pub fn level_name(&mut self) -> Result<Option<String>> {
+        match self.try_read_entry("levelname.txt")? {
+            Some(bytes) => Ok(Some(String::from_utf8(bytes)?.trim().to_owned())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn db_files(&self) -> Vec<String> {
+        let prefix = format!("{}db/", self.root);
+        let mut names: Vec<String> = self
+            .zip
+            .file_names()
+            .filter_map(|name| name.strip_prefix(&prefix))
+            .filter(|name| !name.is_empty() && !name.ends_with('/'))
+            .map(str::to_owned)
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn read_db_file(&mut self, name: &str) -> Result<Vec<u8>> {
+        self.read_entry(&format!("db/{}", name))
+    }
+
+    pub fn extract_db<P: AsRef<Path>>(&mut self, dir: P) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for name in self.db_files() {
+            if name.contains(['/', '\\']) || name == ".." {
+                return Err(NBTError::custom_msg(format!(
+                    "world archive: refusing to extract db entry {:?}",
+                    name
+                )));
+            }
+            let bytes = self.read_db_file(&name)?;
+            fs::write(dir.join(&name), bytes)?;
+        }
+        Ok(())
+    }
+
+    fn read_entry(&mut self, name: &str) -> Result<Vec<u8>> {
+        self.try_read_entry(name)?
+            .ok_or_else(|| std::io::Error::from(ZipError::FileNotFound).into())
+    }
+
+    fn try_read_entry(&mut self, name: &str) -> Result<Option<Vec<u8>>> {
+        let mut entry = match self.zip.by_name(&format!("{}{}", self.root, name)) {
+            Ok(entry) => entry,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(std::io::Error::from(e).into()),
+        };
+        let mut bytes = Vec::with_capacity(entry.size().min(MAX_PREALLOC) as usize);
+        entry.read_to_end(&mut bytes)?;
+        Ok(Some(bytes))
+    }
+}
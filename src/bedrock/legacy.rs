@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+
+use crate::{
+    codec::{NBTCodec, NBTCodecTrait},
+    error::{NBTError, Result},
+    value::Value,
+};
+
+pub const ENTITIES_MAGIC: &[u8; 4] = b"ENT\0";
+pub const SECTOR_SIZE: usize = 4096;
+pub const CHUNKS_PER_SIDE: usize = 32;
+
+const CHUNK_BLOCKS: usize = 16 * 16 * 128;
+const CHUNK_NIBBLES: usize = CHUNK_BLOCKS / 2;
+const CHUNK_COLUMNS: usize = 16 * 16;
+const CHUNK_LEN: usize = CHUNK_BLOCKS + 3 * CHUNK_NIBBLES + CHUNK_COLUMNS;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegacyEntities {
+    pub version: u32,
+    pub value: Value<'static>,
+}
+
+impl LegacyEntities {
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        if data.get(..4) != Some(ENTITIES_MAGIC) {
+            return Err(NBTError::custom_msg("entities.dat: missing ENT header"));
+        }
+        let version = read_u32(data, 4)?;
+        let length = read_u32(data, 8)? as usize;
+        let payload = data
+            .get(12..12 + length)
+            .ok_or_else(|| NBTError::unexpected_eof_reading(length))?;
+
+        let codec = NBTCodec::little_endian();
+        let (_, value) = codec.read_tag(&mut &payload[..])?;
+
+        Ok(Self {
+            version,
+            value: value.into_owned(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut payload = Vec::new();
+        NBTCodec::little_endian().write_tag(&mut payload, Some(Cow::Borrowed("")), &self.value)?;
+
+        let mut out = Vec::with_capacity(payload.len() + 12);
+        out.extend_from_slice(ENTITIES_MAGIC);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+
+    pub fn entities(&self) -> &[Value<'static>] {
+        self.list("Entities")
+    }
+
+    pub fn tile_entities(&self) -> &[Value<'static>] {
+        self.list("TileEntities")
+    }
+
+    fn list(&self, key: &str) -> &[Value<'static>] {
+        self.value
+            .get(key)
+            .and_then(Value::as_list)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegacyChunkLocation {
+    pub sector: usize,
+    pub sectors: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct LegacyChunkStore<'d> {
+    data: &'d [u8],
+}
+
+impl<'d> LegacyChunkStore<'d> {
+    pub fn new(data: &'d [u8]) -> Result<Self> {
+        if data.len() < SECTOR_SIZE {
+            return Err(NBTError::unexpected_eof_reading(SECTOR_SIZE));
+        }
+        Ok(Self { data })
+    }
+
+    pub fn location(&self, x: usize, z: usize) -> Option<LegacyChunkLocation> {
+        if x >= CHUNKS_PER_SIDE || z >= CHUNKS_PER_SIDE {
+            return None;
+        }
+        let entry = read_u32(self.data, (x + z * CHUNKS_PER_SIDE) * 4).ok()?;
+        let location = LegacyChunkLocation {
+            sector: (entry >> 8) as usize,
+            sectors: (entry & 0xFF) as usize,
+        };
+        (location.sectors > 0).then_some(location)
+    }
+
+    pub fn chunk_bytes(&self, x: usize, z: usize) -> Result<Option<&'d [u8]>> {
+        let Some(location) = self.location(x, z) else {
+            return Ok(None);
+        };
+
+        let start = location.sector * SECTOR_SIZE;
+        let length = read_u32(self.data, start)? as usize;
+        if length < 4 || length > location.sectors * SECTOR_SIZE {
+            return Err(NBTError::custom_msg(format!(
+                "chunks.dat: chunk {},{} has invalid length {}",
+                x, z, length
+            )));
+        }
+        self.data
+            .get(start + 4..start + length)
+            .map(Some)
+            .ok_or_else(|| NBTError::unexpected_eof_reading(length))
+    }
+
+    pub fn chunk(&self, x: usize, z: usize) -> Result<Option<LegacyChunk<'d>>> {
+        self.chunk_bytes(x, z)?.map(LegacyChunk::new).transpose()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LegacyChunk<'d> {
+    pub blocks: &'d [u8],
+    pub data: &'d [u8],
+    pub sky_light: &'d [u8],
+    pub block_light: &'d [u8],
+    pub dirty_columns: &'d [u8],
+}
+
+impl<'d> LegacyChunk<'d> {
+    pub fn new(payload: &'d [u8]) -> Result<Self> {
+        if payload.len() < CHUNK_LEN {
+            return Err(NBTError::unexpected_eof_reading(CHUNK_LEN));
+        }
+        let (blocks, rest) = payload.split_at(CHUNK_BLOCKS);
+        let (data, rest) = rest.split_at(CHUNK_NIBBLES);
+        let (sky_light, rest) = rest.split_at(CHUNK_NIBBLES);
+        let (block_light, rest) = rest.split_at(CHUNK_NIBBLES);
+
+        Ok(Self {
+            blocks,
+            data,
+            sky_light,
+            block_light,
+            dirty_columns: &rest[..CHUNK_COLUMNS],
+        })
+    }
+
+    fn index(x: usize, y: usize, z: usize) -> Option<usize> {
+        (x < 16 && y < 128 && z < 16).then_some((x * 16 + z) * 128 + y)
+    }
+
+    pub fn block_id(&self, x: usize, y: usize, z: usize) -> Option<u8> {
+        self.blocks.get(Self::index(x, y, z)?).copied()
+    }
+
+    pub fn block_data(&self, x: usize, y: usize, z: usize) -> Option<u8> {
+        nibble(self.data, Self::index(x, y, z)?)
+    }
+
+    pub fn sky_light(&self, x: usize, y: usize, z: usize) -> Option<u8> {
+        nibble(self.sky_light, Self::index(x, y, z)?)
+    }
+
+    pub fn block_light(&self, x: usize, y: usize, z: usize) -> Option<u8> {
+        nibble(self.block_light, Self::index(x, y, z)?)
+    }
+}
+
+fn nibble(data: &[u8], index: usize) -> Option<u8> {
+    let byte = *data.get(index / 2)?;
+    Some(if index.is_multiple_of(2) {
+        byte & 0x0F
+    } else {
+        byte >> 4
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().expect("slice of four bytes")))
+        .ok_or_else(|| NBTError::unexpected_eof_reading(4))
+}
@@ -0,0 +1,6 @@
+pub mod actor;
+#[cfg(feature = "mcworld")]
+pub mod archive;
+pub mod legacy;
+pub mod records;
+pub mod storage;
@@ -0,0 +1,366 @@
+use std::borrow::Cow;
+
+use crate::{
+    bedrock::actor,
+    error::Result,
+    extract::{Fields, insert_opt},
+    value::{CompoundMap, Value},
+};
+
+pub const LOCAL_PLAYER_KEY: &[u8] = b"~local_player";
+pub const PORTALS_KEY: &[u8] = b"portals";
+pub const SCOREBOARD_KEY: &[u8] = b"scoreboard";
+pub const MOBEVENTS_KEY: &[u8] = b"mobevents";
+pub const MAP_PREFIX: &[u8] = b"map_";
+
+pub type Extra = CompoundMap<'static>;
+
+pub trait DbRecord: Sized {
+    fn from_value(value: Value<'static>) -> Result<Self>;
+
+    fn to_value(&self) -> Value<'static>;
+
+    fn decode(data: &[u8]) -> Result<Self> {
+        Self::from_value(actor::read_actor(data)?)
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        actor::write_actor(&self.to_value())
+    }
+}
+
+pub fn map_key(id: i64) -> Vec<u8> {
+    format!("map_{id}").into_bytes()
+}
+
+pub fn parse_map_key(key: &[u8]) -> Option<i64> {
+    std::str::from_utf8(key.strip_prefix(MAP_PREFIX)?)
+        .ok()?
+        .parse()
+        .ok()
+}
+
+fn float_list(values: &[f32]) -> Value<'static> {
+    Value::List(values.iter().copied().map(Value::Float).collect())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalPlayer {
+    pub unique_id: i64,
+    pub dimension_id: Option<i32>,
+    pub pos: [f32; 3],
+    pub rotation: [f32; 2],
+    pub extra: Extra,
+}
+
+impl DbRecord for LocalPlayer {
+    fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let unique_id = fields.take("UniqueID");
+        let dimension_id = fields.take("DimensionId");
+        let pos = fields.take_floats("Pos");
+        let rotation = fields.take_floats("Rotation");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            unique_id: unique_id.unwrap(),
+            dimension_id: dimension_id.unwrap(),
+            pos: pos.unwrap(),
+            rotation: rotation.unwrap(),
+            extra,
+        })
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("UniqueID"), self.unique_id.into());
+        insert_opt(&mut map, "DimensionId", self.dimension_id);
+        map.insert(Cow::Borrowed("Pos"), float_list(&self.pos));
+        map.insert(Cow::Borrowed("Rotation"), float_list(&self.rotation));
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortalRecord {
+    pub dimension_id: i32,
+    pub span: i8,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub xa: i8,
+    pub za: i8,
+    pub extra: Extra,
+}
+
+impl DbRecord for PortalRecord {
+    fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let dimension_id = fields.take("DimId");
+        let span = fields.take("Span");
+        let x = fields.take("TpX");
+        let y = fields.take("TpY");
+        let z = fields.take("TpZ");
+        let xa = fields.take("Xa");
+        let za = fields.take("Za");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            dimension_id: dimension_id.unwrap(),
+            span: span.unwrap(),
+            x: x.unwrap(),
+            y: y.unwrap(),
+            z: z.unwrap(),
+            xa: xa.unwrap(),
+            za: za.unwrap(),
+            extra,
+        })
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("DimId"), self.dimension_id.into());
+        map.insert(Cow::Borrowed("Span"), Value::Byte(self.span));
+        map.insert(Cow::Borrowed("TpX"), self.x.into());
+        map.insert(Cow::Borrowed("TpY"), self.y.into());
+        map.insert(Cow::Borrowed("TpZ"), self.z.into());
+        map.insert(Cow::Borrowed("Xa"), Value::Byte(self.xa));
+        map.insert(Cow::Borrowed("Za"), Value::Byte(self.za));
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Portals {
+    pub records: Option<Vec<PortalRecord>>,
+    pub data_extra: Option<Extra>,
+    pub extra: Extra,
+}
+
+impl DbRecord for Portals {
+    fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut root = Fields::new(value)?;
+        let data = root.map.remove("data");
+        let extra = root.finish()?;
+
+        let Some(data) = data else {
+            return Ok(Self {
+                records: None,
+                data_extra: None,
+                extra,
+            });
+        };
+
+        let mut data = Fields::new(data)?;
+        let records = data
+            .take_opt_list("PortalRecords")
+            .map(|records| {
+                records
+                    .into_iter()
+                    .map(PortalRecord::from_value)
+                    .collect::<Result<_>>()
+            })
+            .transpose()?;
+        let data_extra = data.finish()?;
+
+        Ok(Self {
+            records,
+            data_extra: Some(data_extra),
+            extra,
+        })
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        if self.records.is_some() || self.data_extra.is_some() {
+            let mut data = self.data_extra.clone().unwrap_or_default();
+            insert_opt(
+                &mut data,
+                "PortalRecords",
+                self.records
+                    .as_ref()
+                    .map(|records| Value::List(records.iter().map(DbRecord::to_value).collect())),
+            );
+            map.insert(Cow::Borrowed("data"), Value::Compound(data));
+        }
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scoreboard {
+    pub objectives: Option<Vec<Value<'static>>>,
+    pub entries: Option<Vec<Value<'static>>>,
+    pub criteria: Option<Vec<Value<'static>>>,
+    pub display_objectives: Option<Vec<Value<'static>>>,
+    pub last_unique_id: Option<i64>,
+    pub extra: Extra,
+}
+
+impl DbRecord for Scoreboard {
+    fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let objectives = fields.take_opt_list("Objectives");
+        let entries = fields.take_opt_list("Entries");
+        let criteria = fields.take_opt_list("Criteria");
+        let display_objectives = fields.take_opt_list("DisplayObjectives");
+        let last_unique_id = fields.take("LastUniqueID");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            objectives,
+            entries,
+            criteria,
+            display_objectives,
+            last_unique_id: last_unique_id.unwrap(),
+            extra,
+        })
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        insert_opt(
+            &mut map,
+            "Objectives",
+            self.objectives.clone().map(Value::List),
+        );
+        insert_opt(&mut map, "Entries", self.entries.clone().map(Value::List));
+        insert_opt(&mut map, "Criteria", self.criteria.clone().map(Value::List));
+        insert_opt(
+            &mut map,
+            "DisplayObjectives",
+            self.display_objectives.clone().map(Value::List),
+        );
+        insert_opt(&mut map, "LastUniqueID", self.last_unique_id);
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MobEvents {
+    pub events_enabled: Option<bool>,
+    pub ender_dragon: Option<bool>,
+    pub pillager_patrols: Option<bool>,
+    pub wandering_trader: Option<bool>,
+    pub extra: Extra,
+}
+
+impl DbRecord for MobEvents {
+    fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let events_enabled = fields.take("events_enabled");
+        let ender_dragon = fields.take("minecraft:ender_dragon_event");
+        let pillager_patrols = fields.take("minecraft:pillager_patrols_event");
+        let wandering_trader = fields.take("minecraft:wandering_trader_event");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            events_enabled: events_enabled.unwrap(),
+            ender_dragon: ender_dragon.unwrap(),
+            pillager_patrols: pillager_patrols.unwrap(),
+            wandering_trader: wandering_trader.unwrap(),
+            extra,
+        })
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        insert_opt(&mut map, "events_enabled", self.events_enabled);
+        insert_opt(&mut map, "minecraft:ender_dragon_event", self.ender_dragon);
+        insert_opt(
+            &mut map,
+            "minecraft:pillager_patrols_event",
+            self.pillager_patrols,
+        );
+        insert_opt(
+            &mut map,
+            "minecraft:wandering_trader_event",
+            self.wandering_trader,
+        );
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapData {
+    pub map_id: i64,
+    pub parent_map_id: Option<i64>,
+    pub dimension: i8,
+    pub scale: i8,
+    pub width: i16,
+    pub height: i16,
+    pub x_center: i32,
+    pub z_center: i32,
+    pub fully_explored: Option<bool>,
+    pub locked: Option<bool>,
+    pub unlimited_tracking: Option<bool>,
+    pub colors: Option<Vec<u8>>,
+    pub decorations: Option<Vec<Value<'static>>>,
+    pub extra: Extra,
+}
+
+impl DbRecord for MapData {
+    fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let map_id = fields.take("mapId");
+        let parent_map_id = fields.take("parentMapId");
+        let dimension = fields.take("dimension");
+        let scale = fields.take("scale");
+        let width = fields.take("width");
+        let height = fields.take("height");
+        let x_center = fields.take("xCenter");
+        let z_center = fields.take("zCenter");
+        let fully_explored = fields.take("fullyExplored");
+        let locked = fields.take("mapLocked");
+        let unlimited_tracking = fields.take("unlimitedTracking");
+        let colors = fields.take("colors");
+        let decorations = fields
+            .map
+            .contains_key("decorations")
+            .then(|| fields.take_list("decorations"));
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            map_id: map_id.unwrap(),
+            parent_map_id: parent_map_id.unwrap(),
+            dimension: dimension.unwrap(),
+            scale: scale.unwrap(),
+            width: width.unwrap(),
+            height: height.unwrap(),
+            x_center: x_center.unwrap(),
+            z_center: z_center.unwrap(),
+            fully_explored: fully_explored.unwrap(),
+            locked: locked.unwrap(),
+            unlimited_tracking: unlimited_tracking.unwrap(),
+            colors: colors.unwrap(),
+            decorations,
+            extra,
+        })
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("mapId"), self.map_id.into());
+        insert_opt(&mut map, "parentMapId", self.parent_map_id);
+        map.insert(Cow::Borrowed("dimension"), Value::Byte(self.dimension));
+        map.insert(Cow::Borrowed("scale"), Value::Byte(self.scale));
+        map.insert(Cow::Borrowed("width"), self.width.into());
+        map.insert(Cow::Borrowed("height"), self.height.into());
+        map.insert(Cow::Borrowed("xCenter"), self.x_center.into());
+        map.insert(Cow::Borrowed("zCenter"), self.z_center.into());
+        insert_opt(&mut map, "fullyExplored", self.fully_explored);
+        insert_opt(&mut map, "mapLocked", self.locked);
+        insert_opt(&mut map, "unlimitedTracking", self.unlimited_tracking);
+        insert_opt(
+            &mut map,
+            "colors",
+            self.colors.clone().map(Value::ByteArray),
+        );
+        insert_opt(
+            &mut map,
+            "decorations",
+            self.decorations.clone().map(Value::List),
+        );
+        Value::Compound(map)
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    error::{NBTError, Result},
+    file::NbtFile,
+    value::Value,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkFormat {
+    LegacyTerrain,
+    LegacySubChunks,
+    Palettized,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubChunkFormat {
+    Legacy,
+    SingleLayer,
+    MultiLayer,
+    MultiLayerIndexed,
+}
+
+impl SubChunkFormat {
+    pub fn from_version(version: u8) -> Option<Self> {
+        match version {
+            0 | 2..=7 => Some(SubChunkFormat::Legacy),
+            1 => Some(SubChunkFormat::SingleLayer),
+            8 => Some(SubChunkFormat::MultiLayer),
+            9 => Some(SubChunkFormat::MultiLayerIndexed),
+            _ => None,
+        }
+    }
+
+    pub fn from_record(data: &[u8]) -> Result<Self> {
+        let version = *data.first().ok_or_else(NBTError::unexpected_eof)?;
+        Self::from_version(version)
+            .ok_or_else(|| NBTError::custom_msg(format!("Unknown subchunk version {}", version)))
+    }
+
+    pub fn is_palettized(self) -> bool {
+        self != SubChunkFormat::Legacy
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StorageVersion(pub i32);
+
+impl StorageVersion {
+    pub fn from_level_dat(root: &Value<'_>) -> Result<Self> {
+        match root.get("StorageVersion") {
+            Some(Value::Int(version)) => Ok(StorageVersion(*version)),
+            Some(other) => Err(NBTError::custom_msg(format!(
+                "StorageVersion: expected Int, got {:?}",
+                other.tag()
+            ))),
+            None => Err(NBTError::custom_msg("StorageVersion: missing")),
+        }
+    }
+
+    pub fn from_file(file: &NbtFile) -> Result<Self> {
+        Self::from_level_dat(&file.value)
+    }
+
+    pub fn chunk_format(self) -> ChunkFormat {
+        match self.0 {
+            ..=4 => ChunkFormat::LegacyTerrain,
+            5..=7 => ChunkFormat::LegacySubChunks,
+            _ => ChunkFormat::Palettized,
+        }
+    }
+
+    pub fn subchunk_format(self) -> Option<SubChunkFormat> {
+        match self.0 {
+            ..=4 => None,
+            5..=7 => Some(SubChunkFormat::Legacy),
+            8..=9 => Some(SubChunkFormat::MultiLayer),
+            _ => Some(SubChunkFormat::MultiLayerIndexed),
+        }
+    }
+
+    pub fn has_negative_subchunks(self) -> bool {
+        self.0 >= 10
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::HashSet;
+
+use bnbt::{
+    error::Result,
+    file::NbtFile,
+    path::{NbtPath, PathSegment},
+    snbt,
+    value::Value,
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+const HELP: &str = "arrows move/expand  / search  n next  e edit  w save  q quit";
+
+enum Mode {
+    Normal,
+    Search(String),
+    Edit(String),
+}
+
+struct Row {
+    path: NbtPath,
+    depth: usize,
+    label: String,
+    container: bool,
+}
+
+struct Browser<'f> {
+    file: &'f str,
+    nbt: NbtFile,
+    expanded: HashSet<NbtPath>,
+    rows: Vec<Row>,
+    state: ListState,
+    mode: Mode,
+    query: String,
+    status: String,
+    dirty: bool,
+    confirm_quit: bool,
+}
+
+pub fn browse(file: &str) -> Result<()> {
+    let mut browser = Browser {
+        file,
+        nbt: NbtFile::open(file)?,
+        expanded: HashSet::new(),
+        rows: Vec::new(),
+        state: ListState::default().with_selected(Some(0)),
+        mode: Mode::Normal,
+        query: String::new(),
+        status: format!("{}  {}", file, HELP),
+        dirty: false,
+        confirm_quit: false,
+    };
+    browser.rebuild();
+
+    let mut terminal = ratatui::init();
+    let result = browser.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl Browser<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle(key.code)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tree, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| ListItem::new(format!("{}{}", "  ".repeat(row.depth), row.label)))
+            .collect();
+        let list = List::new(items).highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, tree, &mut self.state);
+
+        let line = match &self.mode {
+            Mode::Normal => self.status.clone(),
+            Mode::Search(query) => format!("/{}", query),
+            Mode::Edit(text) => format!("= {}", text),
+        };
+        frame.render_widget(Paragraph::new(line), status);
+    }
+
+    fn handle(&mut self, code: KeyCode) -> bool {
+        match &mut self.mode {
+            Mode::Search(query) | Mode::Edit(query) => match code {
+                KeyCode::Char(c) => query.push(c),
+                KeyCode::Backspace => {
+                    query.pop();
+                }
+                KeyCode::Esc => self.mode = Mode::Normal,
+                KeyCode::Enter => match std::mem::replace(&mut self.mode, Mode::Normal) {
+                    Mode::Search(query) => {
+                        self.query = query;
+                        self.search();
+                    }
+                    Mode::Edit(text) => self.apply_edit(&text),
+                    Mode::Normal => {}
+                },
+                _ => {}
+            },
+            Mode::Normal => {
+                if code != KeyCode::Char('q') {
+                    self.confirm_quit = false;
+                }
+                match code {
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        if !self.dirty || self.confirm_quit {
+                            return false;
+                        }
+                        self.confirm_quit = true;
+                        self.status = "unsaved changes, press q again to quit".to_owned();
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => self.state.select_previous(),
+                    KeyCode::Down | KeyCode::Char('j') => self.state.select_next(),
+                    KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => self.expand(),
+                    KeyCode::Left | KeyCode::Char('h') => self.collapse(),
+                    KeyCode::Char('/') => self.mode = Mode::Search(String::new()),
+                    KeyCode::Char('n') => self.search(),
+                    KeyCode::Char('e') => {
+                        if let Some(value) =
+                            self.selected().and_then(|p| self.nbt.value.get_path(p))
+                        {
+                            self.mode = Mode::Edit(snbt::to_string(value));
+                        }
+                    }
+                    KeyCode::Char('w') => {
+                        self.status = match self.nbt.save(self.file) {
+                            Ok(()) => {
+                                self.dirty = false;
+                                format!("saved {}", self.file)
+                            }
+                            Err(e) => format!("save failed: {}", e),
+                        };
+                    }
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    fn selected(&self) -> Option<&NbtPath> {
+        self.state
+            .selected()
+            .and_then(|i| self.rows.get(i))
+            .map(|row| &row.path)
+    }
+
+    fn select(&mut self, path: &NbtPath) {
+        if let Some(i) = self.rows.iter().position(|row| row.path == *path) {
+            self.state.select(Some(i));
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let selected = self.selected().cloned();
+        self.rows.clear();
+        collect(
+            &self.nbt.value,
+            &NbtPath::root(),
+            0,
+            &self.expanded,
+            &mut self.rows,
+        );
+        if let Some(path) = selected {
+            self.select(&path);
+        }
+        if self.state.selected().is_none_or(|i| i >= self.rows.len()) {
+            self.state.select(Some(self.rows.len().saturating_sub(1)));
+        }
+    }
+
+    fn expand(&mut self) {
+        let Some(row) = self.state.selected().and_then(|i| self.rows.get(i)) else {
+            return;
+        };
+        if row.container && self.expanded.insert(row.path.clone()) {
+            self.rebuild();
+        }
+    }
+
+    fn collapse(&mut self) {
+        let Some(path) = self.selected().cloned() else {
+            return;
+        };
+        if !self.expanded.remove(&path)
+            && let Some(parent) = path.parent().filter(|parent| !parent.is_root())
+        {
+            self.expanded.remove(&parent);
+            self.rebuild();
+            self.select(&parent);
+            return;
+        }
+        self.rebuild();
+    }
+
+    fn search(&mut self) {
+        if self.query.is_empty() {
+            return;
+        }
+        let query = self.query.to_lowercase();
+        let mut nodes = Vec::new();
+        walk(&self.nbt.value, &NbtPath::root(), &query, &mut nodes);
+
+        let start = self
+            .selected()
+            .and_then(|current| nodes.iter().position(|(path, _)| path == current))
+            .map_or(0, |i| i + 1);
+        let Some(found) = nodes[start..]
+            .iter()
+            .chain(&nodes[..start])
+            .find(|(_, matched)| *matched)
+            .map(|(path, _)| path.clone())
+        else {
+            self.status = format!("no match for {}", self.query);
+            return;
+        };
+
+        let mut ancestor = found.parent();
+        while let Some(path) = ancestor.filter(|path| !path.is_root()) {
+            ancestor = path.parent();
+            self.expanded.insert(path);
+        }
+        self.rebuild();
+        self.select(&found);
+        self.status = found.to_string();
+    }
+
+    fn apply_edit(&mut self, text: &str) {
+        let Some(path) = self.selected().cloned() else {
+            return;
+        };
+        let result = snbt::from_str(text).and_then(|value| self.nbt.value.set_path(&path, value));
+        self.status = match result {
+            Ok(_) => {
+                self.dirty = true;
+                self.rebuild();
+                format!("{} updated", path)
+            }
+            Err(e) => format!("edit failed: {}", e),
+        };
+    }
+}
+
+fn children<'v>(value: &'v Value<'static>, path: &NbtPath) -> Vec<(NbtPath, &'v Value<'static>)> {
+    match value {
+        Value::Compound(map) => map
+            .iter()
+            .map(|(key, child)| (path.clone().key(key.as_ref()), child))
+            .collect(),
+        Value::List(list) => list
+            .iter()
+            .enumerate()
+            .map(|(i, child)| (path.clone().index(i), child))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn collect(
+    value: &Value<'static>,
+    path: &NbtPath,
+    depth: usize,
+    expanded: &HashSet<NbtPath>,
+    rows: &mut Vec<Row>,
+) {
+    for (child_path, child) in children(value, path) {
+        let open = expanded.contains(&child_path);
+        rows.push(Row {
+            label: label(&child_path, child, open),
+            depth,
+            container: child.is_compound() || child.is_list(),
+            path: child_path.clone(),
+        });
+        if open {
+            collect(child, &child_path, depth + 1, expanded, rows);
+        }
+    }
+}
+
+fn walk(value: &Value<'static>, path: &NbtPath, query: &str, nodes: &mut Vec<(NbtPath, bool)>) {
+    for (child_path, child) in children(value, path) {
+        let key_matches = matches!(
+            child_path.last(),
+            Some(PathSegment::Key(key)) if key.to_lowercase().contains(query)
+        );
+        let value_matches = !child.is_compound()
+            && !child.is_list()
+            && !child.is_array()
+            && snbt::to_string(child).to_lowercase().contains(query);
+        nodes.push((child_path.clone(), key_matches || value_matches));
+        walk(child, &child_path, query, nodes);
+    }
+}
+
+fn label(path: &NbtPath, value: &Value<'_>, open: bool) -> String {
+    let name = match path.last() {
+        Some(PathSegment::Key(key)) => key.clone(),
+        Some(PathSegment::Index(i)) => format!("[{}]", i),
+        None => String::new(),
+    };
+    let marker = if open { "v" } else { ">" };
+
+    match value {
+        Value::Compound(map) => format!("{} {}: {{{} entries}}", marker, name, map.len()),
+        Value::List(list) => format!("{} {}: [{} items]", marker, name, list.len()),
+        Value::EmptyList(_) => format!("{} {}: [0 items]", marker, name),
+        Value::ByteArray(bytes) => format!("  {}: [B; {} values]", name, bytes.len()),
+        Value::IntArray(ints) => format!("  {}: [I; {} values]", name, ints.len()),
+        Value::LongArray(longs) => format!("  {}: [L; {} values]", name, longs.len()),
+        other => format!("  {}: {}", name, snbt::to_string(other)),
+    }
+}
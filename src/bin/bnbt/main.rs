@@ -0,0 +1,162 @@
+#[cfg(feature = "tui")]
+mod browse;
+
+use std::{env, io::Write, process::ExitCode};
+
+use bnbt::{
+    codec::{NBTCodec, NBTCodecTrait},
+    codegen::rust_structs,
+    editor::Editor,
+    error::Result,
+    file::NbtFile,
+    path::{NbtPath, PathPattern},
+    snbt,
+    transcode::transcode_to_serde,
+    value::Value,
+};
+
+const USAGE: &str = "usage: bnbt get [--json] <file> <path>
+       bnbt set <file> <path> <snbt-value>
+       bnbt delete <file> <path>
+       bnbt codegen <name> <file>...
+
+set and delete re-encode the whole file, keeping its endianness,
+root name and header but not any non-canonical byte layout.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Snbt,
+    Json,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("bnbt: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct Args<'a> {
+    positional: Vec<&'a str>,
+    format: Format,
+}
+
+impl<'a> Args<'a> {
+    fn parse(args: &'a [String]) -> Option<Self> {
+        let mut parsed = Args {
+            positional: Vec::new(),
+            format: Format::Snbt,
+        };
+
+        let mut args = args.iter().map(String::as_str);
+        while let Some(arg) = args.next() {
+            match arg {
+                "--" => {
+                    parsed.positional.extend(args);
+                    break;
+                }
+                "--json" => parsed.format = Format::Json,
+                flag if flag.starts_with("--") => return None,
+                value => parsed.positional.push(value),
+            }
+        }
+
+        Some(parsed)
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode> {
+    let Some(args) = Args::parse(args) else {
+        return Ok(usage());
+    };
+
+    if args.format == Format::Json && args.positional.first() != Some(&"get") {
+        return Ok(usage());
+    }
+
+    match args.positional.as_slice() {
+        ["get", file, path] => get(file, path, args.format),
+        ["set", file, path, value] => {
+            let value = snbt::from_str(value)?;
+            edit(file, path, |editor, path| editor.set(path, value))
+        }
+        ["delete", file, path] => edit(file, path, |editor, path| editor.remove(path)),
+        ["codegen", name, files @ ..] if !files.is_empty() => codegen(name, files),
+        #[cfg(feature = "tui")]
+        ["browse", file] => {
+            browse::browse(file)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Ok(usage()),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{}", USAGE);
+    #[cfg(feature = "tui")]
+    eprintln!("       bnbt browse <file>");
+    ExitCode::from(2)
+}
+
+fn get(file: &str, path: &str, format: Format) -> Result<ExitCode> {
+    let pattern: PathPattern = path.parse()?;
+    let file = NbtFile::open(file)?;
+
+    let matches = file.value.query(&pattern);
+    if matches.is_empty() {
+        eprintln!("bnbt: no match for {}", path);
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    for (_, value) in matches {
+        match format {
+            Format::Snbt => writeln!(stdout, "{}", snbt::to_string(value))?,
+            Format::Json => {
+                write_json(&mut stdout, value)?;
+                writeln!(stdout)?;
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn codegen(name: &str, files: &[&str]) -> Result<ExitCode> {
+    let samples = files
+        .iter()
+        .map(|file| NbtFile::open(file).map(|file| file.value))
+        .collect::<Result<Vec<_>>>()?;
+
+    print!("{}", rust_structs(name, &samples));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn edit<F>(file: &str, path: &str, op: F) -> Result<ExitCode>
+where
+    F: FnOnce(&mut Editor<'static>, &NbtPath) -> Result<()>,
+{
+    let path: NbtPath = path.parse()?;
+    let mut nbt = NbtFile::open(file)?;
+
+    let mut editor = Editor::new(nbt.value);
+    op(&mut editor, &path)?;
+    nbt.value = editor.into_inner();
+
+    nbt.save(file)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn write_json<W: Write>(writer: &mut W, value: &Value<'_>) -> Result<()> {
+    let codec = NBTCodec::big_endian();
+    let mut bytes = Vec::new();
+    codec.write_tag(&mut bytes, None, value)?;
+
+    let mut serializer = serde_json::Serializer::new(writer);
+    transcode_to_serde(&codec, &mut bytes.as_slice(), &mut serializer)?;
+    Ok(())
+}
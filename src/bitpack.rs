@@ -0,0 +1,66 @@
+use crate::error::{NBTError, Result};
+
+pub fn bits_for(palette_len: usize, min_bits: u32) -> u32 {
+    if palette_len <= 1 {
+        return 0;
+    }
+
+    (usize::BITS - (palette_len - 1).leading_zeros()).max(min_bits)
+}
+
+pub fn packed_len(len: usize, bits: u32) -> usize {
+    if bits == 0 {
+        return 0;
+    }
+
+    len.div_ceil((64 / bits) as usize)
+}
+
+pub fn unpack(longs: &[i64], bits: u32, len: usize) -> Result<Vec<u32>> {
+    if bits == 0 {
+        return Ok(vec![0; len]);
+    }
+    if bits > 32 {
+        return Err(NBTError::custom_msg(format!(
+            "Unsupported bits per entry {}",
+            bits
+        )));
+    }
+
+    let expected = packed_len(len, bits);
+    if longs.len() < expected {
+        return Err(NBTError::custom_msg(format!(
+            "Packed array too short: expected {} longs, got {}",
+            expected,
+            longs.len()
+        )));
+    }
+
+    let per_long = (64 / bits) as usize;
+    let mask = (1u64 << bits) - 1;
+
+    Ok((0..len)
+        .map(|i| {
+            let long = longs[i / per_long] as u64;
+            ((long >> ((i % per_long) as u32 * bits)) & mask) as u32
+        })
+        .collect())
+}
+
+pub fn pack(values: &[u32], bits: u32) -> Vec<i64> {
+    if bits == 0 {
+        return Vec::new();
+    }
+
+    let per_long = (64 / bits) as usize;
+    let mask = (1u64 << bits) - 1;
+
+    values
+        .chunks(per_long)
+        .map(|chunk| {
+            chunk.iter().enumerate().fold(0u64, |long, (i, &value)| {
+                long | ((value as u64 & mask) << (i as u32 * bits))
+            }) as i64
+        })
+        .collect()
+}
@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+
+use bytes::{Buf, BufMut};
+
+use super::{ConfiguredCodec, NBTCodecTrait};
+use crate::{
+    error::{NBTError, Result},
+    value::Value,
+};
+
+impl ConfiguredCodec {
+    pub fn read_tag_buf<B: Buf>(
+        &self,
+        buf: &mut B,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        if buf.chunk().len() < buf.remaining() {
+            return self.read_tag(&mut buf.reader());
+        }
+
+        let mut chunk = buf.chunk();
+        let (name, value) = self.read_tag(&mut chunk)?;
+        let consumed = buf.remaining() - chunk.len();
+        buf.advance(consumed);
+        Ok((name, value))
+    }
+
+    pub fn write_tag_buf<B: BufMut>(
+        &self,
+        buf: &mut B,
+        name: Option<&str>,
+        value: &Value<'_>,
+    ) -> Result<()> {
+        let len = self.encoded_len(name, value);
+        if buf.remaining_mut() < len {
+            return Err(NBTError::custom_msg(format!(
+                "buffer has room for {} bytes, tag needs {}",
+                buf.remaining_mut(),
+                len
+            )));
+        }
+
+        self.write_tag(&mut buf.writer(), name.map(Cow::Borrowed), value)
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    borrow::Cow,
+    io::{BufRead, ErrorKind},
+};
+
+use super::{
+    ConfiguredCodec, Endian, KeyEncoding, NBTCodecTrait, STAGE_BYTES, Session, decoded_list,
+    escape_key,
+};
+use crate::{
+    error::{NBTError, Result},
+    io::CountingReader,
+    tag::{Tag, TagKind},
+    value::{CompoundMap, Value},
+};
+
+impl ConfiguredCodec {
+    pub fn read_tag_buffered<R: BufRead>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        self.session().read_tag_buffered(reader)
+    }
+}
+
+impl Session<'_> {
+    pub fn read_tag_buffered<R: BufRead>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        if self.progress.is_some() {
+            return self.read_tag(reader);
+        }
+
+        self.reset();
+
+        let mut reader = CountingReader::new(reader);
+        let result = self.read_named_tag_buffered(&mut reader);
+
+        result.map_err(|e| e.at_offset(reader.position()))
+    }
+
+    fn read_named_tag_buffered<R: BufRead>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        let kind = self.read_kind(reader)?;
+        let name = self.read_name_buffered(reader)?;
+        let value = self.read_tagged_buffered(reader, kind)?;
+
+        Ok(((!name.is_empty()).then_some(Cow::Owned(name)), value))
+    }
+
+    fn read_tagged_buffered<R: BufRead>(
+        &self,
+        reader: &mut R,
+        kind: TagKind,
+    ) -> Result<Value<'static>> {
+        match kind {
+            TagKind::Known(tag) => self.read_value_buffered(reader, &tag),
+            TagKind::Extension(id) => self.read_unknown_value(reader, id),
+        }
+    }
+
+    fn read_value_buffered<R: BufRead>(&self, reader: &mut R, tag: &Tag) -> Result<Value<'static>> {
+        self.enter_value(tag)?;
+        let value = self
+            .read_payload_buffered(reader, tag)
+            .map_err(|e| e.at_tag(*tag))?;
+        self.leave_value(tag, &value);
+
+        Ok(value)
+    }
+
+    fn read_payload_buffered<R: BufRead>(
+        &self,
+        reader: &mut R,
+        tag: &Tag,
+    ) -> Result<Value<'static>> {
+        Ok(match tag {
+            Tag::String => Value::String(Cow::Owned(self.read_string_buffered(reader)?)),
+            Tag::ByteArray => {
+                let size = self.read_u32(reader)? as usize;
+                let mut bytes = Vec::with_capacity(size.min(STAGE_BYTES));
+                copy_buffered(reader, size, &mut bytes)?;
+                Value::ByteArray(bytes)
+            }
+            Tag::IntArray => {
+                let size = self.read_u32(reader)? as usize;
+                Value::IntArray(match self.endian {
+                    Endian::Big => decode_buffered(reader, size, i32::from_be_bytes)?,
+                    Endian::Little => decode_buffered(reader, size, i32::from_le_bytes)?,
+                })
+            }
+            Tag::LongArray => {
+                let size = self.read_u32(reader)? as usize;
+                Value::LongArray(match self.endian {
+                    Endian::Big => decode_buffered(reader, size, i64::from_be_bytes)?,
+                    Endian::Little => decode_buffered(reader, size, i64::from_le_bytes)?,
+                })
+            }
+            Tag::List => {
+                let element = self.read_kind(reader)?;
+                let length = self.read_i32(reader)?;
+
+                if length < 0 || length > i16::MAX as i32 {
+                    return Err(NBTError::invalid_string_length(length as usize));
+                }
+
+                let mut list = Vec::with_capacity(length as usize);
+                for i in 0..length as usize {
+                    list.push(
+                        self.read_tagged_buffered(reader, element)
+                            .map_err(|e| e.at_index(i))?,
+                    );
+                }
+                decoded_list(element, list)
+            }
+            Tag::Compound => {
+                let mut compound = CompoundMap::new();
+                loop {
+                    let kind = self.read_kind(reader)?;
+                    if kind == Tag::End {
+                        break;
+                    }
+                    self.check_compound_entries(compound.len())?;
+
+                    let name = self.read_name_buffered(reader)?;
+                    let value = self
+                        .read_tagged_buffered(reader, kind)
+                        .map_err(|e| e.at_key(&name))?;
+                    compound.insert(Cow::Owned(name), value);
+                }
+                Value::Compound(compound)
+            }
+            _ => self.read_payload(reader, tag)?,
+        })
+    }
+
+    fn read_name_buffered<R: BufRead>(&self, reader: &mut R) -> Result<String> {
+        match self.key_encoding {
+            KeyEncoding::Utf8 => self.read_string_buffered(reader),
+            KeyEncoding::Escaped => {
+                let length = self.read_u16(reader)? as usize;
+                let mut bytes = Vec::with_capacity(length);
+                copy_buffered(reader, length, &mut bytes)?;
+                Ok(escape_key(&bytes).into_owned())
+            }
+        }
+    }
+
+    fn read_string_buffered<R: BufRead>(&self, reader: &mut R) -> Result<String> {
+        let length = self.read_u16(reader)? as usize;
+
+        let buf = reader.fill_buf()?;
+        if buf.len() >= length {
+            let value = std::str::from_utf8(&buf[..length])?.to_owned();
+            reader.consume(length);
+            return Ok(value);
+        }
+
+        let mut bytes = Vec::with_capacity(length);
+        copy_buffered(reader, length, &mut bytes)?;
+        String::from_utf8(bytes).map_err(|e| e.utf8_error().into())
+    }
+}
+
+fn copy_buffered<R: BufRead>(reader: &mut R, len: usize, out: &mut Vec<u8>) -> Result<()> {
+    let mut remaining = len;
+    while remaining > 0 {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Err(NBTError::unexpected_eof_reading(len));
+        }
+
+        let take = buf.len().min(remaining);
+        out.extend_from_slice(&buf[..take]);
+        reader.consume(take);
+        remaining -= take;
+    }
+
+    Ok(())
+}
+
+fn decode_buffered<R: BufRead, T, const N: usize>(
+    reader: &mut R,
+    size: usize,
+    decode: fn([u8; N]) -> T,
+) -> Result<Vec<T>> {
+    let mut out = Vec::with_capacity(size.min(STAGE_BYTES / N));
+    while out.len() < size {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Err(NBTError::unexpected_eof_reading(size * N));
+        }
+
+        let count = (buf.len() / N).min(size - out.len());
+        if count == 0 {
+            let mut bytes = [0u8; N];
+            reader.read_exact(&mut bytes).map_err(|e| match e.kind() {
+                ErrorKind::UnexpectedEof => NBTError::unexpected_eof_reading(size * N),
+                _ => e.into(),
+            })?;
+            out.push(decode(bytes));
+            continue;
+        }
+
+        out.extend(
+            buf[..count * N]
+                .chunks_exact(N)
+                .map(|bytes| decode(bytes.try_into().unwrap())),
+        );
+        reader.consume(count * N);
+    }
+
+    Ok(out)
+}
@@ -0,0 +1,46 @@
+use std::{
+    borrow::Cow,
+    io::{Read, Write},
+};
+
+use super::NBTCodecTrait;
+use crate::{error::Result, tag::Tag, value::Value};
+
+pub trait DynCodec {
+    fn read_tag_dyn(&self, reader: &mut dyn Read) -> Result<(Option<String>, Value<'static>)>;
+
+    fn write_tag_dyn(
+        &self,
+        writer: &mut dyn Write,
+        name: Option<&str>,
+        value: &Value<'_>,
+    ) -> Result<()>;
+
+    fn read_value_dyn(&self, reader: &mut dyn Read, tag: Tag) -> Result<Value<'static>>;
+
+    fn write_value_dyn(&self, writer: &mut dyn Write, value: &Value<'_>) -> Result<()>;
+}
+
+impl<C: NBTCodecTrait> DynCodec for C {
+    fn read_tag_dyn(&self, mut reader: &mut dyn Read) -> Result<(Option<String>, Value<'static>)> {
+        let (name, value) = self.read_tag(&mut reader)?;
+        Ok((name.map(Cow::into_owned), value.into_owned()))
+    }
+
+    fn write_tag_dyn(
+        &self,
+        mut writer: &mut dyn Write,
+        name: Option<&str>,
+        value: &Value<'_>,
+    ) -> Result<()> {
+        self.write_tag(&mut writer, name.map(Cow::Borrowed), value)
+    }
+
+    fn read_value_dyn(&self, mut reader: &mut dyn Read, tag: Tag) -> Result<Value<'static>> {
+        Ok(self.read_value(&mut reader, &tag)?.into_owned())
+    }
+
+    fn write_value_dyn(&self, mut writer: &mut dyn Write, value: &Value<'_>) -> Result<()> {
+        self.write_value(&mut writer, value)
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    io::{Read, Write},
+    sync::Arc,
+};
+
+use super::{ConfiguredCodec, NBTCodec, NBTCodecTrait, read_bytes};
+use crate::{
+    error::{NBTError, Result},
+    tag::{Tag, TagKind},
+};
+
+pub trait TagExtension: Send + Sync {
+    fn read_payload(&self, codec: &ConfiguredCodec, reader: &mut dyn Read) -> Result<Vec<u8>>;
+
+    fn write_payload(
+        &self,
+        _codec: &ConfiguredCodec,
+        writer: &mut dyn Write,
+        payload: &[u8],
+    ) -> Result<()> {
+        writer.write_all(payload)?;
+        Ok(())
+    }
+}
+
+impl<F> TagExtension for F
+where
+    F: Fn(&ConfiguredCodec, &mut dyn Read) -> Result<Vec<u8>> + Send + Sync,
+{
+    fn read_payload(&self, codec: &ConfiguredCodec, reader: &mut dyn Read) -> Result<Vec<u8>> {
+        self(codec, reader)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct TagExtensions(BTreeMap<u8, Arc<dyn TagExtension>>);
+
+impl TagExtensions {
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    pub fn get(&self, id: u8) -> Option<&dyn TagExtension> {
+        self.0.get(&id).map(Arc::as_ref)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.keys().copied()
+    }
+}
+
+impl Debug for TagExtensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TagExtensions")
+            .field(&self.0.keys())
+            .finish()
+    }
+}
+
+type LengthHintFn = dyn Fn(u8) -> Option<usize> + Send + Sync;
+
+#[derive(Clone)]
+pub struct LengthHint(pub Arc<LengthHintFn>);
+
+impl Debug for LengthHint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LengthHint(..)")
+    }
+}
+
+impl NBTCodec {
+    pub fn with_unknown_tag_length<F>(self, hint: F) -> ConfiguredCodec
+    where
+        F: Fn(u8) -> Option<usize> + Send + Sync + 'static,
+    {
+        self.configure().with_unknown_tag_length(hint)
+    }
+
+    pub fn with_tag_extension<E: TagExtension + 'static>(
+        self,
+        id: u8,
+        extension: E,
+    ) -> ConfiguredCodec {
+        self.configure().with_tag_extension(id, extension)
+    }
+}
+
+impl ConfiguredCodec {
+    pub fn with_unknown_tag_length<F>(mut self, hint: F) -> Self
+    where
+        F: Fn(u8) -> Option<usize> + Send + Sync + 'static,
+    {
+        self.unknown_tag_length = Some(LengthHint(Arc::new(hint)));
+        self
+    }
+
+    pub(crate) fn accepts_unknown_id(&self, id: u8) -> bool {
+        self.extensions.get(id).is_some() || self.unknown_length(id).is_some()
+    }
+
+    pub(crate) fn unknown_length(&self, id: u8) -> Option<usize> {
+        self.unknown_tag_length.as_ref().and_then(|hint| hint.0(id))
+    }
+
+    pub(crate) fn read_kind<R: Read>(&self, reader: &mut R) -> Result<TagKind> {
+        let id = self.read_u8(reader)?;
+        match Tag::try_from(id) {
+            Ok(tag) => Ok(TagKind::Known(tag)),
+            Err(_) if self.accepts_unknown_id(id) => Ok(TagKind::Extension(id)),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) fn read_unknown_payload<R: Read>(&self, reader: &mut R, id: u8) -> Result<Vec<u8>> {
+        if let Some(extension) = self.extensions.get(id) {
+            return extension.read_payload(self, reader);
+        }
+
+        match self.unknown_length(id) {
+            Some(len) => {
+                let mut payload = Vec::new();
+                read_bytes(reader, len, &mut payload)?;
+                Ok(payload)
+            }
+            None => Err(NBTError::invalid_tag_id(id)),
+        }
+    }
+
+    pub fn with_tag_extension<E: TagExtension + 'static>(mut self, id: u8, extension: E) -> Self {
+        self.extensions.0.insert(id, Arc::new(extension));
+        self
+    }
+}
@@ -0,0 +1,188 @@
+use std::{borrow::Cow, io::Read};
+
+use super::{ConfiguredCodec, Endian, NBTCodecTrait, Session, decoded_list};
+use crate::{
+    error::{NBTError, Result},
+    io::CountingReader,
+    map::{CompoundOps, MapBackend},
+    tag::{Tag, TagKind},
+    value::Value,
+};
+
+impl ConfiguredCodec {
+    pub fn read_tag_into<R: Read>(
+        &self,
+        reader: &mut R,
+        target: &mut Value<'static>,
+    ) -> Result<Option<String>> {
+        self.session().read_tag_into_as(reader, target)
+    }
+
+    pub fn read_tag_into_as<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        target: &mut Value<'static, M>,
+    ) -> Result<Option<String>> {
+        self.session().read_tag_into_as(reader, target)
+    }
+}
+
+impl Session<'_> {
+    pub fn read_tag_into<R: Read>(
+        &self,
+        reader: &mut R,
+        target: &mut Value<'static>,
+    ) -> Result<Option<String>> {
+        self.read_tag_into_as(reader, target)
+    }
+
+    pub fn read_tag_into_as<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        target: &mut Value<'static, M>,
+    ) -> Result<Option<String>> {
+        self.reset();
+
+        let mut reader = CountingReader::new(reader);
+        let result = match &self.progress {
+            Some(progress) => {
+                let mut reader =
+                    super::progress::ProgressReader::new(&mut reader, progress.0.as_ref());
+                self.read_named_tag_into(&mut reader, target)
+            }
+            None => self.read_named_tag_into(&mut reader, target),
+        };
+
+        result.map_err(|e| e.at_offset(reader.position()))
+    }
+
+    fn read_named_tag_into<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        target: &mut Value<'static, M>,
+    ) -> Result<Option<String>> {
+        let kind = self.read_kind(reader)?;
+        let name = self.read_name(reader)?;
+        self.read_value_into(reader, kind, target)?;
+
+        Ok((!name.is_empty()).then_some(name))
+    }
+
+    fn read_value_into<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        kind: TagKind,
+        target: &mut Value<'static, M>,
+    ) -> Result<()> {
+        let tag = match kind {
+            TagKind::Known(tag) => tag,
+            TagKind::Extension(id) => {
+                *target = self.read_unknown_value(reader, id)?;
+                return Ok(());
+            }
+        };
+        self.enter_value(&tag)?;
+        self.read_payload_into(reader, &tag, target)
+            .map_err(|e| e.at_tag(tag))?;
+        self.leave_value(&tag, target);
+        Ok(())
+    }
+
+    fn read_payload_into<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        tag: &Tag,
+        target: &mut Value<'static, M>,
+    ) -> Result<()> {
+        match (tag, &mut *target) {
+            (Tag::Compound, Value::Compound(map)) => {
+                let mut old = std::mem::take(map);
+
+                loop {
+                    let kind = self.read_kind(reader)?;
+                    if kind == Tag::End {
+                        break;
+                    }
+                    self.check_compound_entries(map.len())?;
+
+                    let name = self.read_name(reader)?;
+                    let (key, mut slot) = match old.take_entry(name.as_str()) {
+                        Some(entry) => entry,
+                        None => (Cow::Owned(name), Value::End),
+                    };
+                    self.read_value_into(reader, kind, &mut slot)
+                        .map_err(|e| e.at_key(&key))?;
+                    map.insert(key, slot);
+                }
+            }
+            (Tag::List, Value::List(list)) => {
+                let element = self.read_kind(reader)?;
+                let length = self.read_i32(reader)?;
+
+                if length < 0 || length > i16::MAX as i32 {
+                    return Err(NBTError::invalid_string_length(length as usize));
+                }
+
+                list.truncate(length as usize);
+                for (i, slot) in list.iter_mut().enumerate() {
+                    self.read_value_into(reader, element, slot)
+                        .map_err(|e| e.at_index(i))?;
+                }
+                for i in list.len()..length as usize {
+                    let mut slot = Value::End;
+                    self.read_value_into(reader, element, &mut slot)
+                        .map_err(|e| e.at_index(i))?;
+                    list.push(slot);
+                }
+                if list.is_empty() {
+                    *target = decoded_list(element, Vec::new());
+                }
+            }
+            (Tag::String, Value::String(Cow::Owned(s))) => {
+                let length = self.read_u16(reader)? as usize;
+                let mut scratch = self.scratch.borrow_mut();
+                scratch.clear();
+                scratch.resize(length, 0);
+                super::read_exact(reader, &mut scratch)?;
+
+                s.clear();
+                s.push_str(std::str::from_utf8(&scratch)?);
+            }
+            (Tag::ByteArray, Value::ByteArray(bytes)) => {
+                let size = self.read_u32(reader)? as usize;
+                super::read_bytes(reader, size, bytes)?;
+            }
+            (Tag::IntArray, Value::IntArray(ints)) => {
+                let size = self.read_u32(reader)? as usize;
+                match self.endian {
+                    Endian::Big => self.read_staged_into(reader, size, i32::from_be_bytes, ints)?,
+                    Endian::Little => {
+                        self.read_staged_into(reader, size, i32::from_le_bytes, ints)?
+                    }
+                }
+            }
+            (Tag::LongArray, Value::LongArray(longs)) => {
+                let size = self.read_u32(reader)? as usize;
+                match self.endian {
+                    Endian::Big => {
+                        self.read_staged_into(reader, size, i64::from_be_bytes, longs)?
+                    }
+                    Endian::Little => {
+                        self.read_staged_into(reader, size, i64::from_le_bytes, longs)?
+                    }
+                }
+            }
+            (Tag::Compound, _) => {
+                *target = Value::Compound(M::Map::default());
+                self.read_payload_into(reader, tag, target)?;
+            }
+            (Tag::List, _) => {
+                *target = Value::List(Vec::new());
+                self.read_payload_into(reader, tag, target)?;
+            }
+            _ => *target = self.read_payload(reader, tag)?,
+        }
+
+        Ok(())
+    }
+}
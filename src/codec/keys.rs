@@ -0,0 +1,115 @@
+use std::{
+    borrow::Cow,
+    io::{Read, Write},
+};
+
+use super::{ConfiguredCodec, KeyEncoding, MAX_STRING_LEN, NBTCodecTrait, Session, read_exact};
+use crate::error::{NBTError, Result};
+
+const ESCAPE_BASE: u32 = 0x10FF00;
+
+fn is_escape(c: char) -> bool {
+    (ESCAPE_BASE..=ESCAPE_BASE + 0xFF).contains(&(c as u32))
+}
+
+fn push_escaped(out: &mut String, bytes: &[u8]) {
+    for &byte in bytes {
+        out.push(char::from_u32(ESCAPE_BASE + byte as u32).expect("escape range is valid"));
+    }
+}
+
+pub fn escape_key(bytes: &[u8]) -> Cow<'_, str> {
+    if let Ok(key) = std::str::from_utf8(bytes)
+        && !key.chars().any(is_escape)
+    {
+        return Cow::Borrowed(key);
+    }
+
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if is_escape(c) {
+                push_escaped(&mut out, c.encode_utf8(&mut [0; 4]).as_bytes());
+            } else {
+                out.push(c);
+            }
+        }
+        push_escaped(&mut out, chunk.invalid());
+    }
+    Cow::Owned(out)
+}
+
+pub fn key_bytes(key: &str) -> Cow<'_, [u8]> {
+    if !key.chars().any(is_escape) {
+        return Cow::Borrowed(key.as_bytes());
+    }
+
+    let mut out = Vec::with_capacity(key.len());
+    for c in key.chars() {
+        if is_escape(c) {
+            out.push((c as u32 - ESCAPE_BASE) as u8);
+        } else {
+            out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+    }
+    Cow::Owned(out)
+}
+
+impl Session<'_> {
+    pub(crate) fn read_name<R: Read>(&self, reader: &mut R) -> Result<String> {
+        match self.key_encoding {
+            KeyEncoding::Utf8 => self.read_string(reader),
+            KeyEncoding::Escaped => {
+                let length = self.read_u16(reader)? as usize;
+                let mut bytes = vec![0u8; length];
+                read_exact(reader, &mut bytes)?;
+                match escape_key(&bytes) {
+                    Cow::Borrowed(_) => Ok(String::from_utf8(bytes)?),
+                    Cow::Owned(key) => Ok(key),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn read_name_with<R: Read, T>(
+        &self,
+        reader: &mut R,
+        f: impl FnOnce(&str) -> T,
+    ) -> Result<T> {
+        let length = self.read_u16(reader)? as usize;
+
+        let mut scratch = self.scratch.borrow_mut();
+        scratch.clear();
+        scratch.resize(length, 0);
+        read_exact(reader, &mut scratch)?;
+
+        match self.key_encoding {
+            KeyEncoding::Utf8 => Ok(f(std::str::from_utf8(&scratch)?)),
+            KeyEncoding::Escaped => Ok(f(&escape_key(&scratch))),
+        }
+    }
+}
+
+impl ConfiguredCodec {
+    pub(crate) fn write_name<W: Write>(&self, writer: &mut W, name: &str) -> Result<()> {
+        match self.key_encoding {
+            KeyEncoding::Utf8 => self.write_string(writer, name),
+            KeyEncoding::Escaped => {
+                let bytes = key_bytes(name);
+                if bytes.len() > MAX_STRING_LEN {
+                    return Err(NBTError::invalid_string_length(bytes.len()));
+                }
+                self.write_u16(writer, bytes.len() as u16)?;
+                writer.write_all(&bytes)?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn name_len(&self, name: &str) -> usize {
+        match self.key_encoding {
+            KeyEncoding::Utf8 => self.string_len(name),
+            KeyEncoding::Escaped => 2 + key_bytes(name).len(),
+        }
+    }
+}
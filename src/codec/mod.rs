@@ -1,14 +1,45 @@
+#[cfg(feature = "bytes")]
+mod buf;
+mod buffered;
+mod dynamic;
+mod extension;
+mod inplace;
+mod keys;
+mod partial;
+mod progress;
+mod projection;
+mod session;
+mod size;
+mod skip;
+mod split;
+mod stats;
+mod vectored;
+
+pub use dynamic::DynCodec;
+pub use extension::{LengthHint, TagExtension, TagExtensions};
+pub use keys::{escape_key, key_bytes};
+pub use partial::Decision;
+pub use progress::{Progress, ProgressHook};
+pub use session::Session;
+pub use stats::Stats;
+
 use paste::paste;
 
 use crate::{
     error::{NBTError, Result},
-    tag::Tag,
+    io::CountingReader,
+    map::{CompoundOps, MapBackend},
+    tag::{Tag, TagKind},
     value::Value,
 };
 use std::{
     borrow::Cow,
-    collections::BTreeMap,
     io::{Read, Write},
+    ops::Deref,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -18,11 +49,35 @@ pub enum Endian {
     Little,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StringPolicy {
+    #[default]
+    Error,
+    Truncate,
+    Chunk,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyEncoding {
+    #[default]
+    Utf8,
+    Escaped,
+}
+
+pub const MAX_STRING_LEN: usize = u16::MAX as usize;
+
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+pub(crate) const STAGE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct NBTCodec {
     pub endian: Endian,
 }
 
+static BIG_ENDIAN: ConfiguredCodec = ConfiguredCodec::new(Endian::Big);
+static LITTLE_ENDIAN: ConfiguredCodec = ConfiguredCodec::new(Endian::Little);
+
 impl NBTCodec {
     pub fn new(endian: Endian) -> Self {
         Self { endian }
@@ -35,6 +90,665 @@ impl NBTCodec {
     pub fn little_endian() -> Self {
         Self::new(Endian::Little)
     }
+
+    pub fn configure(self) -> ConfiguredCodec {
+        ConfiguredCodec::new(self.endian)
+    }
+
+    pub fn with_progress<H: ProgressHook + 'static>(self, hook: H) -> ConfiguredCodec {
+        self.configure().with_progress(hook)
+    }
+
+    pub fn with_cancellation(self, token: Arc<AtomicBool>) -> ConfiguredCodec {
+        self.configure().with_cancellation(token)
+    }
+
+    pub fn with_string_policy(self, policy: StringPolicy) -> ConfiguredCodec {
+        self.configure().with_string_policy(policy)
+    }
+
+    pub fn with_key_encoding(self, encoding: KeyEncoding) -> ConfiguredCodec {
+        self.configure().with_key_encoding(encoding)
+    }
+
+    pub fn with_max_compound_entries(self, limit: usize) -> ConfiguredCodec {
+        self.configure().with_max_compound_entries(limit)
+    }
+
+    pub fn with_max_total_tags(self, limit: u64) -> ConfiguredCodec {
+        self.configure().with_max_total_tags(limit)
+    }
+
+    pub fn with_max_depth(self, limit: usize) -> ConfiguredCodec {
+        self.configure().with_max_depth(limit)
+    }
+
+    pub fn without_max_depth(self) -> ConfiguredCodec {
+        self.configure().without_max_depth()
+    }
+}
+
+impl Deref for NBTCodec {
+    type Target = ConfiguredCodec;
+
+    fn deref(&self) -> &ConfiguredCodec {
+        match self.endian {
+            Endian::Big => &BIG_ENDIAN,
+            Endian::Little => &LITTLE_ENDIAN,
+        }
+    }
+}
+
+impl From<NBTCodec> for ConfiguredCodec {
+    fn from(codec: NBTCodec) -> Self {
+        codec.configure()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfiguredCodec {
+    pub endian: Endian,
+    pub string_policy: StringPolicy,
+    pub key_encoding: KeyEncoding,
+    pub progress: Option<Progress>,
+    pub cancellation: Option<Arc<AtomicBool>>,
+    pub max_compound_entries: Option<usize>,
+    pub max_total_tags: Option<u64>,
+    pub max_depth: Option<usize>,
+    pub extensions: TagExtensions,
+    pub unknown_tag_length: Option<LengthHint>,
+}
+
+impl Default for ConfiguredCodec {
+    fn default() -> Self {
+        Self::new(Endian::default())
+    }
+}
+
+impl ConfiguredCodec {
+    pub const fn new(endian: Endian) -> Self {
+        Self {
+            endian,
+            string_policy: StringPolicy::Error,
+            key_encoding: KeyEncoding::Utf8,
+            progress: None,
+            cancellation: None,
+            max_compound_entries: None,
+            max_total_tags: None,
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            extensions: TagExtensions::new(),
+            unknown_tag_length: None,
+        }
+    }
+
+    pub fn session(&self) -> Session<'_> {
+        Session::new(self)
+    }
+
+    pub fn with_progress<H: ProgressHook + 'static>(mut self, hook: H) -> Self {
+        self.progress = Some(Progress::new(hook));
+        self
+    }
+
+    pub fn with_cancellation(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn with_string_policy(mut self, policy: StringPolicy) -> Self {
+        self.string_policy = policy;
+        self
+    }
+
+    pub fn with_key_encoding(mut self, encoding: KeyEncoding) -> Self {
+        self.key_encoding = encoding;
+        self
+    }
+
+    pub fn with_max_compound_entries(mut self, limit: usize) -> Self {
+        self.max_compound_entries = Some(limit);
+        self
+    }
+
+    pub fn with_max_total_tags(mut self, limit: u64) -> Self {
+        self.max_total_tags = Some(limit);
+        self
+    }
+
+    pub fn with_max_depth(mut self, limit: usize) -> Self {
+        self.max_depth = Some(limit);
+        self
+    }
+
+    pub fn without_max_depth(mut self) -> Self {
+        self.max_depth = None;
+        self
+    }
+
+    pub(crate) fn check_compound_entries(&self, entries: usize) -> Result<()> {
+        match self.max_compound_entries {
+            Some(limit) if entries >= limit => Err(NBTError::too_many_entries(limit)),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_depth(&self, depth: usize) -> Result<()> {
+        match self.max_depth {
+            Some(limit) if depth > limit => Err(NBTError::too_deep(limit)),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_list<M: MapBackend>(
+        &self,
+        values: &[Value<'_, M>],
+        element_tag: TagKind,
+    ) -> Result<()> {
+        for (i, value) in values.iter().enumerate() {
+            if let Value::String(v) = value
+                && self.wire_tag(value) != Tag::String
+            {
+                return Err(NBTError::string_too_long(v.len()));
+            }
+            if value.tag() != element_tag {
+                return Err(NBTError::custom_msg(format!(
+                    "List type mismatch at index {}: expected {:?}, got {:?}",
+                    i,
+                    element_tag,
+                    value.tag()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn wire_tag<M: MapBackend>(&self, value: &Value<'_, M>) -> TagKind {
+        match value {
+            Value::String(v)
+                if self.string_policy == StringPolicy::Chunk && v.len() > MAX_STRING_LEN =>
+            {
+                TagKind::Known(Tag::List)
+            }
+            _ => value.tag(),
+        }
+    }
+
+    fn wire_id<M: MapBackend>(&self, value: &Value<'_, M>) -> u8 {
+        self.wire_tag(value).id()
+    }
+
+    fn write_unknown<W: Write>(&self, mut writer: &mut W, id: u8, payload: &[u8]) -> Result<()> {
+        match self.extensions.get(id) {
+            Some(extension) => extension.write_payload(self, &mut writer, payload),
+            None => {
+                writer.write_all(payload)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn fit_string<'s>(&self, value: &'s str) -> Result<&'s str> {
+        if value.len() <= MAX_STRING_LEN {
+            return Ok(value);
+        }
+
+        match self.string_policy {
+            StringPolicy::Truncate => {
+                let end = floor_char_boundary(value, MAX_STRING_LEN);
+                if let Some(progress) = &self.progress {
+                    progress.0.on_warning(&format!(
+                        "string of {} bytes truncated to {} bytes",
+                        value.len(),
+                        end
+                    ));
+                }
+                Ok(&value[..end])
+            }
+            StringPolicy::Error | StringPolicy::Chunk => {
+                Err(NBTError::string_too_long(value.len()))
+            }
+        }
+    }
+
+    fn write_string_chunks<W: Write>(&self, writer: &mut W, value: &str) -> Result<()> {
+        let mut chunks = Vec::new();
+        let mut rest = value;
+        while !rest.is_empty() {
+            let end = floor_char_boundary(rest, MAX_STRING_LEN);
+            chunks.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+
+        self.write_i8(writer, Tag::String as i8)?;
+        self.write_i32(writer, chunks.len() as i32)?;
+        for chunk in chunks {
+            self.write_string(writer, chunk)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read_tag_with_stats<R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>, Stats)> {
+        self.session().read_tag_with_stats(reader)
+    }
+
+    pub fn read_tag_as<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static, M>)> {
+        self.session().read_tag_as(reader)
+    }
+
+    pub fn write_tag_as<W: Write, M: MapBackend>(
+        &self,
+        writer: &mut W,
+        name: Option<Cow<'_, str>>,
+        value: &Value<'_, M>,
+    ) -> Result<()> {
+        self.write_u8(writer, self.wire_id(value))?;
+
+        let wraped_name = match name {
+            Some(n) => n.into_owned(),
+            None => String::new(),
+        };
+
+        self.write_name(writer, &wraped_name)?;
+
+        self.write_value_as(writer, value)?;
+
+        Ok(())
+    }
+
+    fn write_value_as<W: Write, M: MapBackend>(
+        &self,
+        writer: &mut W,
+        value: &Value<'_, M>,
+    ) -> Result<()> {
+        match value {
+            Value::End => Ok(()),
+            Value::Byte(v) => self.write_i8(writer, *v),
+            Value::Short(v) => self.write_i16(writer, *v),
+            Value::Int(v) => self.write_i32(writer, *v),
+            Value::Long(v) => self.write_i64(writer, *v),
+            Value::Float(v) => self.write_f32(writer, *v),
+            Value::Double(v) => self.write_f64(writer, *v),
+            Value::ByteArray(v) => self.write_byte_array(writer, v),
+            Value::String(v) if self.wire_tag(value) == Tag::List => {
+                self.write_string_chunks(writer, v.as_ref())
+            }
+            Value::String(v) => self.write_string(writer, v.as_ref()),
+            Value::IntArray(v) => self.write_int_array(writer, v),
+            Value::LongArray(v) => self.write_long_array(writer, v),
+            Value::List(_) => self.write_list_value(writer, value),
+            Value::EmptyList(tag) => self.write_values_as::<W, M>(writer, &[], (*tag).into()),
+            Value::Compound(_) => self.write_compound_value(writer, value),
+            Value::Unknown(id, payload) => self.write_unknown(writer, *id, payload),
+        }
+    }
+
+    fn write_values_as<W: Write, M: MapBackend>(
+        &self,
+        writer: &mut W,
+        values: &[Value<'_, M>],
+        element_tag: TagKind,
+    ) -> Result<()> {
+        self.check_list(values, element_tag)?;
+
+        self.write_u8(writer, element_tag.id())?;
+        self.write_i32(writer, values.len() as i32)?;
+
+        for value in values {
+            self.write_value_as(writer, value)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_list_value<W: Write, M: MapBackend>(
+        &self,
+        writer: &mut W,
+        value: &Value<'_, M>,
+    ) -> Result<()> {
+        let Value::List(list) = value else {
+            return Err(NBTError::invalid_tag_id(value.tag_id()));
+        };
+
+        let element_tag = list.first().map_or(TagKind::Known(Tag::End), Value::tag);
+        self.write_values_as(writer, list, element_tag)
+    }
+
+    fn write_compound_value<W: Write, M: MapBackend>(
+        &self,
+        writer: &mut W,
+        value: &Value<'_, M>,
+    ) -> Result<()> {
+        let Value::Compound(map) = value else {
+            return Err(NBTError::invalid_tag_id(value.tag_id()));
+        };
+
+        for (name, val) in map.iter() {
+            self.write_u8(writer, self.wire_id(val))?;
+
+            self.write_name(writer, name.as_ref())?;
+
+            self.write_value_as(writer, val)?;
+        }
+
+        self.write_i8(writer, Tag::End as i8)?;
+        Ok(())
+    }
+}
+
+impl Session<'_> {
+    pub fn read_tag<R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        self.read_tag_as(reader)
+    }
+
+    pub fn read_tag_as<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static, M>)> {
+        self.reset();
+
+        let mut reader = CountingReader::new(reader);
+        let result = match &self.progress {
+            Some(progress) => {
+                let mut reader = progress::ProgressReader::new(&mut reader, progress.0.as_ref());
+                self.read_named_tag(&mut reader)
+            }
+            None => self.read_named_tag(&mut reader),
+        };
+
+        result.map_err(|e| e.at_offset(reader.position()))
+    }
+
+    pub fn read_tag_with_stats<R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>, Stats)> {
+        *self.stats.borrow_mut() = Some(Stats::default());
+        let result = self.read_tag(reader);
+        let stats = self.stats.take().unwrap_or_default();
+
+        let (name, value) = result?;
+        Ok((name, value, stats))
+    }
+
+    fn read_payload<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        tag: &Tag,
+    ) -> Result<Value<'static, M>> {
+        match tag {
+            Tag::End => Ok(Value::End),
+            Tag::Byte => Ok(Value::Byte(self.read_i8(reader)?)),
+            Tag::Short => Ok(Value::Short(self.read_i16(reader)?)),
+            Tag::Int => Ok(Value::Int(self.read_i32(reader)?)),
+            Tag::Long => Ok(Value::Long(self.read_i64(reader)?)),
+            Tag::Float => Ok(Value::Float(self.read_f32(reader)?)),
+            Tag::Double => Ok(Value::Double(self.read_f64(reader)?)),
+            Tag::ByteArray => Ok(Value::ByteArray(self.read_byte_array(reader)?)),
+            Tag::String => Ok(Value::String(Cow::Owned(self.read_string(reader)?))),
+            Tag::List => Ok(self.read_list(reader)?),
+            Tag::Compound => Ok(self.read_compound(reader)?),
+            Tag::IntArray => Ok(Value::IntArray(self.read_int_array(reader)?)),
+            Tag::LongArray => Ok(Value::LongArray(self.read_long_array(reader)?)),
+        }
+    }
+
+    fn enter_value(&self, tag: &Tag) -> Result<()> {
+        self.count_value()?;
+
+        self.record(|stats| stats.tags[*tag as usize] += 1);
+
+        if matches!(tag, Tag::List | Tag::Compound) {
+            let depth = self.depth.get() + 1;
+            self.check_depth(depth)?;
+            self.depth.set(depth);
+            self.record(|stats| stats.max_depth = stats.max_depth.max(depth));
+        }
+
+        Ok(())
+    }
+
+    fn count_value(&self) -> Result<()> {
+        let tags = self.tags.get() + 1;
+        if let Some(limit) = self.max_total_tags
+            && tags > limit
+        {
+            return Err(NBTError::too_many_tags(limit));
+        }
+        self.tags.set(tags);
+
+        if let Some(progress) = &self.progress {
+            progress.0.on_tag(tags);
+        }
+
+        if let Some(token) = &self.cancellation
+            && token.load(Ordering::Relaxed)
+        {
+            return Err(NBTError::cancelled());
+        }
+
+        Ok(())
+    }
+
+    fn leave_value<M: MapBackend>(&self, tag: &Tag, value: &Value<'_, M>) {
+        if matches!(tag, Tag::List | Tag::Compound) {
+            self.depth.set(self.depth.get() - 1);
+        }
+
+        self.record(|stats| match value {
+            Value::String(v) => stats.string_bytes += v.len() as u64,
+            Value::ByteArray(v) => stats.largest_byte_array = stats.largest_byte_array.max(v.len()),
+            Value::IntArray(v) => stats.largest_int_array = stats.largest_int_array.max(v.len()),
+            Value::LongArray(v) => stats.largest_long_array = stats.largest_long_array.max(v.len()),
+            _ => {}
+        });
+    }
+
+    fn read_named_tag<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static, M>)> {
+        let kind = self.read_kind(reader)?;
+
+        let name = self.read_name(reader)?;
+
+        let name_opt = if !name.is_empty() {
+            Some(Cow::Owned(name))
+        } else {
+            None
+        };
+
+        let value = self.read_tagged(reader, kind)?;
+
+        Ok((name_opt, value))
+    }
+
+    pub(crate) fn read_value<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        tag: &Tag,
+    ) -> Result<Value<'static, M>> {
+        self.enter_value(tag)?;
+        let value = self.read_payload(reader, tag).map_err(|e| e.at_tag(*tag))?;
+        self.leave_value(tag, &value);
+
+        Ok(value)
+    }
+
+    pub(crate) fn read_string<R: Read>(&self, reader: &mut R) -> Result<String> {
+        let length = self.read_u16(reader)?;
+
+        let mut bytes = vec![0u8; length as usize];
+        read_exact(reader, &mut bytes)?;
+
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    pub(crate) fn read_list<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Value<'static, M>> {
+        let element = self.read_kind(reader)?;
+        let length = self.read_i32(reader)?;
+
+        if length < 0 || length > i16::MAX as i32 {
+            return Err(NBTError::invalid_string_length(length as usize));
+        }
+
+        let mut list = Vec::with_capacity(length as usize);
+        for i in 0..length as usize {
+            list.push(
+                self.read_tagged(reader, element)
+                    .map_err(|e| e.at_index(i))?,
+            );
+        }
+
+        Ok(decoded_list(element, list))
+    }
+
+    pub(crate) fn read_compound<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Value<'static, M>> {
+        let mut compound = M::Map::default();
+
+        loop {
+            let kind = self.read_kind(reader)?;
+            if kind == Tag::End {
+                break;
+            }
+            self.check_compound_entries(compound.len())?;
+
+            let name = self.read_name(reader)?;
+            self.record(|stats| stats.string_bytes += name.len() as u64);
+            let value = self
+                .read_tagged(reader, kind)
+                .map_err(|e| e.at_key(&name))?;
+
+            compound.insert(Cow::Owned(name), value);
+        }
+
+        Ok(Value::Compound(compound))
+    }
+
+    pub(crate) fn read_int_array<R: Read>(&self, reader: &mut R) -> Result<Vec<i32>> {
+        let size = self.read_u32(reader)? as usize;
+        match self.endian {
+            Endian::Big => self.read_staged(reader, size, i32::from_be_bytes),
+            Endian::Little => self.read_staged(reader, size, i32::from_le_bytes),
+        }
+    }
+
+    pub(crate) fn read_long_array<R: Read>(&self, reader: &mut R) -> Result<Vec<i64>> {
+        let size = self.read_u32(reader)? as usize;
+        match self.endian {
+            Endian::Big => self.read_staged(reader, size, i64::from_be_bytes),
+            Endian::Little => self.read_staged(reader, size, i64::from_le_bytes),
+        }
+    }
+
+    pub(crate) fn read_tagged<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        kind: TagKind,
+    ) -> Result<Value<'static, M>> {
+        match kind {
+            TagKind::Known(tag) => self.read_value(reader, &tag),
+            TagKind::Extension(id) => self.read_unknown_value(reader, id),
+        }
+    }
+
+    pub(crate) fn read_unknown_value<M: MapBackend, R: Read>(
+        &self,
+        reader: &mut R,
+        id: u8,
+    ) -> Result<Value<'static, M>> {
+        self.count_value()?;
+        Ok(Value::Unknown(id, self.read_unknown_payload(reader, id)?))
+    }
+
+    fn read_staged<R: Read, T, const N: usize>(
+        &self,
+        reader: &mut R,
+        size: usize,
+        decode: fn([u8; N]) -> T,
+    ) -> Result<Vec<T>> {
+        let mut out = Vec::new();
+        self.read_staged_into(reader, size, decode, &mut out)?;
+        Ok(out)
+    }
+
+    fn read_staged_into<R: Read, T, const N: usize>(
+        &self,
+        reader: &mut R,
+        size: usize,
+        decode: fn([u8; N]) -> T,
+        out: &mut Vec<T>,
+    ) -> Result<()> {
+        let mut scratch = self.scratch.borrow_mut();
+        out.clear();
+        out.reserve(size.min(STAGE_BYTES / N));
+        let mut remaining = size;
+        while remaining > 0 {
+            let len = remaining.min(STAGE_BYTES / N);
+            scratch.clear();
+            scratch.resize(len * N, 0);
+            read_exact(reader, &mut scratch)?;
+
+            out.extend(
+                scratch
+                    .chunks_exact(N)
+                    .map(|bytes| decode(bytes.try_into().unwrap())),
+            );
+            remaining -= len;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn decoded_list<M: MapBackend>(
+    element: TagKind,
+    list: Vec<Value<'static, M>>,
+) -> Value<'static, M> {
+    match element {
+        TagKind::Known(tag) if list.is_empty() && tag != Tag::End => Value::EmptyList(tag),
+        _ => Value::List(list),
+    }
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<()> {
+    reader.read_exact(buf).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => NBTError::unexpected_eof_reading(buf.len()),
+        _ => e.into(),
+    })
+}
+
+pub(crate) fn read_bytes<R: Read>(reader: &mut R, size: usize, out: &mut Vec<u8>) -> Result<()> {
+    out.clear();
+    out.reserve(size.min(STAGE_BYTES));
+    reader.take(size as u64).read_to_end(out)?;
+    if out.len() != size {
+        return Err(NBTError::unexpected_eof_reading(size));
+    }
+    Ok(())
+}
+
+fn floor_char_boundary(value: &str, index: usize) -> usize {
+    (0..=index.min(value.len()))
+        .rev()
+        .find(|&i| value.is_char_boundary(i))
+        .unwrap_or(0)
 }
 
 macro_rules! gen_nbt_codec_trait {
@@ -60,7 +774,10 @@ macro_rules! gen_simple {
 }
 
 pub trait NBTCodecTrait {
-    fn read_tag<R: Read>(&self, reader: &mut R) -> Result<(Option<Cow<'_, str>>, Value<'_>)>;
+    fn read_tag<R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)>;
 
     fn write_tag<W: Write>(
         &self,
@@ -69,19 +786,44 @@ pub trait NBTCodecTrait {
         value: &Value<'_>,
     ) -> Result<()>;
 
-    fn read_value<R: Read>(&self, reader: &mut R, tag: &Tag) -> Result<Value<'_>>;
+    fn read_value<R: Read>(&self, reader: &mut R, tag: &Tag) -> Result<Value<'static>>;
 
     fn write_value<W: Write>(&self, writer: &mut W, value: &Value<'_>) -> Result<()>;
 
     gen_nbt_codec_trait!(
         string: String, &str;
-        list: Value<'_>, &Value<'_>;
-        compound: Value<'_>, &Value<'_>;
-        byte_array: Vec<i8>, &[i8];
+        list: Value<'static>, &Value<'_>;
+        compound: Value<'static>, &Value<'_>;
+        byte_array: Vec<u8>, &[u8];
         int_array: Vec<i32>, &[i32];
         long_array: Vec<i64>, &[i64];
     );
 
+    fn write_list_as<W: Write>(
+        &self,
+        writer: &mut W,
+        values: &[Value<'_>],
+        element_tag: Tag,
+    ) -> Result<()>;
+
+    fn read_byte_array_chunked<R: Read, F: FnMut(&[u8])>(
+        &self,
+        reader: &mut R,
+        f: F,
+    ) -> Result<usize>;
+
+    fn read_int_array_chunked<R: Read, F: FnMut(&[i32])>(
+        &self,
+        reader: &mut R,
+        f: F,
+    ) -> Result<usize>;
+
+    fn read_long_array_chunked<R: Read, F: FnMut(&[i64])>(
+        &self,
+        reader: &mut R,
+        f: F,
+    ) -> Result<usize>;
+
     gen_simple!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
 }
 
@@ -91,7 +833,7 @@ macro_rules! gen_nbt_codec_impl {
             paste! {
                 fn [<read_ $name>]<R: Read>(&self, reader: &mut R) -> Result<$read_ty> {
                     let mut buf = [0u8; std::mem::size_of::<$read_ty>()];
-                    reader.read_exact(&mut buf)?;
+                    read_exact(reader, &mut buf)?;
 
                     match self.endian {
                         Endian::Big => Ok($read_ty::from_be_bytes(buf)),
@@ -120,21 +862,12 @@ macro_rules! gen_simple_impl {
     };
 }
 
-impl NBTCodecTrait for NBTCodec {
-    fn read_tag<R: Read>(&self, reader: &mut R) -> Result<(Option<Cow<'_, str>>, Value<'_>)> {
-        let tag = Tag::try_from(self.read_u8(reader)?)?;
-
-        let name = self.read_string(reader)?;
-
-        let name_opt = if !name.is_empty() {
-            Some(Cow::Owned(name))
-        } else {
-            None
-        };
-
-        let value = self.read_value(reader, &tag)?;
-
-        Ok((name_opt, value))
+impl NBTCodecTrait for ConfiguredCodec {
+    fn read_tag<R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        self.session().read_tag(reader)
     }
 
     fn write_tag<W: Write>(
@@ -143,177 +876,69 @@ impl NBTCodecTrait for NBTCodec {
         name: Option<Cow<'_, str>>,
         value: &Value<'_>,
     ) -> Result<()> {
-        self.write_u8(writer, value.tag() as u8)?;
-
-        let wraped_name = match name {
-            Some(n) => n.into_owned(),
-            None => String::new(),
-        };
-
-        self.write_string(writer, &wraped_name)?;
-
-        self.write_value(writer, value)?;
-
-        Ok(())
+        self.write_tag_as(writer, name, value)
     }
 
-    fn read_value<R: Read>(&self, reader: &mut R, tag: &Tag) -> Result<Value<'_>> {
-        match tag {
-            Tag::End => Ok(Value::End),
-            Tag::Byte => Ok(Value::Byte(self.read_i8(reader)?)),
-            Tag::Short => Ok(Value::Short(self.read_i16(reader)?)),
-            Tag::Int => Ok(Value::Int(self.read_i32(reader)?)),
-            Tag::Long => Ok(Value::Long(self.read_i64(reader)?)),
-            Tag::Float => Ok(Value::Float(self.read_f32(reader)?)),
-            Tag::Double => Ok(Value::Double(self.read_f64(reader)?)),
-            Tag::ByteArray => Ok(Value::ByteArray(self.read_byte_array(reader)?)),
-            Tag::String => Ok(Value::String(Cow::Owned(self.read_string(reader)?))),
-            Tag::List => Ok(self.read_list(reader)?),
-            Tag::Compound => Ok(self.read_compound(reader)?),
-            Tag::IntArray => Ok(Value::IntArray(self.read_int_array(reader)?)),
-            Tag::LongArray => Ok(Value::LongArray(self.read_long_array(reader)?)),
-        }
+    fn read_value<R: Read>(&self, reader: &mut R, tag: &Tag) -> Result<Value<'static>> {
+        self.session().read_value(reader, tag)
     }
 
     fn write_value<W: Write>(&self, writer: &mut W, value: &Value<'_>) -> Result<()> {
-        match value {
-            Value::End => Ok(()),
-            Value::Byte(v) => self.write_i8(writer, *v),
-            Value::Short(v) => self.write_i16(writer, *v),
-            Value::Int(v) => self.write_i32(writer, *v),
-            Value::Long(v) => self.write_i64(writer, *v),
-            Value::Float(v) => self.write_f32(writer, *v),
-            Value::Double(v) => self.write_f64(writer, *v),
-            Value::ByteArray(v) => self.write_byte_array(writer, v),
-            Value::String(v) => self.write_string(writer, v.as_ref()),
-            Value::IntArray(v) => self.write_int_array(writer, v),
-            Value::LongArray(v) => self.write_long_array(writer, v),
-            Value::List(_) => self.write_list(writer, value),
-            Value::Compound(_) => self.write_compound(writer, value),
-        }
+        self.write_value_as(writer, value)
     }
 
     fn read_string<R: Read>(&self, reader: &mut R) -> Result<String> {
-        let length = self.read_u16(reader)?;
-
-        let mut buf = vec![0u8; length as usize];
-        reader.read_exact(&mut buf)?;
-
-        Ok(String::from_utf8(buf)?)
+        self.session().read_string(reader)
     }
 
     fn write_string<W: Write>(&self, writer: &mut W, value: &str) -> Result<()> {
+        let value = self.fit_string(value)?;
         self.write_u16(writer, value.len() as u16)?;
         writer.write_all(value.as_bytes())?;
         Ok(())
     }
 
-    fn read_list<R: Read>(&self, reader: &mut R) -> Result<Value<'_>> {
-        let element_tag_id = self.read_i8(reader)?;
-        let element_tag = Tag::try_from(element_tag_id as u8)?;
-        let length = self.read_i32(reader)?;
-
-        if length < 0 || length > i16::MAX as i32 {
-            return Err(NBTError::invalid_string_length(length as usize));
-        }
-
-        let mut list = Vec::with_capacity(length as usize);
-        for _ in 0..length {
-            list.push(self.read_value(reader, &element_tag)?);
-        }
-
-        Ok(Value::List(list))
+    fn read_list<R: Read>(&self, reader: &mut R) -> Result<Value<'static>> {
+        self.session().read_list(reader)
     }
 
     fn write_list<W: Write>(&self, writer: &mut W, value: &Value<'_>) -> Result<()> {
-        if let Value::List(list) = value {
-            if list.is_empty() {
-                self.write_i8(writer, Tag::End as i8)?;
-                self.write_i32(writer, 0)?;
-                return Ok(());
-            }
-
-            let first_tag = list[0].tag();
-            for (i, value) in list.iter().enumerate() {
-                if value.tag() != first_tag {
-                    return Err(NBTError::custom_msg(format!(
-                        "List type mismatch at index {}: expected {:?}, got {:?}",
-                        i,
-                        first_tag,
-                        value.tag()
-                    )));
-                }
-            }
-
-            self.write_i8(writer, first_tag as i8)?;
-            self.write_i32(writer, list.len() as i32)?;
-
-            for value in list {
-                self.write_value(writer, value)?;
-            }
-
-            return Ok(());
-        }
-
-        Err(NBTError::invalid_tag_id(value.tag() as u8))
+        self.write_list_value(writer, value)
     }
 
-    fn read_compound<R: Read>(&self, reader: &mut R) -> Result<Value<'_>> {
-        let mut compound = BTreeMap::new();
-
-        loop {
-            let tag_id = self.read_i8(reader)?;
-            let tag = Tag::try_from(tag_id as u8)?;
-
-            if tag == Tag::End {
-                break;
-            }
-
-            let name = self.read_string(reader)?;
-            let value = self.read_value(reader, &tag)?;
-
-            compound.insert(Cow::Owned(name), value);
-        }
+    fn write_list_as<W: Write>(
+        &self,
+        writer: &mut W,
+        values: &[Value<'_>],
+        element_tag: Tag,
+    ) -> Result<()> {
+        self.write_values_as(writer, values, element_tag.into())
+    }
 
-        Ok(Value::Compound(compound))
+    fn read_compound<R: Read>(&self, reader: &mut R) -> Result<Value<'static>> {
+        self.session().read_compound(reader)
     }
 
     fn write_compound<W: Write>(&self, writer: &mut W, value: &Value<'_>) -> Result<()> {
-        let Value::Compound(map) = value else {
-            return Err(NBTError::invalid_tag_id(value.tag() as u8));
-        };
-
-        for (name, val) in map {
-            self.write_i8(writer, val.tag() as i8)?;
-
-            self.write_string(writer, name.as_ref())?;
-
-            self.write_value(writer, val)?;
-        }
-
-        self.write_i8(writer, Tag::End as i8)?;
-        Ok(())
+        self.write_compound_value(writer, value)
     }
 
-    fn read_byte_array<R: Read>(&self, reader: &mut R) -> Result<Vec<i8>> {
+    fn read_byte_array<R: Read>(&self, reader: &mut R) -> Result<Vec<u8>> {
         let size = self.read_u32(reader)? as usize;
-        let mut buf = vec![0u8; size];
-        reader.read_exact(&mut buf)?;
-        Ok(buf.into_iter().map(|b| b as i8).collect())
+        let mut buf = Vec::new();
+        read_bytes(reader, size, &mut buf)?;
+        Ok(buf)
     }
 
-    fn write_byte_array<W: Write>(&self, writer: &mut W, value: &[i8]) -> Result<()> {
+    fn write_byte_array<W: Write>(&self, writer: &mut W, value: &[u8]) -> Result<()> {
         self.write_u32(writer, value.len() as u32)?;
-
-        let bytes: Vec<u8> = value.iter().map(|&b| b as u8).collect();
-        writer.write_all(&bytes)?;
+        writer.write_all(value)?;
 
         Ok(())
     }
 
     fn read_int_array<R: Read>(&self, reader: &mut R) -> Result<Vec<i32>> {
-        let size = self.read_u32(reader)? as usize;
-        (0..size).map(|_| self.read_i32(reader)).collect()
+        self.session().read_int_array(reader)
     }
 
     fn write_int_array<W: Write>(&self, writer: &mut W, value: &[i32]) -> Result<()> {
@@ -331,8 +956,7 @@ impl NBTCodecTrait for NBTCodec {
     }
 
     fn read_long_array<R: Read>(&self, reader: &mut R) -> Result<Vec<i64>> {
-        let size = self.read_u32(reader)? as usize;
-        (0..size).map(|_| self.read_i64(reader)).collect()
+        self.session().read_long_array(reader)
     }
 
     fn write_long_array<W: Write>(&self, writer: &mut W, value: &[i64]) -> Result<()> {
@@ -349,9 +973,170 @@ impl NBTCodecTrait for NBTCodec {
         Ok(())
     }
 
+    fn read_byte_array_chunked<R: Read, F: FnMut(&[u8])>(
+        &self,
+        reader: &mut R,
+        mut f: F,
+    ) -> Result<usize> {
+        let size = self.read_u32(reader)? as usize;
+
+        const CHUNK_SIZE: usize = 4096;
+
+        let mut buf = [0u8; CHUNK_SIZE];
+        let mut remaining = size;
+        while remaining > 0 {
+            let len = remaining.min(CHUNK_SIZE);
+            read_exact(reader, &mut buf[..len])?;
+            f(&buf[..len]);
+            remaining -= len;
+        }
+
+        Ok(size)
+    }
+
+    fn read_int_array_chunked<R: Read, F: FnMut(&[i32])>(
+        &self,
+        reader: &mut R,
+        mut f: F,
+    ) -> Result<usize> {
+        let size = self.read_u32(reader)? as usize;
+
+        const CHUNK_SIZE: usize = 1024;
+
+        let mut buf = [0i32; CHUNK_SIZE];
+        let mut remaining = size;
+        while remaining > 0 {
+            let len = remaining.min(CHUNK_SIZE);
+            for slot in &mut buf[..len] {
+                *slot = self.read_i32(reader)?;
+            }
+            f(&buf[..len]);
+            remaining -= len;
+        }
+
+        Ok(size)
+    }
+
+    fn read_long_array_chunked<R: Read, F: FnMut(&[i64])>(
+        &self,
+        reader: &mut R,
+        mut f: F,
+    ) -> Result<usize> {
+        let size = self.read_u32(reader)? as usize;
+
+        const CHUNK_SIZE: usize = 512;
+
+        let mut buf = [0i64; CHUNK_SIZE];
+        let mut remaining = size;
+        while remaining > 0 {
+            let len = remaining.min(CHUNK_SIZE);
+            for slot in &mut buf[..len] {
+                *slot = self.read_i64(reader)?;
+            }
+            f(&buf[..len]);
+            remaining -= len;
+        }
+
+        Ok(size)
+    }
+
     gen_simple_impl!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
 }
 
+macro_rules! forward_nbt_codec_impl {
+    ($($name:ident: $read_ty:ty, $write_ty:ty);* $(;)?) => {
+        $(
+            paste! {
+                fn [<read_ $name>]<R: Read>(&self, reader: &mut R) -> Result<$read_ty> {
+                    (**self).[<read_ $name>](reader)
+                }
+
+                fn [<write_ $name>]<W: Write>(&self, writer: &mut W, value: $write_ty) -> Result<()> {
+                    (**self).[<write_ $name>](writer, value)
+                }
+            }
+        )*
+    };
+}
+
+impl NBTCodecTrait for NBTCodec {
+    fn read_tag<R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        (**self).read_tag(reader)
+    }
+
+    fn write_tag<W: Write>(
+        &self,
+        writer: &mut W,
+        name: Option<Cow<'_, str>>,
+        value: &Value<'_>,
+    ) -> Result<()> {
+        (**self).write_tag(writer, name, value)
+    }
+
+    fn read_value<R: Read>(&self, reader: &mut R, tag: &Tag) -> Result<Value<'static>> {
+        (**self).read_value(reader, tag)
+    }
+
+    fn write_value<W: Write>(&self, writer: &mut W, value: &Value<'_>) -> Result<()> {
+        (**self).write_value(writer, value)
+    }
+
+    fn write_list_as<W: Write>(
+        &self,
+        writer: &mut W,
+        values: &[Value<'_>],
+        element_tag: Tag,
+    ) -> Result<()> {
+        (**self).write_list_as(writer, values, element_tag)
+    }
+
+    fn read_byte_array_chunked<R: Read, F: FnMut(&[u8])>(
+        &self,
+        reader: &mut R,
+        f: F,
+    ) -> Result<usize> {
+        (**self).read_byte_array_chunked(reader, f)
+    }
+
+    fn read_int_array_chunked<R: Read, F: FnMut(&[i32])>(
+        &self,
+        reader: &mut R,
+        f: F,
+    ) -> Result<usize> {
+        (**self).read_int_array_chunked(reader, f)
+    }
+
+    fn read_long_array_chunked<R: Read, F: FnMut(&[i64])>(
+        &self,
+        reader: &mut R,
+        f: F,
+    ) -> Result<usize> {
+        (**self).read_long_array_chunked(reader, f)
+    }
+
+    forward_nbt_codec_impl!(
+        string: String, &str;
+        list: Value<'static>, &Value<'_>;
+        compound: Value<'static>, &Value<'_>;
+        byte_array: Vec<u8>, &[u8];
+        int_array: Vec<i32>, &[i32];
+        long_array: Vec<i64>, &[i64];
+        i8: i8, i8;
+        u8: u8, u8;
+        i16: i16, i16;
+        u16: u16, u16;
+        i32: i32, i32;
+        u32: u32, u32;
+        i64: i64, i64;
+        u64: u64, u64;
+        f32: f32, f32;
+        f64: f64, f64;
+    );
+}
+
 mod tests {
 
     #[test]
@@ -0,0 +1,82 @@
+use std::{borrow::Cow, io::Read};
+
+use super::{ConfiguredCodec, Session};
+use crate::{
+    error::{NBTError, Result},
+    io::CountingReader,
+    tag::Tag,
+    value::{CompoundMap, Value},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Take,
+    Skip,
+    Stop,
+}
+
+impl ConfiguredCodec {
+    pub fn read_compound_until<R: Read, F: FnMut(&str) -> Decision>(
+        &self,
+        reader: &mut R,
+        decide: F,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        self.session().read_compound_until(reader, decide)
+    }
+}
+
+impl Session<'_> {
+    fn read_compound_until<R: Read, F: FnMut(&str) -> Decision>(
+        &self,
+        reader: &mut R,
+        mut decide: F,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        let mut reader = CountingReader::new(reader);
+        let result = self.read_named_compound_until(&mut reader, &mut decide);
+
+        result.map_err(|e| e.at_offset(reader.position()))
+    }
+
+    fn read_named_compound_until<R: Read, F: FnMut(&str) -> Decision>(
+        &self,
+        reader: &mut R,
+        decide: &mut F,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        let kind = self.read_kind(reader)?;
+        if kind != Tag::Compound {
+            return Err(NBTError::custom_msg(format!(
+                "expected a Compound root, found {:?}",
+                kind
+            )));
+        }
+
+        let name = self.read_name(reader)?;
+        let mut compound = CompoundMap::new();
+
+        loop {
+            let kind = self.read_kind(reader)?;
+            if kind == Tag::End {
+                break;
+            }
+            self.check_compound_entries(compound.len())?;
+
+            let decision = self.read_name_with(reader, |key| match decide(key) {
+                Decision::Take => Ok(key.to_owned()),
+                other => Err(other),
+            })?;
+            match decision {
+                Ok(key) => {
+                    let value = self.read_tagged(reader, kind).map_err(|e| e.at_key(&key))?;
+                    compound.insert(Cow::Owned(key), value);
+                }
+                Err(Decision::Stop) => break,
+                Err(_) => self.skip_kind(reader, kind)?,
+            }
+        }
+
+        Ok((
+            (!name.is_empty()).then_some(Cow::Owned(name)),
+            Value::Compound(compound),
+        ))
+    }
+}
@@ -0,0 +1,65 @@
+use std::{fmt::Debug, io::Read, sync::Arc};
+
+pub trait ProgressHook: Send + Sync {
+    fn on_bytes_read(&self, _total: u64) {}
+
+    fn on_tag(&self, _total: u64) {}
+
+    fn on_warning(&self, _message: &str) {}
+}
+
+impl<H: ProgressHook + ?Sized> ProgressHook for Arc<H> {
+    fn on_bytes_read(&self, total: u64) {
+        (**self).on_bytes_read(total)
+    }
+
+    fn on_tag(&self, total: u64) {
+        (**self).on_tag(total)
+    }
+
+    fn on_warning(&self, message: &str) {
+        (**self).on_warning(message)
+    }
+}
+
+#[derive(Clone)]
+pub struct Progress(pub Arc<dyn ProgressHook>);
+
+impl Progress {
+    pub fn new<H: ProgressHook + 'static>(hook: H) -> Self {
+        Self(Arc::new(hook))
+    }
+}
+
+impl Debug for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Progress(..)")
+    }
+}
+
+pub(crate) struct ProgressReader<'h, R> {
+    inner: R,
+    hook: &'h dyn ProgressHook,
+    total: u64,
+}
+
+impl<'h, R: Read> ProgressReader<'h, R> {
+    pub(crate) fn new(inner: R, hook: &'h dyn ProgressHook) -> Self {
+        Self {
+            inner,
+            hook,
+            total: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read > 0 {
+            self.total += read as u64;
+            self.hook.on_bytes_read(self.total);
+        }
+        Ok(read)
+    }
+}
@@ -0,0 +1,231 @@
+use std::{borrow::Cow, collections::BTreeMap, io::Read};
+
+use super::{ConfiguredCodec, NBTCodecTrait, Session};
+use crate::{
+    error::{NBTError, Result},
+    io::CountingReader,
+    path::{NbtPath, PathPattern, PathSegment, PatternSegment},
+    tag::{Tag, TagKind},
+    value::{CompoundMap, Value},
+};
+
+#[derive(Debug, Clone, Default)]
+struct Selection {
+    whole: bool,
+    keys: BTreeMap<String, Selection>,
+    indices: BTreeMap<usize, Selection>,
+    any_key: Option<Box<Selection>>,
+    any_index: Option<Box<Selection>>,
+}
+
+impl Selection {
+    fn new(paths: &[NbtPath]) -> Self {
+        let mut root = Selection::default();
+        for path in paths {
+            let mut node = &mut root;
+            for segment in &path.segments {
+                node = match segment {
+                    PathSegment::Key(key) => node.keys.entry(key.clone()).or_default(),
+                    PathSegment::Index(index) => node.indices.entry(*index).or_default(),
+                };
+            }
+            node.whole = true;
+        }
+        root
+    }
+
+    fn from_patterns(patterns: &[PathPattern]) -> Result<Self> {
+        let mut root = Selection::default();
+        for pattern in patterns {
+            let mut node = &mut root;
+            for segment in &pattern.segments {
+                node = match segment {
+                    PatternSegment::Key(key) => node.keys.entry(key.clone()).or_default(),
+                    PatternSegment::Index(index) => node.indices.entry(*index).or_default(),
+                    PatternSegment::AnyKey => node.any_key.get_or_insert_default(),
+                    PatternSegment::AnyIndex => node.any_index.get_or_insert_default(),
+                    PatternSegment::Deep => {
+                        return Err(NBTError::invalid_path(
+                            "'**' is not supported in projections",
+                        ));
+                    }
+                };
+            }
+            node.whole = true;
+        }
+        Ok(root)
+    }
+
+    fn merge(&mut self, other: &Selection) {
+        self.whole |= other.whole;
+        for (key, child) in &other.keys {
+            self.keys.entry(key.clone()).or_default().merge(child);
+        }
+        for (index, child) in &other.indices {
+            self.indices.entry(*index).or_default().merge(child);
+        }
+        if let Some(child) = &other.any_key {
+            self.any_key.get_or_insert_default().merge(child);
+        }
+        if let Some(child) = &other.any_index {
+            self.any_index.get_or_insert_default().merge(child);
+        }
+    }
+
+    fn child<'s>(
+        exact: Option<&'s Selection>,
+        any: Option<&'s Selection>,
+    ) -> Option<Cow<'s, Selection>> {
+        match (exact, any) {
+            (Some(exact), Some(any)) => {
+                let mut merged = exact.clone();
+                merged.merge(any);
+                Some(Cow::Owned(merged))
+            }
+            (Some(child), None) | (None, Some(child)) => Some(Cow::Borrowed(child)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl ConfiguredCodec {
+    pub fn read_projection<R: Read>(
+        &self,
+        reader: &mut R,
+        paths: &[NbtPath],
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        self.session()
+            .read_selection(reader, &Selection::new(paths))
+    }
+
+    pub fn read_pattern_projection<R: Read>(
+        &self,
+        reader: &mut R,
+        patterns: &[PathPattern],
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        self.session()
+            .read_selection(reader, &Selection::from_patterns(patterns)?)
+    }
+}
+
+impl Session<'_> {
+    fn read_selection<R: Read>(
+        &self,
+        reader: &mut R,
+        selection: &Selection,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        let mut reader = CountingReader::new(reader);
+        let result = self.read_named_projection(&mut reader, selection);
+
+        result.map_err(|e| e.at_offset(reader.position()))
+    }
+
+    fn read_named_projection<R: Read>(
+        &self,
+        reader: &mut R,
+        selection: &Selection,
+    ) -> Result<(Option<Cow<'static, str>>, Value<'static>)> {
+        let kind = self.read_kind(reader)?;
+        let name = self.read_name(reader)?;
+        let value = self
+            .project(reader, kind, selection)?
+            .unwrap_or_else(|| placeholder(kind));
+
+        Ok(((!name.is_empty()).then_some(Cow::Owned(name)), value))
+    }
+
+    fn project<R: Read>(
+        &self,
+        reader: &mut R,
+        kind: TagKind,
+        selection: &Selection,
+    ) -> Result<Option<Value<'static>>> {
+        if selection.whole {
+            return self.read_tagged(reader, kind).map(Some);
+        }
+
+        match kind {
+            TagKind::Known(Tag::Compound)
+                if !selection.keys.is_empty() || selection.any_key.is_some() =>
+            {
+                let mut compound = CompoundMap::new();
+                loop {
+                    let kind = self.read_kind(reader)?;
+                    if kind == Tag::End {
+                        break;
+                    }
+
+                    let child = self.read_name_with(reader, |key| {
+                        Selection::child(selection.keys.get(key), selection.any_key.as_deref())
+                            .map(|child| (key.to_owned(), child))
+                    })?;
+                    match child {
+                        Some((key, child)) => {
+                            let value = self
+                                .project(reader, kind, &child)
+                                .map_err(|e| e.at_key(&key))?;
+                            if let Some(value) = value {
+                                compound.insert(Cow::Owned(key), value);
+                            }
+                        }
+                        None => self.skip_kind(reader, kind)?,
+                    }
+                }
+                Ok((!compound.is_empty()).then_some(Value::Compound(compound)))
+            }
+            TagKind::Known(Tag::List)
+                if !selection.indices.is_empty() || selection.any_index.is_some() =>
+            {
+                let element = self.read_kind(reader)?;
+                let length = self.read_i32(reader)?.max(0) as usize;
+
+                let mut list = Vec::new();
+                let any = selection.any_index.as_deref();
+                for index in 0..length {
+                    match Selection::child(selection.indices.get(&index), any) {
+                        Some(child) => {
+                            let value = self
+                                .project(reader, element, &child)
+                                .map_err(|e| e.at_index(index))?;
+                            if let Some(value) = value {
+                                list.resize_with(index, || placeholder(element));
+                                list.push(value);
+                            }
+                        }
+                        None => self.skip_kind(reader, element)?,
+                    }
+                }
+                if !list.is_empty() && any.is_some() {
+                    list.resize_with(length, || placeholder(element));
+                }
+                Ok((!list.is_empty()).then_some(Value::List(list)))
+            }
+            _ => {
+                self.skip_kind(reader, kind)?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn placeholder(kind: TagKind) -> Value<'static> {
+    let tag = match kind {
+        TagKind::Known(tag) => tag,
+        TagKind::Extension(id) => return Value::Unknown(id, Vec::new()),
+    };
+    match tag {
+        Tag::End => Value::End,
+        Tag::Byte => Value::Byte(0),
+        Tag::Short => Value::Short(0),
+        Tag::Int => Value::Int(0),
+        Tag::Long => Value::Long(0),
+        Tag::Float => Value::Float(0.0),
+        Tag::Double => Value::Double(0.0),
+        Tag::ByteArray => Value::ByteArray(Vec::new()),
+        Tag::String => Value::String(Cow::Borrowed("")),
+        Tag::List => Value::EmptyList(Tag::End),
+        Tag::Compound => Value::compound(),
+        Tag::IntArray => Value::IntArray(Vec::new()),
+        Tag::LongArray => Value::LongArray(Vec::new()),
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    ops::Deref,
+};
+
+use super::{ConfiguredCodec, stats::Stats};
+
+pub struct Session<'c> {
+    codec: &'c ConfiguredCodec,
+    pub(crate) tags: Cell<u64>,
+    pub(crate) depth: Cell<usize>,
+    pub(crate) stats: RefCell<Option<Stats>>,
+    pub(crate) scratch: RefCell<Vec<u8>>,
+}
+
+impl<'c> Session<'c> {
+    pub(crate) fn new(codec: &'c ConfiguredCodec) -> Self {
+        Self {
+            codec,
+            tags: Cell::new(0),
+            depth: Cell::new(0),
+            stats: RefCell::new(None),
+            scratch: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn codec(&self) -> &'c ConfiguredCodec {
+        self.codec
+    }
+
+    pub fn release_buffers(&self) {
+        *self.scratch.borrow_mut() = Vec::new();
+    }
+
+    pub(crate) fn reset(&self) {
+        self.tags.set(0);
+        self.depth.set(0);
+    }
+
+    pub(crate) fn record<F: FnOnce(&mut Stats)>(&self, f: F) {
+        if let Some(stats) = self.stats.borrow_mut().as_mut() {
+            f(stats);
+        }
+    }
+}
+
+impl Deref for Session<'_> {
+    type Target = ConfiguredCodec;
+
+    fn deref(&self) -> &ConfiguredCodec {
+        self.codec
+    }
+}
+
+impl Debug for Session<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session")
+            .field("codec", self.codec)
+            .field("tags", &self.tags.get())
+            .finish_non_exhaustive()
+    }
+}
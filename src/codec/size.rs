@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+
+use super::{ConfiguredCodec, MAX_STRING_LEN, NBTCodecTrait, StringPolicy, floor_char_boundary};
+use crate::{error::Result, tag::Tag, value::Value};
+
+impl ConfiguredCodec {
+    pub fn encoded_len(&self, name: Option<&str>, value: &Value<'_>) -> usize {
+        1 + self.name_len(name.unwrap_or_default()) + self.payload_len(value)
+    }
+
+    pub fn to_vec_exact(&self, name: Option<&str>, value: &Value<'_>) -> Result<Vec<u8>> {
+        let len = self.encoded_len(name, value);
+        let mut buf = Vec::with_capacity(len);
+        self.write_tag(&mut buf, name.map(Cow::Borrowed), value)?;
+        debug_assert_eq!(buf.len(), len);
+
+        Ok(buf)
+    }
+
+    pub(crate) fn string_len(&self, value: &str) -> usize {
+        if value.len() <= MAX_STRING_LEN {
+            return 2 + value.len();
+        }
+
+        match self.string_policy {
+            StringPolicy::Truncate => 2 + floor_char_boundary(value, MAX_STRING_LEN),
+            StringPolicy::Error | StringPolicy::Chunk => 2 + value.len(),
+        }
+    }
+
+    fn payload_len(&self, value: &Value<'_>) -> usize {
+        match value {
+            Value::End => 0,
+            Value::Byte(_) => 1,
+            Value::Short(_) => 2,
+            Value::Int(_) | Value::Float(_) => 4,
+            Value::Long(_) | Value::Double(_) => 8,
+            Value::ByteArray(v) => 4 + v.len(),
+            Value::Unknown(_, v) => v.len(),
+            Value::IntArray(v) => 4 + v.len() * 4,
+            Value::LongArray(v) => 4 + v.len() * 8,
+            Value::String(v) if self.wire_tag(value) == Tag::List => {
+                let mut len = 5;
+                let mut rest = v.as_ref();
+                while !rest.is_empty() {
+                    let end = floor_char_boundary(rest, MAX_STRING_LEN);
+                    len += 2 + end;
+                    rest = &rest[end..];
+                }
+                len
+            }
+            Value::String(v) => self.string_len(v),
+            Value::List(list) => 5 + list.iter().map(|v| self.payload_len(v)).sum::<usize>(),
+            Value::EmptyList(_) => 5,
+            Value::Compound(map) => {
+                1 + map
+                    .iter()
+                    .map(|(k, v)| 1 + self.name_len(k) + self.payload_len(v))
+                    .sum::<usize>()
+            }
+        }
+    }
+}
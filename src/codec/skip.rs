@@ -0,0 +1,126 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use super::{ConfiguredCodec, NBTCodecTrait};
+use crate::{
+    error::{NBTError, Result},
+    tag::{Tag, TagKind},
+};
+
+type Advance<R> = fn(&mut R, u64) -> Result<()>;
+
+fn discard<R: Read>(reader: &mut R, len: u64) -> Result<()> {
+    let copied = std::io::copy(&mut reader.by_ref().take(len), &mut std::io::sink())?;
+    if copied < len {
+        return Err(NBTError::unexpected_eof_reading(len as usize));
+    }
+    Ok(())
+}
+
+fn seek_forward<R: Read + Seek>(reader: &mut R, len: u64) -> Result<()> {
+    if len == 0 {
+        return Ok(());
+    }
+
+    let offset =
+        i64::try_from(len - 1).map_err(|_| NBTError::unexpected_eof_reading(len as usize))?;
+    reader.seek(SeekFrom::Current(offset))?;
+
+    let mut last = [0u8; 1];
+    reader.read_exact(&mut last).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => NBTError::unexpected_eof_reading(len as usize),
+        _ => e.into(),
+    })
+}
+
+impl ConfiguredCodec {
+    pub fn skip_value<R: Read>(&self, reader: &mut R, tag: Tag) -> Result<()> {
+        self.skip_payload(reader, tag as u8, discard::<R>, 0)
+    }
+
+    pub(crate) fn skip_kind<R: Read>(&self, reader: &mut R, kind: TagKind) -> Result<()> {
+        self.skip_payload(reader, kind.id(), discard::<R>, 0)
+    }
+
+    pub fn skip_value_seek<R: Read + Seek>(&self, reader: &mut R, tag: Tag) -> Result<()> {
+        self.skip_payload(reader, tag as u8, seek_forward::<R>, 0)
+    }
+
+    fn skip_payload<R: Read>(
+        &self,
+        reader: &mut R,
+        id: u8,
+        advance: Advance<R>,
+        depth: usize,
+    ) -> Result<()> {
+        let tag = match Tag::try_from(id) {
+            Ok(tag) => tag,
+            Err(_) => return self.skip_unknown(reader, id, advance),
+        };
+
+        if let Some(size) = tag.fixed_payload_size() {
+            return advance(reader, size as u64);
+        }
+
+        match tag {
+            Tag::String => {
+                let length = self.read_u16(reader)?;
+                advance(reader, length as u64)
+            }
+            Tag::ByteArray | Tag::IntArray | Tag::LongArray => {
+                let length = self.read_u32(reader)? as u64;
+                let element = tag
+                    .array_element_tag()
+                    .and_then(Tag::fixed_payload_size)
+                    .unwrap_or(1);
+                advance(reader, length * element as u64)
+            }
+            Tag::List => {
+                self.check_depth(depth + 1)?;
+                let element = self.read_u8(reader)?;
+                let length = self.read_i32(reader)?;
+                if length < 0 {
+                    return Err(NBTError::invalid_string_length(length as usize));
+                }
+
+                match Tag::try_from(element)
+                    .ok()
+                    .and_then(Tag::fixed_payload_size)
+                {
+                    Some(size) => advance(reader, length as u64 * size as u64),
+                    None => {
+                        for i in 0..length as usize {
+                            self.skip_payload(reader, element, advance, depth + 1)
+                                .map_err(|e| e.at_index(i))?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            Tag::Compound => {
+                self.check_depth(depth + 1)?;
+                loop {
+                    let id = self.read_u8(reader)?;
+                    if id == Tag::End as u8 {
+                        return Ok(());
+                    }
+                    let length = self.read_u16(reader)?;
+                    advance(reader, length as u64)?;
+                    self.skip_payload(reader, id, advance, depth + 1)?;
+                }
+            }
+            _ => unreachable!("fixed-size tags are handled above"),
+        }
+    }
+
+    fn skip_unknown<R: Read>(&self, reader: &mut R, id: u8, advance: Advance<R>) -> Result<()> {
+        if let Some(extension) = self.extensions.get(id) {
+            extension.read_payload(self, reader)?;
+            return Ok(());
+        }
+
+        match self.unknown_length(id) {
+            Some(len) => advance(reader, len as u64),
+            None => Err(NBTError::invalid_tag_id(id)),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::ops::Range;
+
+use super::{ConfiguredCodec, NBTCodec, NBTCodecTrait};
+use crate::{error::Result, tag::Tag, value::Value};
+
+impl ConfiguredCodec {
+    pub fn parse_prefix<'d>(&self, bytes: &'d [u8]) -> Result<(Value<'static>, &'d [u8])> {
+        let mut rest = bytes;
+        let (_, value) = self
+            .read_tag(&mut rest)
+            .map_err(|e| e.at_offset((bytes.len() - rest.len()) as u64))?;
+        Ok((value.into_owned(), rest))
+    }
+
+    pub fn document_ranges(&self, bytes: &[u8]) -> Result<Vec<Range<usize>>> {
+        let mut ranges = Vec::new();
+        let mut rest = bytes;
+
+        while !rest.is_empty() {
+            let start = bytes.len() - rest.len();
+            self.skip_document(&mut rest)
+                .map_err(|e| e.at_offset((bytes.len() - rest.len()) as u64))?;
+            ranges.push(start..bytes.len() - rest.len());
+        }
+
+        Ok(ranges)
+    }
+
+    fn skip_document(&self, reader: &mut &[u8]) -> Result<()> {
+        let kind = self.read_kind(reader)?;
+        self.skip_value(reader, Tag::String)?;
+        self.skip_kind(reader, kind)
+    }
+}
+
+impl Value<'_> {
+    pub fn parse_prefix(bytes: &[u8]) -> Result<(Value<'static>, &[u8])> {
+        NBTCodec::big_endian().parse_prefix(bytes)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::tag::Tag;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub tags: [u64; 13],
+    pub string_bytes: u64,
+    pub max_depth: usize,
+    pub largest_byte_array: usize,
+    pub largest_int_array: usize,
+    pub largest_long_array: usize,
+}
+
+impl Stats {
+    pub fn count(&self, tag: Tag) -> u64 {
+        self.tags[tag as usize]
+    }
+
+    pub fn total_tags(&self) -> u64 {
+        self.tags.iter().sum()
+    }
+}
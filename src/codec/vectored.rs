@@ -0,0 +1,110 @@
+use std::io::{ErrorKind, IoSlice, Write};
+
+use super::{ConfiguredCodec, Endian, NBTCodecTrait};
+use crate::{
+    error::Result,
+    tag::{Tag, TagKind},
+    value::Value,
+};
+
+const BORROW_MIN_BYTES: usize = 1024;
+
+struct Segments<'v> {
+    buf: Vec<u8>,
+    borrowed: Vec<(usize, &'v [u8])>,
+}
+
+impl<'v> Segments<'v> {
+    fn borrow(&mut self, bytes: &'v [u8]) {
+        self.borrowed.push((self.buf.len(), bytes));
+    }
+
+    fn slices(&self) -> Vec<IoSlice<'_>> {
+        let mut slices = Vec::with_capacity(self.borrowed.len() * 2 + 1);
+        let mut start = 0;
+        for &(offset, bytes) in &self.borrowed {
+            slices.push(IoSlice::new(&self.buf[start..offset]));
+            slices.push(IoSlice::new(bytes));
+            start = offset;
+        }
+        slices.push(IoSlice::new(&self.buf[start..]));
+        slices.retain(|slice| !slice.is_empty());
+        slices
+    }
+}
+
+impl ConfiguredCodec {
+    pub fn write_tag_vectored<W: Write>(
+        &self,
+        writer: &mut W,
+        name: Option<&str>,
+        value: &Value<'_>,
+    ) -> Result<()> {
+        let mut segments = Segments {
+            buf: Vec::new(),
+            borrowed: Vec::new(),
+        };
+
+        self.write_u8(&mut segments.buf, self.wire_id(value))?;
+        self.write_name(&mut segments.buf, name.unwrap_or_default())?;
+        self.encode_segments(value, &mut segments)?;
+
+        let mut slices = segments.slices();
+        let mut slices = slices.as_mut_slice();
+        while !slices.is_empty() {
+            match writer.write_vectored(slices) {
+                Ok(0) => return Err(std::io::Error::from(ErrorKind::WriteZero).into()),
+                Ok(n) => IoSlice::advance_slices(&mut slices, n),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_segments<'v>(&self, value: &'v Value<'_>, out: &mut Segments<'v>) -> Result<()> {
+        match value {
+            Value::ByteArray(v) if v.len() >= BORROW_MIN_BYTES => {
+                self.write_u32(&mut out.buf, v.len() as u32)?;
+                out.borrow(v);
+            }
+            Value::IntArray(v) if self.is_native_endian() && v.len() * 4 >= BORROW_MIN_BYTES => {
+                self.write_u32(&mut out.buf, v.len() as u32)?;
+                out.borrow(bytemuck::cast_slice(v));
+            }
+            Value::LongArray(v) if self.is_native_endian() && v.len() * 8 >= BORROW_MIN_BYTES => {
+                self.write_u32(&mut out.buf, v.len() as u32)?;
+                out.borrow(bytemuck::cast_slice(v));
+            }
+            Value::List(list) => {
+                let element_tag = list.first().map_or(TagKind::Known(Tag::End), Value::tag);
+                self.check_list(list, element_tag)?;
+
+                self.write_u8(&mut out.buf, element_tag.id())?;
+                self.write_i32(&mut out.buf, list.len() as i32)?;
+                for value in list {
+                    self.encode_segments(value, out)?;
+                }
+            }
+            Value::Compound(map) => {
+                for (name, value) in map {
+                    self.write_u8(&mut out.buf, self.wire_id(value))?;
+                    self.write_name(&mut out.buf, name)?;
+                    self.encode_segments(value, out)?;
+                }
+                self.write_i8(&mut out.buf, Tag::End as i8)?;
+            }
+            _ => self.write_value(&mut out.buf, value)?,
+        }
+
+        Ok(())
+    }
+
+    fn is_native_endian(&self) -> bool {
+        match self.endian {
+            Endian::Big => cfg!(target_endian = "big"),
+            Endian::Little => cfg!(target_endian = "little"),
+        }
+    }
+}
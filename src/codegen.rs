@@ -0,0 +1,163 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    schema::{Field, Schema},
+    value::Value,
+};
+
+pub fn rust_structs(root: &str, samples: &[Value<'_>]) -> String {
+    rust_structs_for_schema(root, &Schema::infer(samples))
+}
+
+pub fn rust_structs_for_schema(root: &str, schema: &Schema) -> String {
+    let mut emitter = Emitter {
+        structs: Vec::new(),
+    };
+    let root_type = emitter.type_of(&pascal_case(root), schema);
+    if emitter.structs.is_empty() {
+        return format!("pub type {} = {};\n", pascal_case(root), root_type);
+    }
+
+    let mut out = String::new();
+    for (i, (name, fields)) in emitter.structs.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str("#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]\n");
+        let _ = writeln!(out, "pub struct {} {{", name);
+        for field in fields {
+            out.push_str(field);
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+struct Emitter {
+    structs: Vec<(String, Vec<String>)>,
+}
+
+impl Emitter {
+    fn type_of(&mut self, name: &str, schema: &Schema) -> String {
+        match schema {
+            Schema::Byte => "i8".into(),
+            Schema::Short => "i16".into(),
+            Schema::Int => "i32".into(),
+            Schema::Long => "i64".into(),
+            Schema::Float => "f32".into(),
+            Schema::Double => "f64".into(),
+            Schema::ByteArray => "Vec<u8>".into(),
+            Schema::String => "String".into(),
+            Schema::IntArray => "Vec<i32>".into(),
+            Schema::LongArray => "Vec<i64>".into(),
+            Schema::List(element) => format!("Vec<{}>", self.type_of(name, element)),
+            Schema::Compound(fields) => self.compound(name, fields),
+            Schema::Unknown | Schema::Mixed => "serde_json::Value".into(),
+        }
+    }
+
+    fn compound(&mut self, name: &str, fields: &BTreeMap<String, Field>) -> String {
+        let mut name = name.to_owned();
+        while self.structs.iter().any(|(n, _)| *n == name) {
+            name.push('_');
+        }
+
+        let index = self.structs.len();
+        self.structs.push((name.clone(), Vec::new()));
+
+        let mut out = Vec::new();
+        let mut used = Vec::new();
+        for (key, Field { schema, optional }) in fields {
+            let mut field = field_name(key);
+            while used.contains(&field) {
+                field.push('_');
+            }
+            used.push(field.clone());
+
+            let mut ty = self.type_of(&pascal_case(key), schema);
+            if *optional {
+                ty = format!("Option<{}>", ty);
+            }
+
+            let mut line = String::new();
+            if field != *key || *optional {
+                line.push_str("    #[serde(");
+                if field != *key {
+                    let _ = write!(line, "rename = {:?}", key);
+                }
+                if *optional {
+                    if field != *key {
+                        line.push_str(", ");
+                    }
+                    line.push_str("default, skip_serializing_if = \"Option::is_none\"");
+                }
+                line.push_str(")]\n");
+            }
+            let _ = writeln!(line, "    pub {}: {},", field, ty);
+            out.push(line);
+        }
+
+        self.structs[index].1 = out;
+        name
+    }
+}
+
+fn words(key: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev: Option<char> = None;
+
+    for c in key.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev = None;
+            continue;
+        }
+        if c.is_uppercase() && prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+        prev = Some(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn pascal_case(key: &str) -> String {
+    let name: String = words(key)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    match name.chars().next() {
+        Some(c) if c.is_alphabetic() => name,
+        _ => format!("T{}", name),
+    }
+}
+
+fn field_name(key: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match",
+        "mod", "move", "mut", "priv", "pub", "ref", "return", "self", "static", "struct", "super",
+        "trait", "true", "try", "type", "unsafe", "use", "where", "while", "yield",
+    ];
+
+    let name = words(key).join("_");
+    match name.chars().next() {
+        None => "field".into(),
+        Some(c) if !c.is_alphabetic() => format!("f_{}", name),
+        _ if KEYWORDS.contains(&name.as_str()) => format!("{}_", name),
+        _ => name,
+    }
+}
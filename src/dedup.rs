@@ -0,0 +1,172 @@
+use std::{
+    cell::OnceCell,
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use crate::{path::NbtPath, value::Value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedSubtree {
+    pub hash: u64,
+    pub paths: Vec<NbtPath>,
+}
+
+impl<'a> Value<'a> {
+    pub fn structural_hash(&self) -> u64 {
+        hash_node(self, &mut |_, _| {}, NbtPath::root())
+    }
+
+    pub fn dedup_shared(&self) -> Vec<SharedSubtree> {
+        let mut nodes: Vec<(u64, NbtPath, &Value<'a>)> = Vec::new();
+        hash_node(
+            self,
+            &mut |hash, node| nodes.push((hash, node.0, node.1)),
+            NbtPath::root(),
+        );
+        nodes.reverse();
+
+        let mut groups: Vec<(SharedSubtree, &Value<'a>)> = Vec::new();
+        let mut by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+
+        for (hash, path, value) in nodes {
+            let candidates = by_hash.entry(hash).or_default();
+            match candidates.iter().find(|&&i| groups[i].1 == value) {
+                Some(&i) => groups[i].0.paths.push(path),
+                None => {
+                    candidates.push(groups.len());
+                    groups.push((
+                        SharedSubtree {
+                            hash,
+                            paths: vec![path],
+                        },
+                        value,
+                    ));
+                }
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(group, _)| group)
+            .filter(|group| group.paths.len() > 1)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Hashed<'a> {
+    value: Value<'a>,
+    hash: OnceCell<u64>,
+}
+
+impl<'a> Hashed<'a> {
+    pub fn new(value: Value<'a>) -> Self {
+        Self {
+            value,
+            hash: OnceCell::new(),
+        }
+    }
+
+    pub fn value(&self) -> &Value<'a> {
+        &self.value
+    }
+
+    pub fn value_mut(&mut self) -> &mut Value<'a> {
+        self.hash.take();
+        &mut self.value
+    }
+
+    pub fn into_inner(self) -> Value<'a> {
+        self.value
+    }
+
+    pub fn structural_hash(&self) -> u64 {
+        *self.hash.get_or_init(|| self.value.structural_hash())
+    }
+
+    pub fn is_cached(&self) -> bool {
+        self.hash.get().is_some()
+    }
+}
+
+impl<'a> From<Value<'a>> for Hashed<'a> {
+    fn from(value: Value<'a>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl PartialEq for Hashed<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+            || (self.structural_hash() == other.structural_hash()
+                && same_bits(&self.value, &other.value))
+    }
+}
+
+impl Eq for Hashed<'_> {}
+
+impl Hash for Hashed<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.structural_hash());
+    }
+}
+
+fn same_bits<'a>(left: &Value<'a>, right: &Value<'a>) -> bool {
+    match (left, right) {
+        (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+        (Value::Double(a), Value::Double(b)) => a.to_bits() == b.to_bits(),
+        (Value::List(a), Value::List(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| same_bits(x, y))
+        }
+        (Value::Compound(a), Value::Compound(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, x)| b.get(key).is_some_and(|y| same_bits(x, y)))
+        }
+        _ => left == right,
+    }
+}
+
+fn hash_node<'v, 'a, F>(value: &'v Value<'a>, visit: &mut F, path: NbtPath) -> u64
+where
+    F: FnMut(u64, (NbtPath, &'v Value<'a>)),
+{
+    let mut hasher = DefaultHasher::new();
+    value.tag_id().hash(&mut hasher);
+
+    match value {
+        Value::End => {}
+        Value::Byte(v) => v.hash(&mut hasher),
+        Value::Short(v) => v.hash(&mut hasher),
+        Value::Int(v) => v.hash(&mut hasher),
+        Value::Long(v) => v.hash(&mut hasher),
+        Value::Float(v) => v.to_bits().hash(&mut hasher),
+        Value::Double(v) => v.to_bits().hash(&mut hasher),
+        Value::ByteArray(v) => v.hash(&mut hasher),
+        Value::String(v) => v.hash(&mut hasher),
+        Value::IntArray(v) => v.hash(&mut hasher),
+        Value::LongArray(v) => v.hash(&mut hasher),
+        Value::Unknown(_, v) => v.hash(&mut hasher),
+        Value::EmptyList(tag) => (*tag as u8).hash(&mut hasher),
+        Value::List(list) => {
+            list.len().hash(&mut hasher);
+            for (index, child) in list.iter().enumerate().rev() {
+                hash_node(child, visit, path.clone().index(index)).hash(&mut hasher);
+            }
+        }
+        Value::Compound(map) => {
+            map.len().hash(&mut hasher);
+            for (key, child) in map.iter_sorted().rev() {
+                key.hash(&mut hasher);
+                hash_node(child, visit, path.clone().key(key.as_ref())).hash(&mut hasher);
+            }
+        }
+    }
+
+    let hash = hasher.finish();
+    if matches!(value, Value::List(_) | Value::Compound(_)) {
+        visit(hash, (path, value));
+    }
+    hash
+}
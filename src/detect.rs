@@ -0,0 +1,194 @@
+use crate::{
+    codec::{Endian, NBTCodec},
+    error::{NBTError, Result},
+    file::{BedrockHeader, guess_endian_from_name, parses_as, split_bedrock_header},
+    tag::Tag,
+    varint::{read_var_i32, read_var_i64, read_var_u32},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zlib,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedFormat {
+    pub compression: Compression,
+    pub endian: Option<Endian>,
+    pub header: Option<BedrockHeader>,
+    pub network: bool,
+    pub payload_offset: usize,
+}
+
+impl DetectedFormat {
+    pub fn codec(&self) -> Option<NBTCodec> {
+        match (self.compression, self.endian, self.network) {
+            (Compression::None, Some(endian), false) => Some(NBTCodec::new(endian)),
+            _ => None,
+        }
+    }
+}
+
+const MAX_DEPTH: usize = 512;
+
+pub fn detect_flavor(data: &[u8]) -> Result<DetectedFormat> {
+    let mut format = DetectedFormat {
+        compression: Compression::None,
+        endian: None,
+        header: None,
+        network: false,
+        payload_offset: 0,
+    };
+
+    format.compression = detect_compression(data);
+    if format.compression != Compression::None {
+        return Ok(format);
+    }
+
+    if let Some((header, _)) = split_bedrock_header(data) {
+        format.header = Some(header);
+        format.endian = Some(Endian::Little);
+        format.payload_offset = 8;
+        return Ok(format);
+    }
+
+    format.endian = match (
+        parses_as(data, Endian::Big),
+        parses_as(data, Endian::Little),
+    ) {
+        (true, false) => Some(Endian::Big),
+        (false, true) => Some(Endian::Little),
+        (true, true) => Some(guess_endian_from_name(data)?),
+        (false, false) if is_network_document(data) => {
+            format.network = true;
+            Some(Endian::Little)
+        }
+        (false, false) => return Err(NBTError::invalid_format()),
+    };
+
+    Ok(format)
+}
+
+pub(crate) fn detect_compression(data: &[u8]) -> Compression {
+    match data {
+        [0x1F, 0x8B, ..] => Compression::Gzip,
+        [0x78, second, ..] if (0x78u16 << 8 | *second as u16).is_multiple_of(31) => {
+            Compression::Zlib
+        }
+        _ => Compression::None,
+    }
+}
+
+fn is_network_document(data: &[u8]) -> bool {
+    let mut rest = data;
+    let parsed = (|| {
+        let tag = Tag::try_from(take(&mut rest, 1)?[0])?;
+        skip_network(&mut rest, Tag::String)?;
+        skip_network(&mut rest, tag)
+    })();
+
+    parsed.is_ok() && rest.is_empty()
+}
+
+fn take<'d>(rest: &mut &'d [u8], len: usize) -> Result<&'d [u8]> {
+    if rest.len() < len {
+        return Err(NBTError::unexpected_eof_reading(len));
+    }
+    let (head, tail) = rest.split_at(len);
+    *rest = tail;
+    Ok(head)
+}
+
+fn length(value: i32) -> Result<usize> {
+    usize::try_from(value).map_err(|_| NBTError::invalid_string_length(value as usize))
+}
+
+enum Frame {
+    List(Tag, usize),
+    Compound,
+}
+
+fn enter(stack: &mut Vec<Frame>, frame: Frame) -> Result<()> {
+    if stack.len() >= MAX_DEPTH {
+        return Err(NBTError::too_deep(MAX_DEPTH));
+    }
+    stack.push(frame);
+    Ok(())
+}
+
+fn skip_network(rest: &mut &[u8], tag: Tag) -> Result<()> {
+    let mut stack = Vec::new();
+    let mut next = Some(tag);
+
+    loop {
+        match next.take() {
+            Some(Tag::List) => {
+                let element = Tag::try_from(take(rest, 1)?[0])?;
+                let len = length(read_var_i32(rest)?)?;
+                enter(&mut stack, Frame::List(element, len))?;
+            }
+            Some(Tag::Compound) => enter(&mut stack, Frame::Compound)?,
+            Some(tag) => skip_scalar(rest, tag)?,
+            None => {}
+        }
+
+        next = match stack.last_mut() {
+            None => return Ok(()),
+            Some(Frame::List(_, 0)) => {
+                stack.pop();
+                None
+            }
+            Some(Frame::List(element, remaining)) => {
+                *remaining -= 1;
+                Some(*element)
+            }
+            Some(Frame::Compound) => match Tag::try_from(take(rest, 1)?[0])? {
+                Tag::End => {
+                    stack.pop();
+                    None
+                }
+                tag => {
+                    skip_scalar(rest, Tag::String)?;
+                    Some(tag)
+                }
+            },
+        };
+    }
+}
+
+fn skip_scalar(rest: &mut &[u8], tag: Tag) -> Result<()> {
+    match tag {
+        Tag::Int => {
+            read_var_i32(rest)?;
+        }
+        Tag::Long => {
+            read_var_i64(rest)?;
+        }
+        Tag::String => {
+            let len = read_var_u32(rest)? as usize;
+            take(rest, len)?;
+        }
+        Tag::ByteArray => {
+            let len = length(read_var_i32(rest)?)?;
+            take(rest, len)?;
+        }
+        Tag::IntArray => {
+            for _ in 0..length(read_var_i32(rest)?)? {
+                read_var_i32(rest)?;
+            }
+        }
+        Tag::LongArray => {
+            for _ in 0..length(read_var_i32(rest)?)? {
+                read_var_i64(rest)?;
+            }
+        }
+        Tag::List | Tag::Compound => unreachable!("containers are skipped by skip_network"),
+        Tag::End | Tag::Byte | Tag::Short | Tag::Float | Tag::Double => {
+            take(rest, tag.fixed_payload_size().unwrap_or_default())?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::fmt::Display;
+
+use crate::{path::NbtPath, value::Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference<'v, 'a> {
+    pub path: NbtPath,
+    pub left: Option<&'v Value<'a>>,
+    pub right: Option<&'v Value<'a>>,
+}
+
+impl Display for Difference<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_root() {
+            f.write_str("<root>")?;
+        } else {
+            write!(f, "{}", self.path)?;
+        }
+
+        match (self.left, self.right) {
+            (Some(left), Some(right)) => write!(f, ": {:?} != {:?}", left, right),
+            (Some(left), None) => write!(f, ": {:?} only on the left", left),
+            (None, Some(right)) => write!(f, ": {:?} only on the right", right),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn diff<'v>(&'v self, other: &'v Value<'a>, epsilon: f64) -> Vec<Difference<'v, 'a>> {
+        let mut differences = Vec::new();
+        diff_into(self, other, epsilon, NbtPath::root(), &mut differences);
+        differences
+    }
+
+    pub fn approx_eq(&self, other: &Value<'a>, epsilon: f64) -> bool {
+        self.diff(other, epsilon).is_empty()
+    }
+}
+
+fn diff_into<'v, 'a>(
+    left: &'v Value<'a>,
+    right: &'v Value<'a>,
+    epsilon: f64,
+    path: NbtPath,
+    differences: &mut Vec<Difference<'v, 'a>>,
+) {
+    let changed = |path| Difference {
+        path,
+        left: Some(left),
+        right: Some(right),
+    };
+
+    match (left, right) {
+        (Value::Compound(a), Value::Compound(b)) => {
+            for (key, value) in a {
+                let path = path.clone().key(key.as_ref());
+                match b.get(key) {
+                    Some(other) => diff_into(value, other, epsilon, path, differences),
+                    None => differences.push(Difference {
+                        path,
+                        left: Some(value),
+                        right: None,
+                    }),
+                }
+            }
+            for (key, value) in b {
+                if !a.contains_key(key) {
+                    differences.push(Difference {
+                        path: path.clone().key(key.as_ref()),
+                        left: None,
+                        right: Some(value),
+                    });
+                }
+            }
+        }
+        (Value::List(a), Value::List(b)) => {
+            for index in 0..a.len().max(b.len()) {
+                let path = path.clone().index(index);
+                match (a.get(index), b.get(index)) {
+                    (Some(x), Some(y)) => diff_into(x, y, epsilon, path, differences),
+                    (x, y) => differences.push(Difference {
+                        path,
+                        left: x,
+                        right: y,
+                    }),
+                }
+            }
+        }
+        (Value::Float(a), Value::Float(b)) => {
+            if !approx(*a as f64, *b as f64, epsilon) {
+                differences.push(changed(path));
+            }
+        }
+        (Value::Double(a), Value::Double(b)) => {
+            if !approx(*a, *b, epsilon) {
+                differences.push(changed(path));
+            }
+        }
+        _ => {
+            if left != right {
+                differences.push(changed(path));
+            }
+        }
+    }
+}
+
+fn approx(a: f64, b: f64, epsilon: f64) -> bool {
+    a == b || (a.is_nan() && b.is_nan()) || (a - b).abs() <= epsilon
+}
+
+#[cfg(feature = "test-support")]
+#[macro_export]
+macro_rules! assert_nbt_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_nbt_eq!($left, $right, epsilon = 0.0)
+    };
+    ($left:expr, $right:expr, epsilon = $epsilon:expr $(,)?) => {{
+        let __left: &$crate::value::Value<'_> = &$left;
+        let __right: &$crate::value::Value<'_> = &$right;
+        let __differences = __left.diff(__right, $epsilon);
+
+        if !__differences.is_empty() {
+            let mut __message = format!(
+                "assertion `left == right` failed: {} difference(s)",
+                __differences.len()
+            );
+            for __difference in &__differences {
+                __message.push_str("\n  ");
+                __message.push_str(&__difference.to_string());
+            }
+            panic!("{}", __message);
+        }
+    }};
+}
@@ -0,0 +1,70 @@
+use crate::{
+    codec::{NBTCodec, NBTCodecTrait},
+    error::Result,
+    tag::Tag,
+    value::Value,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Fnv1a64,
+    #[cfg(feature = "digest")]
+    Crc32,
+    #[cfg(feature = "digest")]
+    Sha256,
+}
+
+impl<'a> Value<'a> {
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>> {
+        let codec = NBTCodec::big_endian();
+        let mut out = Vec::new();
+        codec.write_u8(&mut out, self.tag_id())?;
+        codec.write_string(&mut out, "")?;
+        write_canonical(&codec, &mut out, self)?;
+        Ok(out)
+    }
+
+    pub fn content_hash(&self, algorithm: HashAlgorithm) -> Result<Vec<u8>> {
+        let bytes = self.canonical_bytes()?;
+        Ok(match algorithm {
+            HashAlgorithm::Fnv1a64 => fnv1a64(&bytes).to_be_bytes().to_vec(),
+            #[cfg(feature = "digest")]
+            HashAlgorithm::Crc32 => crc32fast::hash(&bytes).to_be_bytes().to_vec(),
+            #[cfg(feature = "digest")]
+            HashAlgorithm::Sha256 => {
+                use sha2::Digest;
+                sha2::Sha256::digest(&bytes).to_vec()
+            }
+        })
+    }
+}
+
+fn write_canonical(codec: &NBTCodec, out: &mut Vec<u8>, value: &Value<'_>) -> Result<()> {
+    match value {
+        Value::List(list) => {
+            let element_id = list.first().map_or(Tag::End as u8, Value::tag_id);
+            codec.write_u8(out, element_id)?;
+            codec.write_i32(out, list.len() as i32)?;
+            for item in list {
+                write_canonical(codec, out, item)?;
+            }
+        }
+        Value::Compound(map) => {
+            for (key, item) in map.iter_sorted() {
+                codec.write_u8(out, item.tag_id())?;
+                codec.write_string(out, key)?;
+                write_canonical(codec, out, item)?;
+            }
+            codec.write_u8(out, Tag::End as u8)?;
+        }
+        _ => codec.write_value(out, value)?,
+    }
+
+    Ok(())
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,287 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    error::{NBTError, Result},
+    path::{NbtPath, PathSegment},
+    tag::Tag,
+    value::Value,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Op<'a> {
+    Set(Value<'a>),
+    Remove,
+    Insert(Value<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Edit<'a> {
+    path: NbtPath,
+    op: Op<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Replaced,
+    Removed,
+}
+
+type ObserverFn = dyn Fn(&NbtPath, ChangeKind) + Send + Sync;
+
+#[derive(Clone)]
+struct Observer(Arc<ObserverFn>);
+
+impl Debug for Observer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Observer(..)")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Editor<'a> {
+    value: Value<'a>,
+    undo: Vec<Vec<Edit<'a>>>,
+    redo: Vec<Vec<Edit<'a>>>,
+    pending: Option<Vec<Edit<'a>>>,
+    observers: Vec<Observer>,
+}
+
+impl<'a> Editor<'a> {
+    pub fn new(value: Value<'a>) -> Self {
+        Self {
+            value,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            pending: None,
+            observers: Vec::new(),
+        }
+    }
+
+    pub fn on_change<F>(&mut self, observer: F)
+    where
+        F: Fn(&NbtPath, ChangeKind) + Send + Sync + 'static,
+    {
+        self.observers.push(Observer(Arc::new(observer)));
+    }
+
+    fn notify(&self, path: &NbtPath, kind: ChangeKind) {
+        for observer in &self.observers {
+            (observer.0)(path, kind);
+        }
+    }
+
+    pub fn value(&self) -> &Value<'a> {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> Value<'a> {
+        self.value
+    }
+
+    pub fn set<V: Into<Value<'a>>>(&mut self, path: &NbtPath, value: V) -> Result<()> {
+        self.edit(Edit {
+            path: path.clone(),
+            op: Op::Set(value.into()),
+        })
+    }
+
+    pub fn remove(&mut self, path: &NbtPath) -> Result<()> {
+        self.edit(Edit {
+            path: path.clone(),
+            op: Op::Remove,
+        })
+    }
+
+    pub fn insert<V: Into<Value<'a>>>(&mut self, path: &NbtPath, value: V) -> Result<()> {
+        self.edit(Edit {
+            path: path.clone(),
+            op: Op::Insert(value.into()),
+        })
+    }
+
+    pub fn begin(&mut self) {
+        self.pending.get_or_insert_with(Vec::new);
+    }
+
+    pub fn commit(&mut self) {
+        if let Some(group) = self.pending.take()
+            && !group.is_empty()
+        {
+            self.undo.push(group);
+        }
+    }
+
+    pub fn rollback(&mut self) -> Result<()> {
+        let group = self.pending.take().unwrap_or_default();
+        self.revert(group)?;
+        Ok(())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self) -> Result<bool> {
+        self.ensure_no_transaction()?;
+        let Some(group) = self.undo.pop() else {
+            return Ok(false);
+        };
+
+        let inverse = self.revert(group)?;
+        self.redo.push(inverse);
+        Ok(true)
+    }
+
+    pub fn redo(&mut self) -> Result<bool> {
+        self.ensure_no_transaction()?;
+        let Some(group) = self.redo.pop() else {
+            return Ok(false);
+        };
+
+        let inverse = self.revert(group)?;
+        self.undo.push(inverse);
+        Ok(true)
+    }
+
+    fn ensure_no_transaction(&self) -> Result<()> {
+        match self.pending {
+            Some(_) => Err(NBTError::custom_msg(
+                "Cannot undo or redo while a transaction is open",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn edit(&mut self, edit: Edit<'a>) -> Result<()> {
+        let inverse = self.apply(edit)?;
+        self.redo.clear();
+
+        match &mut self.pending {
+            Some(group) => group.push(inverse),
+            None => self.undo.push(vec![inverse]),
+        }
+        Ok(())
+    }
+
+    fn revert(&mut self, group: Vec<Edit<'a>>) -> Result<Vec<Edit<'a>>> {
+        let mut inverse = Vec::with_capacity(group.len());
+        for edit in group.into_iter().rev() {
+            inverse.push(self.apply(edit)?);
+        }
+        Ok(inverse)
+    }
+
+    fn apply(&mut self, edit: Edit<'a>) -> Result<Edit<'a>> {
+        let Edit { path, op } = edit;
+
+        let op = match op {
+            Op::Set(value) => {
+                let created = self.first_missing(&path);
+                if let Some(created) = &created
+                    && path.segments[created.segments.len() - 1..]
+                        .iter()
+                        .any(|segment| matches!(segment, PathSegment::Index(_)))
+                {
+                    return Err(missing(created));
+                }
+
+                let old = self.value.set_path(&path, value)?;
+                match (created, old) {
+                    (Some(created), _) => {
+                        self.notify(&created, ChangeKind::Added);
+                        return Ok(Edit {
+                            path: created,
+                            op: Op::Remove,
+                        });
+                    }
+                    (None, Some(old)) => {
+                        self.notify(&path, ChangeKind::Replaced);
+                        Op::Set(old)
+                    }
+                    (None, None) => {
+                        self.notify(&path, ChangeKind::Added);
+                        Op::Remove
+                    }
+                }
+            }
+            Op::Remove => {
+                let (parent, last) = self.parent_mut(&path)?;
+                let op = match (last, parent) {
+                    (PathSegment::Key(key), Value::Compound(map)) => {
+                        Op::Set(map.remove(key.as_str()).ok_or_else(|| missing(&path))?)
+                    }
+                    (PathSegment::Index(index), Value::List(list)) if *index < list.len() => {
+                        Op::Insert(list.remove(*index))
+                    }
+                    _ => return Err(missing(&path)),
+                };
+                self.notify(&path, ChangeKind::Removed);
+                op
+            }
+            Op::Insert(value) => {
+                let (parent, last) = self.parent_mut(&path)?;
+                let index = match (last, parent.as_list()) {
+                    (PathSegment::Index(index), Some(list)) if *index <= list.len() => *index,
+                    _ => {
+                        return Err(NBTError::invalid_path(format!(
+                            "{}: not an insertable list position",
+                            path
+                        )));
+                    }
+                };
+                if let Some(held) = parent.list_tag()
+                    && held != Tag::End
+                    && held != value.tag()
+                {
+                    return Err(NBTError::invalid_path(format!(
+                        "{}: list holds {:?}, cannot insert {:?}",
+                        path,
+                        held,
+                        value.tag()
+                    )));
+                }
+                parent
+                    .make_list_mut()
+                    .expect("checked to be a list")
+                    .insert(index, value);
+                self.notify(&path, ChangeKind::Added);
+                Op::Remove
+            }
+        };
+
+        Ok(Edit { path, op })
+    }
+
+    fn first_missing(&self, path: &NbtPath) -> Option<NbtPath> {
+        let mut prefix = NbtPath::root();
+        for segment in &path.segments {
+            prefix.segments.push(segment.clone());
+            if self.value.get_path(&prefix).is_none() {
+                return Some(prefix);
+            }
+        }
+        None
+    }
+
+    fn parent_mut<'p>(&mut self, path: &'p NbtPath) -> Result<(&mut Value<'a>, &'p PathSegment)> {
+        let parent = path
+            .parent()
+            .ok_or_else(|| NBTError::invalid_path("cannot remove or insert at the root"))?;
+        let last = path.last().expect("non-root path has a last segment");
+
+        let value = self
+            .value
+            .get_path_mut(&parent)
+            .ok_or_else(|| missing(&parent))?;
+        Ok((value, last))
+    }
+}
+
+fn missing(path: &NbtPath) -> NBTError {
+    NBTError::invalid_path(format!("{}: no such entry", path))
+}
@@ -0,0 +1,115 @@
+use crate::{
+    error::{NBTError, Result},
+    value::Value,
+};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from_be_bytes([0, b[0], b[1], b[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+pub fn from_base64(input: &str) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    let mut acc = 0u32;
+    let mut bits = 0;
+
+    for c in input.trim_end_matches('=').chars() {
+        let sextet = match c {
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' | '-' => 62,
+            '/' | '_' => 63,
+            c if c.is_whitespace() => continue,
+            c => {
+                return Err(NBTError::invalid_syntax(format!(
+                    "invalid base64 character {:?}",
+                    c
+                )));
+            }
+        };
+
+        acc = (acc << 6) | sextet;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    if bits >= 6 {
+        return Err(NBTError::invalid_syntax("truncated base64 input"));
+    }
+
+    Ok(out)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0xF) as usize] as char);
+    }
+    out
+}
+
+pub fn from_hex(input: &str) -> Result<Vec<u8>> {
+    let digits = input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or_else(|| NBTError::invalid_syntax(format!("invalid hex character {:?}", c)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if digits.len() % 2 != 0 {
+        return Err(NBTError::invalid_syntax("odd number of hex digits"));
+    }
+
+    Ok(digits.chunks(2).map(|d| (d[0] << 4) | d[1]).collect())
+}
+
+impl<'a> Value<'a> {
+    pub fn byte_array_from_base64(input: &str) -> Result<Self> {
+        Ok(Value::ByteArray(from_base64(input)?))
+    }
+
+    pub fn byte_array_from_hex(input: &str) -> Result<Self> {
+        Ok(Value::ByteArray(from_hex(input)?))
+    }
+
+    pub fn to_base64(&self) -> Option<String> {
+        self.as_bytes().map(to_base64)
+    }
+
+    pub fn to_hex(&self) -> Option<String> {
+        self.as_bytes().map(to_hex)
+    }
+}
@@ -1,39 +1,121 @@
 use std::{backtrace::Backtrace, fmt::Debug};
 
+use crate::{
+    path::{NbtPath, PathSegment},
+    tag::Tag,
+};
+
 pub struct NBTError {
     pub source: Option<Box<dyn std::error::Error + Send + Sync>>,
     pub kind: NBTErrorKind,
+    pub path: Option<Box<NbtPath>>,
     pub backtrace: Backtrace,
 }
 
 #[derive(Debug)]
 pub enum NBTErrorKind {
     IO,
-    UnexpectedEOF,
+    UnexpectedEOF {
+        tag: Option<Tag>,
+        expected: Option<usize>,
+        offset: Option<u64>,
+    },
     FromUTF8,
     InvalidTagID(u8),
     InvalidStringLength(usize),
+    StringTooLong(usize),
     InvalidFormat,
+    InvalidFields(Vec<String>),
+    PayloadTooLarge(u64),
+    TooManyEntries(usize),
+    TooManyTags(u64),
+    TooDeep(usize),
+    Cancelled,
+    InvalidPath(String),
+    InvalidSyntax(String),
     Custom(String),
 }
 
 pub type Result<T> = std::result::Result<T, NBTError>;
 
+impl std::fmt::Display for NBTErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NBTErrorKind::IO => f.write_str("I/O error"),
+            NBTErrorKind::UnexpectedEOF {
+                tag,
+                expected,
+                offset,
+            } => {
+                f.write_str("unexpected end of input")?;
+                if let Some(expected) = expected {
+                    write!(f, " while reading {} bytes", expected)?;
+                }
+                if let Some(tag) = tag {
+                    write!(f, " of {:?}", tag)?;
+                }
+                if let Some(offset) = offset {
+                    write!(f, " at byte {}", offset)?;
+                }
+                Ok(())
+            }
+            NBTErrorKind::FromUTF8 => f.write_str("invalid UTF-8 in string"),
+            NBTErrorKind::InvalidTagID(id) => write!(f, "invalid tag id {}", id),
+            NBTErrorKind::InvalidStringLength(len) => write!(f, "invalid length {}", len),
+            NBTErrorKind::StringTooLong(len) => {
+                write!(f, "string of {} bytes exceeds the 65535 byte limit", len)
+            }
+            NBTErrorKind::InvalidFormat => f.write_str("invalid format"),
+            NBTErrorKind::InvalidFields(fields) => {
+                write!(f, "invalid fields: {}", fields.join("; "))
+            }
+            NBTErrorKind::PayloadTooLarge(limit) => {
+                write!(f, "payload exceeds the {} byte limit", limit)
+            }
+            NBTErrorKind::TooManyEntries(limit) => {
+                write!(f, "compound exceeds the {} entry limit", limit)
+            }
+            NBTErrorKind::TooManyTags(limit) => {
+                write!(f, "document exceeds the {} tag limit", limit)
+            }
+            NBTErrorKind::TooDeep(limit) => {
+                write!(f, "nesting exceeds the {} level limit", limit)
+            }
+            NBTErrorKind::Cancelled => f.write_str("decoding cancelled"),
+            NBTErrorKind::InvalidPath(msg) => write!(f, "invalid path: {}", msg),
+            NBTErrorKind::InvalidSyntax(msg) => write!(f, "invalid syntax: {}", msg),
+            NBTErrorKind::Custom(msg) => f.write_str(msg),
+        }
+    }
+}
+
 impl std::fmt::Display for NBTError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.kind)?;
-        write!(f, ": {:?}", &self.source)?;
+        write!(f, "{}", self.kind)?;
+        if let Some(path) = &self.path {
+            write!(f, " in {}", path)?;
+        }
 
-        write!(f, "\nBacktrace:\n{:?}", self.backtrace)
+        match &self.source {
+            Some(source) => write!(f, ": {}", source),
+            None => Ok(()),
+        }
     }
 }
 
 impl std::fmt::Debug for NBTError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.kind)?;
+        if let Some(path) = &self.path {
+            write!(f, " in {}", path)?;
+        }
         write!(f, ": {:?}", &self.source)?;
 
-        write!(f, "\nBacktrace:\n{:?}", self.backtrace)
+        if f.alternate() {
+            write!(f, "\nBacktrace:\n{}", self.backtrace)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -44,11 +126,16 @@ impl std::error::Error for NBTError {
 }
 
 impl NBTError {
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+
     fn new(source: Box<dyn std::error::Error + Send + Sync>, kind: NBTErrorKind) -> Self {
         Self {
             source: Some(source),
             kind,
-            backtrace: Backtrace::force_capture(),
+            path: None,
+            backtrace: Backtrace::capture(),
         }
     }
 
@@ -56,7 +143,8 @@ impl NBTError {
         Self {
             source: None,
             kind,
-            backtrace: Backtrace::force_capture(),
+            path: None,
+            backtrace: Backtrace::capture(),
         }
     }
 
@@ -65,7 +153,57 @@ impl NBTError {
     }
 
     pub fn unexpected_eof() -> Self {
-        Self::no_source(NBTErrorKind::UnexpectedEOF)
+        Self::no_source(NBTErrorKind::UnexpectedEOF {
+            tag: None,
+            expected: None,
+            offset: None,
+        })
+    }
+
+    pub fn unexpected_eof_reading(expected: usize) -> Self {
+        Self::no_source(NBTErrorKind::UnexpectedEOF {
+            tag: None,
+            expected: Some(expected),
+            offset: None,
+        })
+    }
+
+    pub fn at_tag(mut self, at: Tag) -> Self {
+        if let NBTErrorKind::UnexpectedEOF { tag, .. } = &mut self.kind
+            && tag.is_none()
+        {
+            *tag = Some(at);
+        }
+        self
+    }
+
+    pub fn path(&self) -> Option<&NbtPath> {
+        self.path.as_deref()
+    }
+
+    pub fn at_key(self, key: &str) -> Self {
+        self.within(PathSegment::Key(key.to_owned()))
+    }
+
+    pub fn at_index(self, index: usize) -> Self {
+        self.within(PathSegment::Index(index))
+    }
+
+    fn within(mut self, segment: PathSegment) -> Self {
+        self.path
+            .get_or_insert_with(Default::default)
+            .segments
+            .insert(0, segment);
+        self
+    }
+
+    pub fn at_offset(mut self, at: u64) -> Self {
+        if let NBTErrorKind::UnexpectedEOF { offset, .. } = &mut self.kind
+            && offset.is_none()
+        {
+            *offset = Some(at);
+        }
+        self
     }
 
     pub fn from_utf8(source: std::string::FromUtf8Error) -> Self {
@@ -80,6 +218,46 @@ impl NBTError {
         Self::no_source(NBTErrorKind::InvalidStringLength(len))
     }
 
+    pub fn string_too_long(len: usize) -> Self {
+        Self::no_source(NBTErrorKind::StringTooLong(len))
+    }
+
+    pub fn invalid_format() -> Self {
+        Self::no_source(NBTErrorKind::InvalidFormat)
+    }
+
+    pub fn invalid_fields(errors: Vec<String>) -> Self {
+        Self::no_source(NBTErrorKind::InvalidFields(errors))
+    }
+
+    pub fn payload_too_large(limit: u64) -> Self {
+        Self::no_source(NBTErrorKind::PayloadTooLarge(limit))
+    }
+
+    pub fn too_many_entries(limit: usize) -> Self {
+        Self::no_source(NBTErrorKind::TooManyEntries(limit))
+    }
+
+    pub fn too_many_tags(limit: u64) -> Self {
+        Self::no_source(NBTErrorKind::TooManyTags(limit))
+    }
+
+    pub fn too_deep(limit: usize) -> Self {
+        Self::no_source(NBTErrorKind::TooDeep(limit))
+    }
+
+    pub fn cancelled() -> Self {
+        Self::no_source(NBTErrorKind::Cancelled)
+    }
+
+    pub fn invalid_path<S: Into<String>>(msg: S) -> Self {
+        Self::no_source(NBTErrorKind::InvalidPath(msg.into()))
+    }
+
+    pub fn invalid_syntax<S: Into<String>>(msg: S) -> Self {
+        Self::no_source(NBTErrorKind::InvalidSyntax(msg.into()))
+    }
+
     pub fn custom_msg<S: Into<String>>(msg: S) -> Self {
         Self::no_source(NBTErrorKind::Custom(msg.into()))
     }
@@ -87,10 +265,34 @@ impl NBTError {
 
 impl From<std::io::Error> for NBTError {
     fn from(source: std::io::Error) -> Self {
+        if source.get_ref().is_some_and(|e| e.is::<NBTError>()) {
+            if let Some(Ok(err)) = source.into_inner().map(|e| e.downcast::<NBTError>()) {
+                return *err;
+            }
+            unreachable!();
+        }
+
+        if source.kind() == std::io::ErrorKind::UnexpectedEof {
+            return Self::new(
+                Box::new(source),
+                NBTErrorKind::UnexpectedEOF {
+                    tag: None,
+                    expected: None,
+                    offset: None,
+                },
+            );
+        }
+
         Self::io(source)
     }
 }
 
+impl From<std::str::Utf8Error> for NBTError {
+    fn from(source: std::str::Utf8Error) -> Self {
+        Self::new(Box::new(source), NBTErrorKind::FromUTF8)
+    }
+}
+
 impl From<std::string::FromUtf8Error> for NBTError {
     fn from(source: std::string::FromUtf8Error) -> Self {
         Self::from_utf8(source)
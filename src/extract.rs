@@ -0,0 +1,292 @@
+use std::borrow::Cow;
+
+use crate::{
+    error::{NBTError, Result},
+    value::{CompoundMap, Value},
+};
+
+pub trait FromValue<'v, 'a>: Sized {
+    fn from_value(value: &'v Value<'a>) -> Option<Self>;
+
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! impl_from_value {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl<'v, 'a> FromValue<'v, 'a> for $ty {
+                fn from_value(value: &'v Value<'a>) -> Option<Self> {
+                    match value {
+                        Value::$variant(v) => Some(v.clone()),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value!(
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    Vec<u8> => ByteArray,
+    Vec<i32> => IntArray,
+    Vec<i64> => LongArray,
+);
+
+impl<'v, 'a> FromValue<'v, 'a> for bool {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        match value {
+            Value::Byte(v) => Some(*v != 0),
+            _ => None,
+        }
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for String {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        match value {
+            Value::String(v) => Some(v.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v str {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        match value {
+            Value::String(v) => Some(v.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v Value<'a> {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v [Value<'a>] {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        value.as_list()
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v CompoundMap<'a> {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        match value {
+            Value::Compound(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for Vec<i8> {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        value.as_i8_slice().map(<[i8]>::to_vec)
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v [i8] {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        value.as_i8_slice()
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v [u8] {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        value.as_bytes()
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v [i32] {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        match value {
+            Value::IntArray(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<'v, 'a> FromValue<'v, 'a> for &'v [i64] {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        match value {
+            Value::LongArray(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<'v, 'a, T: FromValue<'v, 'a>> FromValue<'v, 'a> for Option<T> {
+    fn from_value(value: &'v Value<'a>) -> Option<Self> {
+        T::from_value(value).map(Some)
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+pub(crate) struct Fields {
+    pub(crate) map: CompoundMap<'static>,
+    errors: Vec<String>,
+}
+
+impl Fields {
+    pub(crate) fn new(value: Value<'static>) -> Result<Self> {
+        match value {
+            Value::Compound(map) => Ok(Self {
+                map,
+                errors: Vec::new(),
+            }),
+            other => Err(NBTError::custom_msg(format!(
+                "Expected Compound, got {:?}",
+                other.tag()
+            ))),
+        }
+    }
+
+    pub(crate) fn take<T: for<'v> FromValue<'v, 'static>>(&mut self, key: &str) -> Option<T> {
+        match self.map.remove(key) {
+            Some(value) => {
+                let result = T::from_value(&value);
+                if result.is_none() {
+                    self.errors.push(format!(
+                        "{:?}: expected {}, got {:?}",
+                        key,
+                        std::any::type_name::<T>(),
+                        value.tag()
+                    ));
+                }
+                result
+            }
+            None => {
+                let result = T::missing();
+                if result.is_none() {
+                    self.errors.push(format!("{:?}: missing", key));
+                }
+                result
+            }
+        }
+    }
+
+    pub(crate) fn take_list(&mut self, key: &str) -> Vec<Value<'static>> {
+        self.take_opt_list(key).unwrap_or_default()
+    }
+
+    pub(crate) fn take_opt_list(&mut self, key: &str) -> Option<Vec<Value<'static>>> {
+        match self.map.remove(key) {
+            Some(Value::List(items)) => Some(items),
+            Some(Value::EmptyList(_)) => Some(Vec::new()),
+            Some(other) => {
+                self.errors
+                    .push(format!("{:?}: expected List, got {:?}", key, other.tag()));
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn take_compound(&mut self, key: &str) -> Option<CompoundMap<'static>> {
+        match self.map.remove(key) {
+            Some(Value::Compound(map)) => Some(map),
+            Some(other) => {
+                self.errors.push(format!(
+                    "{:?}: expected Compound, got {:?}",
+                    key,
+                    other.tag()
+                ));
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn take_floats<const N: usize>(&mut self, key: &str) -> Option<[f32; N]> {
+        let items = self.take_list(key);
+        let floats: Option<Vec<f32>> = items.iter().map(f32::from_value).collect();
+
+        match floats.and_then(|floats| floats.try_into().ok()) {
+            Some(floats) => Some(floats),
+            None => {
+                self.errors
+                    .push(format!("{:?}: expected {} floats", key, N));
+                None
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<CompoundMap<'static>> {
+        if self.errors.is_empty() {
+            Ok(self.map)
+        } else {
+            Err(NBTError::invalid_fields(self.errors))
+        }
+    }
+}
+
+pub(crate) fn insert_opt<V: Into<Value<'static>>>(
+    map: &mut CompoundMap<'static>,
+    key: &'static str,
+    value: Option<V>,
+) {
+    if let Some(value) = value {
+        map.insert(Cow::Borrowed(key), value.into());
+    }
+}
+
+#[macro_export]
+macro_rules! extract_nbt {
+    ($value:expr => { $($field:ident : $ty:ty = $key:expr),* $(,)? }) => {
+        $crate::extract_nbt!(@lookup get, $value => { $($field: $ty = $key),* })
+    };
+    ($value:expr, ignore_case => { $($field:ident : $ty:ty = $key:expr),* $(,)? }) => {
+        $crate::extract_nbt!(@lookup get_ignore_case, $value => { $($field: $ty = $key),* })
+    };
+    (@lookup $get:ident, $value:expr => { $($field:ident : $ty:ty = $key:expr),* }) => {{
+        let __value: &$crate::value::Value<'_> = &$value;
+        let mut __errors: Vec<String> = Vec::new();
+
+        if !matches!(__value, $crate::value::Value::Compound(_)) {
+            __errors.push(format!("expected Compound, got {:?}", __value.tag()));
+        }
+
+        $(
+            let $field: Option<$ty> = match __value.$get($key) {
+                Some(v) => match <$ty as $crate::extract::FromValue<'_, '_>>::from_value(v) {
+                    Some(v) => Some(v),
+                    None => {
+                        __errors.push(format!(
+                            "{} ({:?}): expected {}, got {:?}",
+                            stringify!($field),
+                            $key,
+                            stringify!($ty),
+                            v.tag()
+                        ));
+                        None
+                    }
+                },
+                None => match <$ty as $crate::extract::FromValue<'_, '_>>::missing() {
+                    Some(v) => Some(v),
+                    None => {
+                        __errors.push(format!("{} ({:?}): missing", stringify!($field), $key));
+                        None
+                    }
+                },
+            };
+        )*
+
+        if __errors.is_empty() {
+            Ok(($($field.unwrap(),)*))
+        } else {
+            Err($crate::error::NBTError::invalid_fields(__errors))
+        }
+    }};
+}
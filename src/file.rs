@@ -0,0 +1,198 @@
+use std::{
+    borrow::Cow,
+    fs,
+    io::{Read, Write},
+    path::Path,
+};
+
+use crate::{
+    codec::{Endian, NBTCodec, NBTCodecTrait},
+    detect::{Compression, detect_compression},
+    error::{NBTError, Result},
+    tag::Tag,
+    value::Value,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BedrockHeader {
+    pub version: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NbtFile {
+    pub root_name: Option<String>,
+    pub value: Value<'static>,
+    pub endian: Endian,
+    pub header: Option<BedrockHeader>,
+    pub compression: Compression,
+}
+
+impl NbtFile {
+    pub fn new(value: Value<'static>, endian: Endian) -> Self {
+        Self {
+            root_name: None,
+            value,
+            endian,
+            header: None,
+            compression: Compression::None,
+        }
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = self.to_bytes()?;
+
+        let name = path
+            .file_name()
+            .ok_or_else(|| NBTError::custom_msg(format!("cannot save to {}", path.display())))?;
+        let mut temp_name = std::ffi::OsString::from(".");
+        temp_name.push(name);
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp = path.with_file_name(temp_name);
+
+        let result = write_synced(&temp, &bytes)
+            .and_then(|()| match fs::metadata(path) {
+                Ok(meta) => fs::set_permissions(&temp, meta.permissions()),
+                Err(_) => Ok(()),
+            })
+            .and_then(|()| fs::rename(&temp, path));
+        if let Err(e) = result {
+            let _ = fs::remove_file(&temp);
+            return Err(e.into());
+        }
+
+        #[cfg(unix)]
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::File::open(dir)?.sync_all()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Self::from_bytes(&data)
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes()?)?;
+        Ok(())
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let compression = detect_compression(data);
+        if compression != Compression::None {
+            return Err(unsupported_compression(compression));
+        }
+
+        let (header, payload) = match split_bedrock_header(data) {
+            Some((header, payload)) => (Some(header), payload),
+            None => (None, data),
+        };
+
+        let endian = match header {
+            Some(_) => Endian::Little,
+            None => guess_endian(payload)?,
+        };
+
+        let codec = NBTCodec::new(endian);
+        let (name, value) = codec.read_tag(&mut &payload[..])?;
+
+        Ok(Self {
+            root_name: name.map(Cow::into_owned),
+            value: value.into_owned(),
+            endian,
+            header,
+            compression,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        if self.compression != Compression::None {
+            return Err(unsupported_compression(self.compression));
+        }
+
+        let codec = NBTCodec::new(self.endian);
+
+        let mut payload = Vec::new();
+        codec.write_tag(
+            &mut payload,
+            self.root_name.as_deref().map(Cow::Borrowed),
+            &self.value,
+        )?;
+
+        let Some(header) = self.header else {
+            return Ok(payload);
+        };
+
+        let mut out = Vec::with_capacity(payload.len() + 8);
+        out.extend_from_slice(&header.version.to_le_bytes());
+        out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+}
+
+fn unsupported_compression(compression: Compression) -> NBTError {
+    NBTError::custom_msg(format!(
+        "{:?} compressed files are not supported",
+        compression
+    ))
+}
+
+pub(crate) fn split_bedrock_header(data: &[u8]) -> Option<(BedrockHeader, &[u8])> {
+    let version = i32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+    let length = u32::from_le_bytes(data.get(4..8)?.try_into().ok()?) as usize;
+    let payload = &data[8..];
+
+    if length != payload.len() || payload.first() != Some(&(Tag::Compound as u8)) {
+        return None;
+    }
+
+    Some((BedrockHeader { version }, payload))
+}
+
+pub(crate) fn parses_as(data: &[u8], endian: Endian) -> bool {
+    NBTCodec::new(endian)
+        .document_ranges(data)
+        .is_ok_and(|ranges| ranges.len() == 1)
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+pub(crate) fn guess_endian(data: &[u8]) -> Result<Endian> {
+    match (
+        parses_as(data, Endian::Big),
+        parses_as(data, Endian::Little),
+    ) {
+        (true, false) => Ok(Endian::Big),
+        (false, true) => Ok(Endian::Little),
+        _ => guess_endian_from_name(data),
+    }
+}
+
+pub(crate) fn guess_endian_from_name(data: &[u8]) -> Result<Endian> {
+    if data.len() < 3 {
+        return Err(NBTError::unexpected_eof());
+    }
+    Tag::try_from(data[0])?;
+
+    let remaining = data.len() - 3;
+    let big = u16::from_be_bytes([data[1], data[2]]) as usize;
+    let little = u16::from_le_bytes([data[1], data[2]]) as usize;
+
+    Ok(if big <= remaining || little > remaining {
+        Endian::Big
+    } else {
+        Endian::Little
+    })
+}
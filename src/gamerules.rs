@@ -0,0 +1,283 @@
+use std::{borrow::Cow, fmt::Display};
+
+use crate::{
+    error::{NBTError, Result},
+    snbt,
+    value::Value,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    Bool,
+    Int,
+}
+
+pub const KNOWN_RULES: &[(&str, RuleKind)] = &[
+    ("announceAdvancements", RuleKind::Bool),
+    ("blockExplosionDropDecay", RuleKind::Bool),
+    ("commandBlockOutput", RuleKind::Bool),
+    ("commandBlocksEnabled", RuleKind::Bool),
+    ("commandModificationBlockLimit", RuleKind::Int),
+    ("disableElytraMovementCheck", RuleKind::Bool),
+    ("disableRaids", RuleKind::Bool),
+    ("doDaylightCycle", RuleKind::Bool),
+    ("doEntityDrops", RuleKind::Bool),
+    ("doFireTick", RuleKind::Bool),
+    ("doImmediateRespawn", RuleKind::Bool),
+    ("doInsomnia", RuleKind::Bool),
+    ("doLimitedCrafting", RuleKind::Bool),
+    ("doMobLoot", RuleKind::Bool),
+    ("doMobSpawning", RuleKind::Bool),
+    ("doPatrolSpawning", RuleKind::Bool),
+    ("doTileDrops", RuleKind::Bool),
+    ("doTraderSpawning", RuleKind::Bool),
+    ("doWardenSpawning", RuleKind::Bool),
+    ("doWeatherCycle", RuleKind::Bool),
+    ("drowningDamage", RuleKind::Bool),
+    ("fallDamage", RuleKind::Bool),
+    ("fireDamage", RuleKind::Bool),
+    ("forgiveDeadPlayers", RuleKind::Bool),
+    ("freezeDamage", RuleKind::Bool),
+    ("functionCommandLimit", RuleKind::Int),
+    ("globalSoundEvents", RuleKind::Bool),
+    ("keepInventory", RuleKind::Bool),
+    ("lavaSourceConversion", RuleKind::Bool),
+    ("logAdminCommands", RuleKind::Bool),
+    ("maxCommandChainLength", RuleKind::Int),
+    ("maxCommandForkCount", RuleKind::Int),
+    ("maxEntityCramming", RuleKind::Int),
+    ("mobExplosionDropDecay", RuleKind::Bool),
+    ("mobGriefing", RuleKind::Bool),
+    ("naturalRegeneration", RuleKind::Bool),
+    ("playersSleepingPercentage", RuleKind::Int),
+    ("projectilesCanBreakBlocks", RuleKind::Bool),
+    ("pvp", RuleKind::Bool),
+    ("randomTickSpeed", RuleKind::Int),
+    ("recipesUnlock", RuleKind::Bool),
+    ("reducedDebugInfo", RuleKind::Bool),
+    ("respawnBlocksExplode", RuleKind::Bool),
+    ("sendCommandFeedback", RuleKind::Bool),
+    ("showBorderEffect", RuleKind::Bool),
+    ("showCoordinates", RuleKind::Bool),
+    ("showDaysPlayed", RuleKind::Bool),
+    ("showDeathMessages", RuleKind::Bool),
+    ("showRecipeMessages", RuleKind::Bool),
+    ("showTags", RuleKind::Bool),
+    ("snowAccumulationHeight", RuleKind::Int),
+    ("spawnRadius", RuleKind::Int),
+    ("spectatorsGenerateChunks", RuleKind::Bool),
+    ("tntExplodes", RuleKind::Bool),
+    ("tntExplosionDropDecay", RuleKind::Bool),
+    ("universalAnger", RuleKind::Bool),
+    ("waterSourceConversion", RuleKind::Bool),
+];
+
+fn known_rule(name: &str) -> Option<(&'static str, RuleKind)> {
+    KNOWN_RULES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+pub fn rule_kind(name: &str) -> Option<RuleKind> {
+    known_rule(name).map(|(_, kind)| kind)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleValue {
+    Bool(bool),
+    Int(i32),
+    Text(String),
+}
+
+impl Display for RuleValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleValue::Bool(v) => write!(f, "{}", v),
+            RuleValue::Int(v) => write!(f, "{}", v),
+            RuleValue::Text(v) => f.write_str(v),
+        }
+    }
+}
+
+impl From<bool> for RuleValue {
+    fn from(value: bool) -> Self {
+        RuleValue::Bool(value)
+    }
+}
+
+impl From<i32> for RuleValue {
+    fn from(value: i32) -> Self {
+        RuleValue::Int(value)
+    }
+}
+
+impl From<&str> for RuleValue {
+    fn from(value: &str) -> Self {
+        RuleValue::Text(value.to_owned())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleStorage {
+    Java,
+    Bedrock,
+}
+
+#[derive(Debug)]
+pub struct GameRules<'v, 'a> {
+    storage: RuleStorage,
+    parent: &'v mut Value<'a>,
+}
+
+impl<'v, 'a> GameRules<'v, 'a> {
+    pub fn from_level_dat(root: &'v mut Value<'a>) -> Result<Self> {
+        if !root.is_compound() {
+            return Err(NBTError::custom_msg("level.dat: root is not a Compound"));
+        }
+
+        if !root.get("Data").is_some_and(Value::is_compound) {
+            return Ok(Self {
+                storage: RuleStorage::Bedrock,
+                parent: root,
+            });
+        }
+
+        Ok(Self {
+            storage: RuleStorage::Java,
+            parent: root.get_mut("Data").expect("Data is a Compound"),
+        })
+    }
+
+    pub fn storage(&self) -> RuleStorage {
+        self.storage
+    }
+
+    fn rules(&self) -> Option<&Value<'a>> {
+        match self.storage {
+            RuleStorage::Java => self.parent.get("GameRules").filter(|v| v.is_compound()),
+            RuleStorage::Bedrock => Some(&*self.parent),
+        }
+    }
+
+    fn rules_mut(&mut self) -> Result<&mut Value<'a>> {
+        if self.storage == RuleStorage::Bedrock {
+            return Ok(self.parent);
+        }
+
+        if !self.parent.get("GameRules").is_some_and(Value::is_compound) {
+            self.parent.insert("GameRules", Value::compound())?;
+        }
+        Ok(self
+            .parent
+            .get_mut("GameRules")
+            .expect("GameRules was inserted"))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let Some(Value::Compound(map)) = self.rules() else {
+            return Vec::new();
+        };
+
+        map.keys()
+            .filter(|key| self.storage == RuleStorage::Java || known_rule(key).is_some())
+            .map(|key| key.to_string())
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<RuleValue> {
+        let kind = rule_kind(name);
+        if self.storage == RuleStorage::Bedrock && kind.is_none() {
+            return None;
+        }
+        let value = self.rules()?.get_ignore_case(name)?;
+
+        Some(match (value, kind) {
+            (Value::String(text), Some(RuleKind::Bool)) => match text.as_ref() {
+                "true" => RuleValue::Bool(true),
+                "false" => RuleValue::Bool(false),
+                _ => RuleValue::Text(text.to_string()),
+            },
+            (Value::String(text), Some(RuleKind::Int)) => text
+                .parse()
+                .map_or_else(|_| RuleValue::Text(text.to_string()), RuleValue::Int),
+            (Value::String(text), None) => RuleValue::Text(text.to_string()),
+            (Value::Byte(v), Some(RuleKind::Bool) | None) => RuleValue::Bool(*v != 0),
+            (Value::Byte(v), Some(RuleKind::Int)) => RuleValue::Int(*v as i32),
+            (Value::Int(v), Some(RuleKind::Bool)) => RuleValue::Bool(*v != 0),
+            (Value::Int(v), _) => RuleValue::Int(*v),
+            (other, _) => RuleValue::Text(snbt::to_string(other)),
+        })
+    }
+
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            RuleValue::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_int(&self, name: &str) -> Option<i32> {
+        match self.get(name)? {
+            RuleValue::Int(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn set<V: Into<RuleValue>>(&mut self, name: &str, value: V) -> Result<()> {
+        if self.storage == RuleStorage::Bedrock && known_rule(name).is_none() {
+            return Err(NBTError::custom_msg(format!(
+                "gamerule {}: not a known Bedrock rule, use force_set to write it anyway",
+                name
+            )));
+        }
+        self.force_set(name, value)
+    }
+
+    pub fn force_set<V: Into<RuleValue>>(&mut self, name: &str, value: V) -> Result<()> {
+        let value = value.into();
+        let known = known_rule(name);
+
+        match (known, &value) {
+            (Some((_, RuleKind::Bool)), RuleValue::Bool(_))
+            | (Some((_, RuleKind::Int)), RuleValue::Int(_))
+            | (None, _) => {}
+            (Some((rule, kind)), other) => {
+                return Err(NBTError::custom_msg(format!(
+                    "gamerule {}: expected {:?}, got {:?}",
+                    rule, kind, other
+                )));
+            }
+        }
+
+        let storage = self.storage;
+        let encoded = match (storage, value) {
+            (RuleStorage::Java, value) => Value::String(Cow::Owned(value.to_string())),
+            (RuleStorage::Bedrock, RuleValue::Bool(v)) => Value::Byte(v as i8),
+            (RuleStorage::Bedrock, RuleValue::Int(v)) => Value::Int(v),
+            (RuleStorage::Bedrock, RuleValue::Text(v)) => Value::String(Cow::Owned(v)),
+        };
+
+        let rules = self.rules_mut()?;
+        if let Some(existing) = rules.get_ignore_case_mut(name) {
+            *existing = encoded;
+            return Ok(());
+        }
+
+        let key = match (storage, known) {
+            (RuleStorage::Java, Some((rule, _))) => rule.to_owned(),
+            (RuleStorage::Java, None) => name.to_owned(),
+            (RuleStorage::Bedrock, _) => name.to_ascii_lowercase(),
+        };
+        rules.insert(key, encoded)?;
+        Ok(())
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) -> Result<()> {
+        self.set(name, value)
+    }
+
+    pub fn set_int(&mut self, name: &str, value: i32) -> Result<()> {
+        self.set(name, value)
+    }
+}
@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+
+use crate::{
+    tag::Tag,
+    value::{CompoundMap, Value},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenOptions {
+    pub max_depth: usize,
+    pub max_len: usize,
+    pub max_string_len: usize,
+}
+
+impl Default for GenOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            max_len: 8,
+            max_string_len: 16,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Generator {
+    state: u64,
+    pub options: GenOptions,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Self::with_options(seed, GenOptions::default())
+    }
+
+    pub fn with_options(seed: u64, options: GenOptions) -> Self {
+        Self {
+            state: seed,
+            options,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            return 0;
+        }
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    fn string(&mut self) -> String {
+        const ALPHABET: &[u8] =
+            b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_:.";
+
+        let len = self.below(self.options.max_string_len + 1);
+        (0..len)
+            .map(|_| ALPHABET[self.below(ALPHABET.len())] as char)
+            .collect()
+    }
+
+    fn tag(&mut self, depth: usize) -> Tag {
+        let max = if depth < self.options.max_depth {
+            12
+        } else {
+            8
+        };
+        let tag = Tag::try_from(self.below(max) as u8 + 1).unwrap();
+        match tag {
+            Tag::List | Tag::Compound if depth >= self.options.max_depth => Tag::String,
+            tag => tag,
+        }
+    }
+
+    pub fn value_of(&mut self, tag: Tag, depth: usize) -> Value<'static> {
+        let len = self.below(self.options.max_len + 1);
+
+        match tag {
+            Tag::End => Value::End,
+            Tag::Byte => Value::Byte(self.next_u64() as i8),
+            Tag::Short => Value::Short(self.next_u64() as i16),
+            Tag::Int => Value::Int(self.next_u64() as i32),
+            Tag::Long => Value::Long(self.next_u64() as i64),
+            Tag::Float => Value::Float((self.next_u64() >> 40) as f32 / (1u32 << 24) as f32),
+            Tag::Double => Value::Double((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64),
+            Tag::ByteArray => Value::ByteArray((0..len).map(|_| self.next_u64() as u8).collect()),
+            Tag::String => Value::String(Cow::Owned(self.string())),
+            Tag::IntArray => Value::IntArray((0..len).map(|_| self.next_u64() as i32).collect()),
+            Tag::LongArray => Value::LongArray((0..len).map(|_| self.next_u64() as i64).collect()),
+            Tag::List => {
+                let element = self.tag(depth + 1);
+                Value::List(
+                    (0..len)
+                        .map(|_| self.value_of(element, depth + 1))
+                        .collect(),
+                )
+            }
+            Tag::Compound => {
+                let mut map = CompoundMap::new();
+                for _ in 0..len {
+                    let key = self.string();
+                    let tag = self.tag(depth + 1);
+                    map.insert(Cow::Owned(key), self.value_of(tag, depth + 1));
+                }
+                Value::Compound(map)
+            }
+        }
+    }
+
+    pub fn value(&mut self) -> Value<'static> {
+        let tag = self.tag(0);
+        self.value_of(tag, 0)
+    }
+
+    pub fn compound(&mut self) -> Value<'static> {
+        self.value_of(Tag::Compound, 0)
+    }
+}
+
+pub fn random_compound(seed: u64, options: GenOptions) -> Value<'static> {
+    Generator::with_options(seed, options).compound()
+}
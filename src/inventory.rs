@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+
+use crate::{
+    error::{NBTError, Result},
+    extract::{Fields, insert_opt},
+    value::{CompoundMap, Value},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemLayout {
+    JavaLegacy,
+    JavaComponents,
+    Bedrock,
+}
+
+impl ItemLayout {
+    pub fn detect(value: &Value<'_>) -> Self {
+        if value.get("Name").is_some() {
+            ItemLayout::Bedrock
+        } else if value.get("count").is_some() || value.get("components").is_some() {
+            ItemLayout::JavaComponents
+        } else {
+            ItemLayout::JavaLegacy
+        }
+    }
+
+    fn id_key(self) -> &'static str {
+        match self {
+            ItemLayout::Bedrock => "Name",
+            ItemLayout::JavaLegacy | ItemLayout::JavaComponents => "id",
+        }
+    }
+
+    fn data_key(self) -> &'static str {
+        match self {
+            ItemLayout::JavaComponents => "components",
+            ItemLayout::JavaLegacy | ItemLayout::Bedrock => "tag",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStack {
+    pub layout: ItemLayout,
+    pub slot: Option<i8>,
+    pub id: String,
+    pub count: i32,
+    pub damage: Option<i16>,
+    pub data: Option<CompoundMap<'static>>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl ItemStack {
+    pub fn new<S: Into<String>>(layout: ItemLayout, id: S, count: i32) -> Self {
+        Self {
+            layout,
+            slot: None,
+            id: id.into(),
+            count,
+            damage: None,
+            data: None,
+            extra: CompoundMap::new(),
+        }
+    }
+
+    pub fn with_slot(mut self, slot: i8) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count <= 0 || self.id.is_empty() || self.id == "minecraft:air"
+    }
+
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let layout = ItemLayout::detect(&value);
+        let mut fields = Fields::new(value)?;
+        let slot = fields.take::<Option<i8>>("Slot");
+        let id = fields.take::<String>(layout.id_key());
+        let count = match layout {
+            ItemLayout::JavaComponents => fields
+                .take::<Option<i32>>("count")
+                .map(|count| count.unwrap_or(1)),
+            ItemLayout::JavaLegacy | ItemLayout::Bedrock => {
+                fields.take::<i8>("Count").map(i32::from)
+            }
+        };
+        let damage = match layout {
+            ItemLayout::JavaComponents => Some(None),
+            ItemLayout::JavaLegacy | ItemLayout::Bedrock => fields.take::<Option<i16>>("Damage"),
+        };
+        let data = fields.take_compound(layout.data_key());
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            layout,
+            slot: slot.unwrap(),
+            id: id.unwrap(),
+            count: count.unwrap(),
+            damage: damage.unwrap(),
+            data,
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        insert_opt(&mut map, "Slot", self.slot.map(Value::Byte));
+        map.insert(Cow::Borrowed(self.layout.id_key()), self.id.clone().into());
+        match self.layout {
+            ItemLayout::JavaComponents => {
+                map.insert(Cow::Borrowed("count"), self.count.into());
+            }
+            ItemLayout::JavaLegacy | ItemLayout::Bedrock => {
+                let count = self.count.clamp(0, i8::MAX as i32) as i8;
+                map.insert(Cow::Borrowed("Count"), Value::Byte(count));
+                insert_opt(&mut map, "Damage", self.damage);
+            }
+        }
+        insert_opt(
+            &mut map,
+            self.layout.data_key(),
+            self.data.clone().map(Value::Compound),
+        );
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    pub items: Vec<ItemStack>,
+}
+
+impl Inventory {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        match value {
+            Value::List(items) => Ok(Self {
+                items: items
+                    .into_iter()
+                    .map(ItemStack::from_value)
+                    .collect::<Result<_>>()?,
+            }),
+            Value::EmptyList(_) => Ok(Self { items: Vec::new() }),
+            other => Err(NBTError::custom_msg(format!(
+                "Inventory: expected List, got {:?}",
+                other.tag()
+            ))),
+        }
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        Value::List(self.items.iter().map(ItemStack::to_value).collect())
+    }
+
+    pub fn get(&self, slot: i8) -> Option<&ItemStack> {
+        self.items.iter().find(|item| item.slot == Some(slot))
+    }
+
+    pub fn get_mut(&mut self, slot: i8) -> Option<&mut ItemStack> {
+        self.items.iter_mut().find(|item| item.slot == Some(slot))
+    }
+
+    pub fn set(&mut self, slot: i8, stack: ItemStack) -> Option<ItemStack> {
+        let stack = stack.with_slot(slot);
+        match self.get_mut(slot) {
+            Some(existing) => Some(std::mem::replace(existing, stack)),
+            None => {
+                self.items.push(stack);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, slot: i8) -> Option<ItemStack> {
+        let index = self.items.iter().position(|item| item.slot == Some(slot))?;
+        Some(self.items.remove(index))
+    }
+
+    pub fn first_free_slot(&self, size: i8) -> Option<i8> {
+        (0..size).find(|&slot| self.get(slot).is_none_or(ItemStack::is_empty))
+    }
+}
@@ -0,0 +1,97 @@
+use std::io::{BufRead, Read};
+
+use crate::error::NBTError;
+
+#[derive(Debug)]
+pub struct LimitedReader<R> {
+    inner: R,
+    limit: u64,
+    consumed: u64,
+}
+
+impl<R: Read> LimitedReader<R> {
+    pub fn new(inner: R, limit: u64) -> Self {
+        Self {
+            inner,
+            limit,
+            consumed: 0,
+        }
+    }
+
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit - self.consumed
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let remaining = self.remaining();
+        if remaining == 0 {
+            return Err(std::io::Error::other(NBTError::payload_too_large(
+                self.limit,
+            )));
+        }
+
+        let len = buf.len().min(remaining.try_into().unwrap_or(usize::MAX));
+        let read = self.inner.read(&mut buf[..len])?;
+        self.consumed += read as u64;
+
+        Ok(read)
+    }
+}
+
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R: Read> CountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.position += read as u64;
+
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.inner.consume(amount);
+        self.position += amount as u64;
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    error::{NBTError, Result},
+    nibble::{NIBBLE_ARRAY_LEN, NibbleView, NibbleViewMut},
+    value::Value,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightKind {
+    Block,
+    Sky,
+}
+
+impl LightKind {
+    pub fn key(self) -> &'static str {
+        match self {
+            LightKind::Block => "BlockLight",
+            LightKind::Sky => "SkyLight",
+        }
+    }
+}
+
+fn level<'v, 'a>(chunk: &'v Value<'a>) -> &'v Value<'a> {
+    match chunk.get("Level") {
+        Some(level) if level.is_compound() => level,
+        _ => chunk,
+    }
+}
+
+fn level_mut<'v, 'a>(chunk: &'v mut Value<'a>) -> &'v mut Value<'a> {
+    match chunk.get("Level").is_some_and(Value::is_compound) {
+        true => chunk.get_mut("Level").expect("Level is a Compound"),
+        false => chunk,
+    }
+}
+
+fn sections_key(level: &Value<'_>) -> &'static str {
+    match level.get("Sections") {
+        Some(_) => "Sections",
+        None => "sections",
+    }
+}
+
+fn is_section(section: &Value<'_>, y: i8) -> bool {
+    matches!(section.get("Y"), Some(Value::Byte(v)) if *v == y)
+}
+
+pub fn status<'v>(chunk: &'v Value<'_>) -> Option<&'v str> {
+    match level(chunk).get("Status")? {
+        Value::String(status) => Some(status),
+        _ => None,
+    }
+}
+
+pub fn set_status(chunk: &mut Value<'_>, status: &str) -> Result<()> {
+    level_mut(chunk).insert("Status", status.to_owned())?;
+    Ok(())
+}
+
+pub fn is_light_on(chunk: &Value<'_>) -> bool {
+    matches!(level(chunk).get("isLightOn"), Some(Value::Byte(v)) if *v != 0)
+}
+
+pub fn set_light_on(chunk: &mut Value<'_>, on: bool) -> Result<()> {
+    level_mut(chunk).insert("isLightOn", Value::Byte(on as i8))?;
+    Ok(())
+}
+
+pub fn section_ys(chunk: &Value<'_>) -> Vec<i8> {
+    let level = level(chunk);
+    level
+        .get(sections_key(level))
+        .and_then(Value::as_list)
+        .into_iter()
+        .flatten()
+        .filter_map(|section| match section.get("Y") {
+            Some(Value::Byte(y)) => Some(*y),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn light<'v>(
+    chunk: &'v Value<'_>,
+    kind: LightKind,
+    section_y: i8,
+) -> Result<Option<NibbleView<'v>>> {
+    let level = level(chunk);
+    let section = level
+        .get(sections_key(level))
+        .and_then(Value::as_list)
+        .and_then(|sections| sections.iter().find(|s| is_section(s, section_y)));
+
+    match section.and_then(|s| s.get(kind.key())) {
+        Some(Value::ByteArray(bytes)) => NibbleView::new(bytes).map(Some),
+        Some(other) => Err(NBTError::custom_msg(format!(
+            "{}: expected ByteArray, got {:?}",
+            kind.key(),
+            other.tag()
+        ))),
+        None => Ok(None),
+    }
+}
+
+pub fn light_mut<'v>(
+    chunk: &'v mut Value<'_>,
+    kind: LightKind,
+    section_y: i8,
+) -> Result<NibbleViewMut<'v>> {
+    let level = level_mut(chunk);
+    let key = sections_key(level);
+    if level.get(key).is_none() {
+        level.insert(key, Value::List(Vec::new()))?;
+    }
+    let sections = level
+        .get_mut(key)
+        .and_then(Value::make_list_mut)
+        .ok_or_else(|| NBTError::custom_msg(format!("{}: expected a List", key)))?;
+
+    let index = match sections.iter().position(|s| is_section(s, section_y)) {
+        Some(index) => index,
+        None => {
+            let mut section = Value::compound();
+            section.insert("Y", Value::Byte(section_y))?;
+            sections.push(section);
+            sections.len() - 1
+        }
+    };
+
+    let section = &mut sections[index];
+    if section.get(kind.key()).is_none() {
+        section.insert(kind.key(), Value::ByteArray(vec![0; NIBBLE_ARRAY_LEN]))?;
+    }
+    let bytes = section
+        .get_mut(kind.key())
+        .and_then(Value::as_bytes_mut)
+        .ok_or_else(|| NBTError::custom_msg(format!("{}: expected ByteArray", kind.key())))?;
+    NibbleViewMut::new(bytes)
+}
+
+pub fn block_light<'v>(chunk: &'v Value<'_>, section_y: i8) -> Result<Option<NibbleView<'v>>> {
+    light(chunk, LightKind::Block, section_y)
+}
+
+pub fn block_light_mut<'v>(chunk: &'v mut Value<'_>, section_y: i8) -> Result<NibbleViewMut<'v>> {
+    light_mut(chunk, LightKind::Block, section_y)
+}
+
+pub fn sky_light<'v>(chunk: &'v Value<'_>, section_y: i8) -> Result<Option<NibbleView<'v>>> {
+    light(chunk, LightKind::Sky, section_y)
+}
+
+pub fn sky_light_mut<'v>(chunk: &'v mut Value<'_>, section_y: i8) -> Result<NibbleViewMut<'v>> {
+    light_mut(chunk, LightKind::Sky, section_y)
+}
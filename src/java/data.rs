@@ -0,0 +1,283 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{
+    error::{NBTError, Result},
+    extract::{Fields, FromValue},
+    file::NbtFile,
+    value::{CompoundMap, Value},
+};
+
+pub(crate) fn split_data(value: Value<'static>) -> Result<(Fields, CompoundMap<'static>)> {
+    let mut root = Fields::new(value)?;
+    let data = root.map.remove("data").unwrap_or_else(Value::compound);
+    let extra = root.finish()?;
+    Ok((Fields::new(data)?, extra))
+}
+
+pub(crate) fn join_data(
+    data: CompoundMap<'static>,
+    extra: &CompoundMap<'static>,
+) -> Value<'static> {
+    let mut map = extra.clone();
+    map.insert(Cow::Borrowed("data"), Value::Compound(data));
+    Value::Compound(map)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raid {
+    pub id: i32,
+    pub status: String,
+    pub center: [i32; 3],
+    pub omen_level: i32,
+    pub legacy_omen: bool,
+    pub groups_spawned: i32,
+    pub ticks_active: i64,
+    pub heroes: Vec<Vec<i32>>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl Raid {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let id = fields.take("Id");
+        let status = fields.take::<Option<String>>("Status");
+        let x = fields.take("CX");
+        let y = fields.take("CY");
+        let z = fields.take("CZ");
+        let legacy_omen = fields.map.contains_key("BadOmenLevel");
+        let omen_level = match legacy_omen {
+            true => fields.take::<Option<i32>>("BadOmenLevel"),
+            false => fields.take::<Option<i32>>("RaidOmenLevel"),
+        };
+        let groups_spawned = fields.take::<Option<i32>>("GroupsSpawned");
+        let ticks_active = fields.take::<Option<i64>>("TicksActive");
+        let heroes = fields
+            .take_list("HeroesOfTheVillage")
+            .iter()
+            .map(Vec::<i32>::from_value)
+            .collect::<Option<Vec<_>>>();
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            id: id.unwrap(),
+            status: status.unwrap().unwrap_or_else(|| "ongoing".to_owned()),
+            center: [x.unwrap(), y.unwrap(), z.unwrap()],
+            omen_level: omen_level.unwrap().unwrap_or_default(),
+            legacy_omen,
+            groups_spawned: groups_spawned.unwrap().unwrap_or_default(),
+            ticks_active: ticks_active.unwrap().unwrap_or_default(),
+            heroes: heroes.ok_or_else(|| {
+                NBTError::custom_msg("HeroesOfTheVillage: expected a list of IntArray")
+            })?,
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        let omen_key = match self.legacy_omen {
+            true => "BadOmenLevel",
+            false => "RaidOmenLevel",
+        };
+        map.insert(Cow::Borrowed("Id"), self.id.into());
+        map.insert(Cow::Borrowed("Status"), self.status.clone().into());
+        map.insert(Cow::Borrowed("CX"), self.center[0].into());
+        map.insert(Cow::Borrowed("CY"), self.center[1].into());
+        map.insert(Cow::Borrowed("CZ"), self.center[2].into());
+        map.insert(Cow::Borrowed(omen_key), self.omen_level.into());
+        map.insert(Cow::Borrowed("GroupsSpawned"), self.groups_spawned.into());
+        map.insert(Cow::Borrowed("TicksActive"), self.ticks_active.into());
+        map.insert(
+            Cow::Borrowed("HeroesOfTheVillage"),
+            Value::List(self.heroes.iter().cloned().map(Value::IntArray).collect()),
+        );
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Raids {
+    pub raids: Vec<Raid>,
+    pub next_id: i32,
+    pub tick: i32,
+    pub data_extra: CompoundMap<'static>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl Raids {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let (mut data, extra) = split_data(value)?;
+        let raids = data
+            .take_list("Raids")
+            .into_iter()
+            .map(Raid::from_value)
+            .collect::<Result<_>>()?;
+        let next_id = data.take::<Option<i32>>("NextAvailableID");
+        let tick = data.take::<Option<i32>>("Tick");
+        let data_extra = data.finish()?;
+
+        Ok(Self {
+            raids,
+            next_id: next_id.unwrap().unwrap_or(1),
+            tick: tick.unwrap().unwrap_or_default(),
+            data_extra,
+            extra,
+        })
+    }
+
+    pub fn from_file(file: &NbtFile) -> Result<Self> {
+        Self::from_value(file.value.clone())
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut data = self.data_extra.clone();
+        data.insert(
+            Cow::Borrowed("Raids"),
+            Value::List(self.raids.iter().map(Raid::to_value).collect()),
+        );
+        data.insert(Cow::Borrowed("NextAvailableID"), self.next_id.into());
+        data.insert(Cow::Borrowed("Tick"), self.tick.into());
+        join_data(data, &self.extra)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdCounts {
+    pub counts: BTreeMap<String, i32>,
+    pub legacy: bool,
+    pub extra: CompoundMap<'static>,
+}
+
+impl IdCounts {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let legacy = value.get("data").is_none();
+        let (counts, extra) = match legacy {
+            true => (Fields::new(value)?.finish()?, CompoundMap::new()),
+            false => {
+                let (data, extra) = split_data(value)?;
+                (data.finish()?, extra)
+            }
+        };
+
+        let counts = counts
+            .iter()
+            .map(|(key, count)| {
+                let count = match count {
+                    Value::Short(v) => Some(*v as i32),
+                    other => i32::from_value(other),
+                };
+                count
+                    .map(|count| (key.to_string(), count))
+                    .ok_or_else(|| NBTError::custom_msg(format!("{:?}: expected a number", key)))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            counts,
+            legacy,
+            extra,
+        })
+    }
+
+    pub fn from_file(file: &NbtFile) -> Result<Self> {
+        Self::from_value(file.value.clone())
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let counts = self
+            .counts
+            .iter()
+            .map(|(key, &count)| {
+                let count = match self.legacy {
+                    true => Value::Short(count as i16),
+                    false => Value::Int(count),
+                };
+                (Cow::Owned(key.clone()), count)
+            })
+            .collect();
+
+        match self.legacy {
+            true => Value::Compound(counts),
+            false => join_data(counts, &self.extra),
+        }
+    }
+
+    pub fn last_map_id(&self) -> Option<i32> {
+        self.counts.get("map").copied()
+    }
+
+    pub fn allocate_map_id(&mut self) -> i32 {
+        let next = self.last_map_id().map_or(0, |id| id + 1);
+        self.counts.insert("map".to_owned(), next);
+        next
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RandomSequences {
+    pub salt: i64,
+    pub include_world_seed: bool,
+    pub include_sequence_id: bool,
+    pub sequences: BTreeMap<String, Value<'static>>,
+    pub data_extra: CompoundMap<'static>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl RandomSequences {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let (mut data, extra) = split_data(value)?;
+        let salt = data.take::<Option<i64>>("salt");
+        let include_world_seed = data.take::<Option<bool>>("include_world_seed");
+        let include_sequence_id = data.take::<Option<bool>>("include_sequence_id");
+        let sequences = data
+            .take_compound("sequences")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, sequence)| (key.into_owned(), sequence))
+            .collect();
+        let data_extra = data.finish()?;
+
+        Ok(Self {
+            salt: salt.unwrap().unwrap_or_default(),
+            include_world_seed: include_world_seed.unwrap().unwrap_or(true),
+            include_sequence_id: include_sequence_id.unwrap().unwrap_or(true),
+            sequences,
+            data_extra,
+            extra,
+        })
+    }
+
+    pub fn from_file(file: &NbtFile) -> Result<Self> {
+        Self::from_value(file.value.clone())
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut data = self.data_extra.clone();
+        data.insert(Cow::Borrowed("salt"), self.salt.into());
+        data.insert(
+            Cow::Borrowed("include_world_seed"),
+            self.include_world_seed.into(),
+        );
+        data.insert(
+            Cow::Borrowed("include_sequence_id"),
+            self.include_sequence_id.into(),
+        );
+        data.insert(
+            Cow::Borrowed("sequences"),
+            Value::Compound(
+                self.sequences
+                    .iter()
+                    .map(|(key, sequence)| (Cow::Owned(key.clone()), sequence.clone()))
+                    .collect(),
+            ),
+        );
+        join_data(data, &self.extra)
+    }
+
+    pub fn source(&self, sequence: &str) -> Option<&[i64]> {
+        match self.sequences.get(sequence)?.get("source")? {
+            Value::LongArray(source) => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    codec::NBTCodec,
+    error::Result,
+    extract::FromValue,
+    path::{NbtPath, PathPattern},
+    value::Value,
+};
+
+const ENTITY_LISTS: &[&str] = &["Entities", "Level.Entities"];
+const ENTITY_FIELDS: &[&str] = &["UUID", "UUIDMost", "UUIDLeast", "Passengers"];
+const POSITION_FIELDS: &[&str] = &["Position", "xPos", "zPos", "Level.xPos", "Level.zPos"];
+
+pub fn uuid_from_ints(ints: [i32; 4]) -> u128 {
+    ints.iter()
+        .fold(0, |uuid, &part| (uuid << 32) | part as u32 as u128)
+}
+
+pub fn uuid_to_ints(uuid: u128) -> [i32; 4] {
+    [96, 64, 32, 0].map(|shift| (uuid >> shift) as u32 as i32)
+}
+
+pub fn format_uuid(uuid: u128) -> String {
+    let hex = format!("{:032x}", uuid);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+pub fn parse_uuid(text: &str) -> Option<u128> {
+    let hex: String = text.chars().filter(|&c| c != '-').collect();
+    match hex.len() {
+        32 => u128::from_str_radix(&hex, 16).ok(),
+        _ => None,
+    }
+}
+
+pub fn entity_uuid(entity: &Value<'_>) -> Option<u128> {
+    if let Some(Value::IntArray(ints)) = entity.get("UUID") {
+        return ints.as_slice().try_into().ok().map(uuid_from_ints);
+    }
+    let most = entity.get("UUIDMost").and_then(i64::from_value)?;
+    let least = entity.get("UUIDLeast").and_then(i64::from_value)?;
+    Some(((most as u64 as u128) << 64) | least as u64 as u128)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityLocation {
+    pub chunk: Option<(i32, i32)>,
+    pub path: NbtPath,
+}
+
+fn patterns() -> Result<Vec<PathPattern>> {
+    let entities = ENTITY_LISTS.iter().flat_map(|list| {
+        ENTITY_FIELDS
+            .iter()
+            .map(move |field| format!("{}[*].{}", list, field))
+    });
+    POSITION_FIELDS
+        .iter()
+        .map(|field| field.to_string())
+        .chain(entities)
+        .map(|pattern| pattern.parse())
+        .collect()
+}
+
+fn chunk_pos(chunk: &Value<'_>) -> Option<(i32, i32)> {
+    if let Some(Value::IntArray(pos)) = chunk.get("Position") {
+        return match pos.as_slice() {
+            [x, z] => Some((*x, *z)),
+            _ => None,
+        };
+    }
+    let level = chunk.get("Level").unwrap_or(chunk);
+    let x = level.get("xPos").and_then(i32::from_value)?;
+    let z = level.get("zPos").and_then(i32::from_value)?;
+    Some((x, z))
+}
+
+fn search(entities: &Value<'_>, path: NbtPath, uuid: u128) -> Option<NbtPath> {
+    let list = entities.as_list()?;
+    list.iter().enumerate().find_map(|(i, entity)| {
+        let path = path.clone().index(i);
+        match entity_uuid(entity) == Some(uuid) {
+            true => Some(path),
+            false => search(entity.get("Passengers")?, path.key("Passengers"), uuid),
+        }
+    })
+}
+
+fn find_in_chunk(
+    codec: &NBTCodec,
+    patterns: &[PathPattern],
+    data: &[u8],
+    uuid: u128,
+) -> Result<Option<EntityLocation>> {
+    let (_, chunk) = codec.read_pattern_projection(&mut &data[..], patterns)?;
+
+    let found = ENTITY_LISTS.iter().find_map(|list| {
+        let path: NbtPath = list.parse().ok()?;
+        search(chunk.get_path(&path)?, path, uuid)
+    });
+    Ok(found.map(|path| EntityLocation {
+        chunk: chunk_pos(&chunk),
+        path,
+    }))
+}
+
+pub fn find_entity_in_chunk(data: &[u8], uuid: u128) -> Result<Option<EntityLocation>> {
+    find_in_chunk(&NBTCodec::big_endian(), &patterns()?, data, uuid)
+}
+
+pub fn find_entity<I, B>(chunks: I, uuid: u128) -> Result<Option<EntityLocation>>
+where
+    I: IntoIterator<Item = B>,
+    B: AsRef<[u8]>,
+{
+    let codec = NBTCodec::big_endian();
+    let patterns = patterns()?;
+    for data in chunks {
+        if let Some(location) = find_in_chunk(&codec, &patterns, data.as_ref(), uuid)? {
+            return Ok(Some(location));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,212 @@
+use std::borrow::Cow;
+
+use crate::{
+    error::{NBTError, Result},
+    extract::{Fields, insert_opt},
+    file::NbtFile,
+    value::{CompoundMap, Value},
+};
+
+pub const MAP_SIZE: usize = 128;
+pub const MAP_PIXELS: usize = MAP_SIZE * MAP_SIZE;
+
+pub const BASE_COLORS: [[u8; 3]; 62] = [
+    [0, 0, 0],
+    [127, 178, 56],
+    [247, 233, 163],
+    [199, 199, 199],
+    [255, 0, 0],
+    [160, 160, 255],
+    [167, 167, 167],
+    [0, 124, 0],
+    [255, 255, 255],
+    [164, 168, 184],
+    [151, 109, 77],
+    [112, 112, 112],
+    [64, 64, 255],
+    [143, 119, 72],
+    [255, 252, 245],
+    [216, 127, 51],
+    [178, 76, 216],
+    [102, 153, 216],
+    [229, 229, 51],
+    [127, 204, 25],
+    [242, 127, 165],
+    [76, 76, 76],
+    [153, 153, 153],
+    [76, 127, 153],
+    [127, 63, 178],
+    [51, 76, 178],
+    [102, 76, 51],
+    [102, 127, 51],
+    [153, 51, 51],
+    [25, 25, 25],
+    [250, 238, 77],
+    [92, 219, 213],
+    [74, 128, 255],
+    [0, 217, 58],
+    [129, 86, 49],
+    [112, 2, 0],
+    [209, 177, 161],
+    [159, 82, 36],
+    [149, 87, 108],
+    [112, 108, 138],
+    [186, 133, 36],
+    [103, 117, 53],
+    [160, 77, 78],
+    [57, 41, 35],
+    [135, 107, 98],
+    [87, 92, 92],
+    [122, 73, 88],
+    [76, 62, 92],
+    [76, 50, 35],
+    [76, 82, 42],
+    [142, 60, 46],
+    [37, 22, 16],
+    [189, 48, 49],
+    [148, 63, 97],
+    [92, 25, 29],
+    [22, 126, 134],
+    [58, 142, 140],
+    [86, 44, 62],
+    [20, 180, 133],
+    [100, 100, 100],
+    [216, 175, 147],
+    [127, 167, 150],
+];
+
+const SHADES: [u16; 4] = [180, 220, 255, 135];
+
+pub fn color_to_rgba(color: u8) -> [u8; 4] {
+    let base = (color / 4) as usize;
+    if base == 0 || base >= BASE_COLORS.len() {
+        return [0; 4];
+    }
+
+    let shade = SHADES[(color % 4) as usize];
+    let [r, g, b] = BASE_COLORS[base].map(|c| (c as u16 * shade / 255) as u8);
+    [r, g, b, 255]
+}
+
+pub fn rgba_to_color(rgba: [u8; 4]) -> u8 {
+    if rgba[3] < 128 {
+        return 0;
+    }
+
+    let distance = |color: u8| {
+        let [r, g, b, _] = color_to_rgba(color);
+        [(r, rgba[0]), (g, rgba[1]), (b, rgba[2])]
+            .iter()
+            .map(|&(a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+
+    (4..BASE_COLORS.len() as u8 * 4)
+        .min_by_key(|&color| distance(color))
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapItem {
+    pub scale: Option<i8>,
+    pub x_center: Option<i32>,
+    pub z_center: Option<i32>,
+    pub locked: Option<bool>,
+    pub colors: Option<Vec<u8>>,
+    pub data_extra: CompoundMap<'static>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl MapItem {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut root = Fields::new(value)?;
+        let data = root.map.remove("data").unwrap_or_else(Value::compound);
+        let extra = root.finish()?;
+
+        let mut data = Fields::new(data)?;
+        let scale = data.take("scale");
+        let x_center = data.take("xCenter");
+        let z_center = data.take("zCenter");
+        let locked = data.take("locked");
+        let colors = data.take::<Option<Vec<u8>>>("colors");
+        let data_extra = data.finish()?;
+
+        let colors = colors.unwrap();
+        if let Some(colors) = colors.as_ref().filter(|c| c.len() != MAP_PIXELS) {
+            return Err(NBTError::custom_msg(format!(
+                "colors: expected {} bytes, got {}",
+                MAP_PIXELS,
+                colors.len()
+            )));
+        }
+        Ok(Self {
+            scale: scale.unwrap(),
+            x_center: x_center.unwrap(),
+            z_center: z_center.unwrap(),
+            locked: locked.unwrap(),
+            colors,
+            data_extra,
+            extra,
+        })
+    }
+
+    pub fn from_file(file: &NbtFile) -> Result<Self> {
+        Self::from_value(file.value.clone())
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut data = self.data_extra.clone();
+        insert_opt(&mut data, "scale", self.scale.map(Value::Byte));
+        insert_opt(&mut data, "xCenter", self.x_center);
+        insert_opt(&mut data, "zCenter", self.z_center);
+        insert_opt(&mut data, "locked", self.locked);
+        insert_opt(&mut data, "colors", self.colors.clone());
+
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("data"), Value::Compound(data));
+        Value::Compound(map)
+    }
+
+    fn index(x: usize, z: usize) -> Option<usize> {
+        (x < MAP_SIZE && z < MAP_SIZE).then_some(z * MAP_SIZE + x)
+    }
+
+    pub fn color_at(&self, x: usize, z: usize) -> Option<u8> {
+        self.colors.as_ref()?.get(Self::index(x, z)?).copied()
+    }
+
+    pub fn set_color(&mut self, x: usize, z: usize, color: u8) -> Result<()> {
+        let index = Self::index(x, z).ok_or_else(|| {
+            NBTError::custom_msg(format!("map pixel ({}, {}) is out of bounds", x, z))
+        })?;
+        self.colors.get_or_insert_with(|| vec![0; MAP_PIXELS])[index] = color;
+        Ok(())
+    }
+
+    pub fn to_rgba(&self) -> Vec<u8> {
+        match &self.colors {
+            Some(colors) => colors
+                .iter()
+                .flat_map(|&color| color_to_rgba(color))
+                .collect(),
+            None => vec![0; MAP_PIXELS * 4],
+        }
+    }
+
+    pub fn set_rgba(&mut self, rgba: &[u8]) -> Result<()> {
+        if rgba.len() != MAP_PIXELS * 4 {
+            return Err(NBTError::custom_msg(format!(
+                "map image: expected {} RGBA bytes, got {}",
+                MAP_PIXELS * 4,
+                rgba.len()
+            )));
+        }
+
+        self.colors = Some(
+            rgba.chunks_exact(4)
+                .map(|pixel| rgba_to_color(pixel.try_into().unwrap()))
+                .collect(),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+pub mod chunk;
+pub mod data;
+pub mod entities;
+pub mod map;
+pub mod poi;
+pub mod scoreboard;
+pub mod section;
+pub mod storage;
+pub mod structures;
+pub mod version;
@@ -0,0 +1,221 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{
+    error::{NBTError, Result},
+    extract::{Fields, FromValue},
+    value::{CompoundMap, Value},
+};
+
+pub const VILLAGER_MEMORIES: &[&str] = &[
+    "minecraft:home",
+    "minecraft:job_site",
+    "minecraft:potential_job_site",
+    "minecraft:meeting_point",
+];
+
+fn block_pos(value: &Value<'_>) -> Option<[i32; 3]> {
+    match value {
+        Value::IntArray(pos) => pos.as_slice().try_into().ok(),
+        _ => {
+            let coord = |key| value.get(key).and_then(i32::from_value);
+            Some([coord("X")?, coord("Y")?, coord("Z")?])
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoiRecord {
+    pub kind: String,
+    pub pos: [i32; 3],
+    pub free_tickets: i32,
+    pub extra: CompoundMap<'static>,
+}
+
+impl PoiRecord {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let kind = fields.take("type");
+        let pos = fields.take::<Vec<i32>>("pos");
+        let free_tickets = fields.take::<Option<i32>>("free_tickets");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            kind: kind.unwrap(),
+            pos: pos
+                .unwrap()
+                .try_into()
+                .map_err(|_| NBTError::custom_msg("pos: expected 3 ints"))?,
+            free_tickets: free_tickets.unwrap().unwrap_or_default(),
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("type"), self.kind.clone().into());
+        map.insert(Cow::Borrowed("pos"), Value::IntArray(self.pos.to_vec()));
+        map.insert(Cow::Borrowed("free_tickets"), self.free_tickets.into());
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoiSection {
+    pub valid: bool,
+    pub records: Vec<PoiRecord>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl PoiSection {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let valid = fields.take::<Option<bool>>("Valid");
+        let records = fields
+            .take_list("Records")
+            .into_iter()
+            .map(PoiRecord::from_value)
+            .collect::<Result<_>>()?;
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            valid: valid.unwrap().unwrap_or_default(),
+            records,
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("Valid"), self.valid.into());
+        map.insert(
+            Cow::Borrowed("Records"),
+            Value::List(self.records.iter().map(PoiRecord::to_value).collect()),
+        );
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoiChunk {
+    pub sections: BTreeMap<i32, PoiSection>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl PoiChunk {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let sections = fields
+            .take_compound("Sections")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(y, section)| {
+                let y = y.parse().map_err(|_| {
+                    NBTError::custom_msg(format!("Sections: bad section key {:?}", y))
+                })?;
+                Ok((y, PoiSection::from_value(section)?))
+            })
+            .collect::<Result<_>>()?;
+
+        let extra = fields.finish()?;
+        Ok(Self { sections, extra })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(
+            Cow::Borrowed("Sections"),
+            Value::Compound(
+                self.sections
+                    .iter()
+                    .map(|(y, section)| (Cow::Owned(y.to_string()), section.to_value()))
+                    .collect(),
+            ),
+        );
+        Value::Compound(map)
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &PoiRecord> {
+        self.sections
+            .values()
+            .flat_map(|section| section.records.iter())
+    }
+
+    pub fn record_at(&self, pos: [i32; 3]) -> Option<&PoiRecord> {
+        self.sections
+            .get(&pos[1].div_euclid(16))?
+            .records
+            .iter()
+            .find(|record| record.pos == pos)
+    }
+
+    pub fn record_at_mut(&mut self, pos: [i32; 3]) -> Option<&mut PoiRecord> {
+        self.sections
+            .get_mut(&pos[1].div_euclid(16))?
+            .records
+            .iter_mut()
+            .find(|record| record.pos == pos)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalPos {
+    pub dimension: String,
+    pub pos: [i32; 3],
+}
+
+impl GlobalPos {
+    pub fn from_value(value: &Value<'_>) -> Option<Self> {
+        Some(Self {
+            dimension: value.get("dimension").and_then(String::from_value)?,
+            pos: block_pos(value.get("pos")?)?,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = CompoundMap::new();
+        map.insert(Cow::Borrowed("dimension"), self.dimension.clone().into());
+        map.insert(Cow::Borrowed("pos"), Value::IntArray(self.pos.to_vec()));
+        Value::Compound(map)
+    }
+}
+
+pub fn villager_memory(entity: &Value<'_>, memory: &str) -> Option<GlobalPos> {
+    let entry = entity.get("Brain")?.get("memories")?.get(memory)?;
+    GlobalPos::from_value(entry.get("value").unwrap_or(entry))
+}
+
+pub fn villager_memories(entity: &Value<'_>) -> BTreeMap<&'static str, GlobalPos> {
+    VILLAGER_MEMORIES
+        .iter()
+        .filter_map(|&memory| Some((memory, villager_memory(entity, memory)?)))
+        .collect()
+}
+
+pub fn set_villager_memory(
+    entity: &mut Value<'static>,
+    memory: &str,
+    pos: Option<&GlobalPos>,
+) -> Result<()> {
+    if entity.get("Brain").is_none() {
+        entity.insert("Brain", Value::compound())?;
+    }
+    let brain = entity.get_mut("Brain").expect("Brain was inserted");
+    if brain.get("memories").is_none() {
+        brain.insert("memories", Value::compound())?;
+    }
+    let Some(Value::Compound(memories)) = brain.get_mut("memories") else {
+        return Err(NBTError::custom_msg("Brain.memories: expected Compound"));
+    };
+
+    match pos {
+        Some(pos) => {
+            let mut entry = CompoundMap::new();
+            entry.insert(Cow::Borrowed("value"), pos.to_value());
+            memories.insert(Cow::Owned(memory.to_owned()), Value::Compound(entry));
+        }
+        None => {
+            memories.remove(memory);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,297 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{
+    error::{NBTError, Result},
+    extract::{Fields, FromValue, insert_opt},
+    file::NbtFile,
+    value::{CompoundMap, Value},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Objective {
+    pub name: String,
+    pub criteria: String,
+    pub display_name: String,
+    pub render_type: String,
+    pub extra: CompoundMap<'static>,
+}
+
+impl Objective {
+    pub fn new<S: Into<String>>(name: S, criteria: S) -> Self {
+        let name = name.into();
+        Self {
+            display_name: format!("{{\"text\":{:?}}}", name),
+            name,
+            criteria: criteria.into(),
+            render_type: "integer".to_owned(),
+            extra: CompoundMap::new(),
+        }
+    }
+
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let name = fields.take("Name");
+        let criteria = fields.take("CriteriaName");
+        let display_name = fields.take::<Option<String>>("DisplayName");
+        let render_type = fields.take::<Option<String>>("RenderType");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            name: name.unwrap(),
+            criteria: criteria.unwrap(),
+            display_name: display_name.unwrap().unwrap_or_default(),
+            render_type: render_type.unwrap().unwrap_or_else(|| "integer".to_owned()),
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("Name"), self.name.clone().into());
+        map.insert(Cow::Borrowed("CriteriaName"), self.criteria.clone().into());
+        map.insert(
+            Cow::Borrowed("DisplayName"),
+            self.display_name.clone().into(),
+        );
+        map.insert(Cow::Borrowed("RenderType"), self.render_type.clone().into());
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    pub holder: String,
+    pub objective: String,
+    pub score: i32,
+    pub locked: bool,
+    pub extra: CompoundMap<'static>,
+}
+
+impl Score {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let holder = fields.take("Name");
+        let objective = fields.take("Objective");
+        let score = fields.take("Score");
+        let locked = fields.take::<Option<bool>>("Locked");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            holder: holder.unwrap(),
+            objective: objective.unwrap(),
+            score: score.unwrap(),
+            locked: locked.unwrap().unwrap_or_default(),
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("Name"), self.holder.clone().into());
+        map.insert(Cow::Borrowed("Objective"), self.objective.clone().into());
+        map.insert(Cow::Borrowed("Score"), self.score.into());
+        map.insert(Cow::Borrowed("Locked"), self.locked.into());
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Team {
+    pub name: String,
+    pub display_name: String,
+    pub players: Vec<String>,
+    pub color: Option<String>,
+    pub friendly_fire: Option<bool>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl Team {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let name = fields.take("Name");
+        let display_name = fields.take::<Option<String>>("DisplayName");
+        let players = fields
+            .take_list("Players")
+            .iter()
+            .map(String::from_value)
+            .collect::<Option<Vec<_>>>();
+        let color = fields.take("TeamColor");
+        let friendly_fire = fields.take("AllowFriendlyFire");
+
+        let extra = fields.finish()?;
+        Ok(Self {
+            name: name.unwrap(),
+            display_name: display_name.unwrap().unwrap_or_default(),
+            players: players
+                .ok_or_else(|| NBTError::custom_msg("Players: expected a list of String"))?,
+            color: color.unwrap(),
+            friendly_fire: friendly_fire.unwrap(),
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("Name"), self.name.clone().into());
+        map.insert(
+            Cow::Borrowed("DisplayName"),
+            self.display_name.clone().into(),
+        );
+        map.insert(
+            Cow::Borrowed("Players"),
+            Value::List(self.players.iter().cloned().map(Value::from).collect()),
+        );
+        insert_opt(&mut map, "TeamColor", self.color.clone());
+        insert_opt(&mut map, "AllowFriendlyFire", self.friendly_fire);
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scoreboard {
+    pub objectives: Vec<Objective>,
+    pub scores: Vec<Score>,
+    pub teams: Vec<Team>,
+    pub display_slots: BTreeMap<String, String>,
+    pub data_extra: CompoundMap<'static>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl Scoreboard {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut root = Fields::new(value)?;
+        let data = root.map.remove("data").unwrap_or_else(Value::compound);
+        let extra = root.finish()?;
+
+        let mut data = Fields::new(data)?;
+        let objectives = data
+            .take_list("Objectives")
+            .into_iter()
+            .map(Objective::from_value)
+            .collect::<Result<_>>()?;
+        let scores = data
+            .take_list("PlayerScores")
+            .into_iter()
+            .map(Score::from_value)
+            .collect::<Result<_>>()?;
+        let teams = data
+            .take_list("Teams")
+            .into_iter()
+            .map(Team::from_value)
+            .collect::<Result<_>>()?;
+        let display_slots = data
+            .take_compound("DisplaySlots")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|(slot, name)| Some((slot.to_string(), String::from_value(name)?)))
+            .collect();
+        let data_extra = data.finish()?;
+
+        Ok(Self {
+            objectives,
+            scores,
+            teams,
+            display_slots,
+            data_extra,
+            extra,
+        })
+    }
+
+    pub fn from_file(file: &NbtFile) -> Result<Self> {
+        Self::from_value(file.value.clone())
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut data = self.data_extra.clone();
+        data.insert(
+            Cow::Borrowed("Objectives"),
+            Value::List(self.objectives.iter().map(Objective::to_value).collect()),
+        );
+        data.insert(
+            Cow::Borrowed("PlayerScores"),
+            Value::List(self.scores.iter().map(Score::to_value).collect()),
+        );
+        data.insert(
+            Cow::Borrowed("Teams"),
+            Value::List(self.teams.iter().map(Team::to_value).collect()),
+        );
+        data.insert(
+            Cow::Borrowed("DisplaySlots"),
+            Value::Compound(
+                self.display_slots
+                    .iter()
+                    .map(|(slot, name)| (Cow::Owned(slot.clone()), Value::from(name.clone())))
+                    .collect(),
+            ),
+        );
+
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("data"), Value::Compound(data));
+        Value::Compound(map)
+    }
+
+    pub fn objective(&self, name: &str) -> Option<&Objective> {
+        self.objectives
+            .iter()
+            .find(|objective| objective.name == name)
+    }
+
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.iter().find(|team| team.name == name)
+    }
+
+    pub fn team_of(&self, holder: &str) -> Option<&Team> {
+        self.teams
+            .iter()
+            .find(|team| team.players.iter().any(|player| player == holder))
+    }
+
+    pub fn score(&self, holder: &str, objective: &str) -> Option<i32> {
+        self.scores
+            .iter()
+            .find(|score| score.holder == holder && score.objective == objective)
+            .map(|score| score.score)
+    }
+
+    pub fn set_score(&mut self, holder: &str, objective: &str, value: i32) -> Result<()> {
+        if self.objective(objective).is_none() {
+            return Err(NBTError::custom_msg(format!(
+                "scoreboard: no objective named {:?}",
+                objective
+            )));
+        }
+
+        match self
+            .scores
+            .iter_mut()
+            .find(|score| score.holder == holder && score.objective == objective)
+        {
+            Some(score) => score.score = value,
+            None => self.scores.push(Score {
+                holder: holder.to_owned(),
+                objective: objective.to_owned(),
+                score: value,
+                locked: false,
+                extra: CompoundMap::new(),
+            }),
+        }
+        Ok(())
+    }
+
+    pub fn remove_objective(&mut self, name: &str) -> Option<Objective> {
+        let index = self
+            .objectives
+            .iter()
+            .position(|objective| objective.name == name)?;
+        self.scores.retain(|score| score.objective != name);
+        self.display_slots.retain(|_, objective| objective != name);
+        Some(self.objectives.remove(index))
+    }
+
+    pub fn remove_holder(&mut self, holder: &str) {
+        self.scores.retain(|score| score.holder != holder);
+        for team in &mut self.teams {
+            team.players.retain(|player| player != holder);
+        }
+    }
+}
@@ -0,0 +1,444 @@
+use std::borrow::Cow;
+
+use crate::{
+    bitpack,
+    error::{NBTError, Result},
+    registry::{RegistryKind, RegistryResolver},
+    value::{CompoundMap, Value},
+};
+
+pub trait PaletteEntry: Clone + PartialEq + Sized {
+    const REGISTRY: RegistryKind;
+
+    fn from_value(value: &Value<'_>) -> Result<Self>;
+
+    fn to_value(&self) -> Value<'static>;
+
+    fn registry_name(&self) -> Option<Cow<'_, str>>;
+
+    fn from_registry_name(name: &str) -> Self;
+}
+
+impl PaletteEntry for Value<'static> {
+    const REGISTRY: RegistryKind = RegistryKind::Block;
+
+    fn from_value(value: &Value<'_>) -> Result<Self> {
+        match value {
+            Value::Compound(_) => Ok(value.clone().into_owned()),
+            other => Err(NBTError::custom_msg(format!(
+                "Block state: expected Compound, got {:?}",
+                other.tag()
+            ))),
+        }
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        self.clone()
+    }
+
+    fn registry_name(&self) -> Option<Cow<'_, str>> {
+        let Value::String(name) = self.get("Name")? else {
+            return None;
+        };
+        let properties = match self.get("Properties") {
+            None => return Some(Cow::Borrowed(name)),
+            Some(Value::Compound(properties)) if properties.is_empty() => {
+                return Some(Cow::Borrowed(name));
+            }
+            Some(Value::Compound(properties)) => properties,
+            Some(_) => return None,
+        };
+
+        let mut pairs = properties
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(value) => Some(format!("{}={}", key, value)),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        pairs.sort();
+        Some(Cow::Owned(format!("{}[{}]", name, pairs.join(","))))
+    }
+
+    fn from_registry_name(name: &str) -> Self {
+        let Some((name, properties)) = name.strip_suffix(']').and_then(|n| n.split_once('['))
+        else {
+            return block_state(name);
+        };
+
+        let mut state = block_state(name);
+        let mut map = CompoundMap::new();
+        for pair in properties.split(',').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            map.insert(Cow::Owned(key.to_owned()), value.to_owned().into());
+        }
+        if !map.is_empty() {
+            state.insert("Properties", Value::Compound(map)).unwrap();
+        }
+        state
+    }
+}
+
+impl PaletteEntry for String {
+    const REGISTRY: RegistryKind = RegistryKind::Biome;
+
+    fn from_value(value: &Value<'_>) -> Result<Self> {
+        match value {
+            Value::String(name) => Ok(name.to_string()),
+            other => Err(NBTError::custom_msg(format!(
+                "Biome: expected String, got {:?}",
+                other.tag()
+            ))),
+        }
+    }
+
+    fn to_value(&self) -> Value<'static> {
+        Value::from(self.clone())
+    }
+
+    fn registry_name(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+
+    fn from_registry_name(name: &str) -> Self {
+        name.to_owned()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PalettedContainer<T, const LEN: usize, const MIN_BITS: u32> {
+    pub palette: Vec<T>,
+    indices: Vec<u32>,
+}
+
+pub type BlockStatePalette = PalettedContainer<Value<'static>, 4096, 4>;
+pub type BiomePalette = PalettedContainer<String, 64, 1>;
+
+impl<T: PaletteEntry, const LEN: usize, const MIN_BITS: u32> PalettedContainer<T, LEN, MIN_BITS> {
+    pub fn filled(entry: T) -> Self {
+        Self {
+            palette: vec![entry],
+            indices: vec![0; LEN],
+        }
+    }
+
+    pub fn from_value(container: &Value<'_>) -> Result<Self> {
+        let palette = match container.get("palette") {
+            Some(Value::List(items)) if !items.is_empty() => items
+                .iter()
+                .map(T::from_value)
+                .collect::<Result<Vec<_>>>()?,
+            _ => return Err(NBTError::custom_msg("palette: missing or empty")),
+        };
+
+        let bits = bitpack::bits_for(palette.len(), MIN_BITS);
+        let data = match container.get("data") {
+            Some(Value::LongArray(data)) => data.as_slice(),
+            Some(other) => {
+                return Err(NBTError::custom_msg(format!(
+                    "data: expected LongArray, got {:?}",
+                    other.tag()
+                )));
+            }
+            None => &[],
+        };
+
+        let indices = bitpack::unpack(data, bits, LEN)?;
+        if let Some(&index) = indices.iter().find(|&&i| i as usize >= palette.len()) {
+            return Err(NBTError::custom_msg(format!(
+                "Palette index {} out of range for palette of {}",
+                index,
+                palette.len()
+            )));
+        }
+
+        Ok(Self { palette, indices })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut container = Value::compound();
+        container
+            .insert(
+                "palette",
+                Value::List(self.palette.iter().map(T::to_value).collect()),
+            )
+            .unwrap();
+
+        let bits = bitpack::bits_for(self.palette.len(), MIN_BITS);
+        if bits > 0 {
+            container
+                .insert("data", Value::LongArray(bitpack::pack(&self.indices, bits)))
+                .unwrap();
+        }
+
+        container
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        Some(&self.palette[*self.indices.get(index)? as usize])
+    }
+
+    pub fn set(&mut self, index: usize, entry: T) -> Result<()> {
+        if index >= LEN {
+            return Err(NBTError::custom_msg(format!(
+                "{:?} index {} is out of range for {} entries",
+                T::REGISTRY,
+                index,
+                LEN
+            )));
+        }
+
+        let palette_index = match self.palette.iter().position(|e| *e == entry) {
+            Some(i) => i,
+            None => {
+                self.palette.push(entry);
+                self.palette.len() - 1
+            }
+        };
+
+        self.indices[index] = palette_index as u32;
+        Ok(())
+    }
+
+    fn resolve_entry<R: RegistryResolver + ?Sized>(&self, entry: &T, resolver: &R) -> Result<u32> {
+        let name = entry.registry_name().ok_or_else(|| {
+            NBTError::custom_msg(format!("{:?} palette entry has no name", T::REGISTRY))
+        })?;
+        resolver.numeric_id(T::REGISTRY, &name).ok_or_else(|| {
+            NBTError::custom_msg(format!("{:?} {} has no numeric ID", T::REGISTRY, name))
+        })
+    }
+
+    fn resolve_id<R: RegistryResolver + ?Sized>(id: u32, resolver: &R) -> Result<T> {
+        resolver
+            .name(T::REGISTRY, id)
+            .map(|name| T::from_registry_name(&name))
+            .ok_or_else(|| {
+                NBTError::custom_msg(format!("{:?} ID {} is not registered", T::REGISTRY, id))
+            })
+    }
+
+    pub fn get_id<R: RegistryResolver + ?Sized>(&self, index: usize, resolver: &R) -> Result<u32> {
+        let entry = self.get(index).ok_or_else(|| {
+            NBTError::custom_msg(format!(
+                "{:?} index {} is out of range for {} entries",
+                T::REGISTRY,
+                index,
+                LEN
+            ))
+        })?;
+        self.resolve_entry(entry, resolver)
+    }
+
+    pub fn set_id<R: RegistryResolver + ?Sized>(
+        &mut self,
+        index: usize,
+        id: u32,
+        resolver: &R,
+    ) -> Result<()> {
+        let entry = Self::resolve_id(id, resolver)?;
+        self.set(index, entry)
+    }
+
+    pub fn to_ids<R: RegistryResolver + ?Sized>(&self, resolver: &R) -> Result<Vec<u32>> {
+        let palette = self
+            .palette
+            .iter()
+            .map(|entry| self.resolve_entry(entry, resolver))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.indices.iter().map(|&i| palette[i as usize]).collect())
+    }
+
+    pub fn from_ids<R: RegistryResolver + ?Sized>(ids: &[u32], resolver: &R) -> Result<Self> {
+        if ids.len() != LEN {
+            return Err(NBTError::custom_msg(format!(
+                "{:?} IDs: expected {} values, got {}",
+                T::REGISTRY,
+                LEN,
+                ids.len()
+            )));
+        }
+
+        let mut palette_ids = Vec::new();
+        let mut indices = Vec::with_capacity(LEN);
+        for &id in ids {
+            let index = match palette_ids.iter().position(|&p| p == id) {
+                Some(i) => i,
+                None => {
+                    palette_ids.push(id);
+                    palette_ids.len() - 1
+                }
+            };
+            indices.push(index as u32);
+        }
+
+        let palette = palette_ids
+            .into_iter()
+            .map(|id| Self::resolve_id(id, resolver))
+            .collect::<Result<_>>()?;
+        Ok(Self { palette, indices })
+    }
+}
+
+fn cell_index(x: usize, y: usize, z: usize, size: usize) -> Option<usize> {
+    (x < size && y < size && z < size).then(|| (y * size + z) * size + x)
+}
+
+fn checked_index(x: usize, y: usize, z: usize, size: usize) -> Result<usize> {
+    cell_index(x, y, z, size).ok_or_else(|| {
+        NBTError::custom_msg(format!(
+            "({}, {}, {}) is outside a {}x{}x{} section",
+            x, y, z, size, size, size
+        ))
+    })
+}
+
+impl BlockStatePalette {
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> Option<&Value<'static>> {
+        self.get(cell_index(x, y, z, 16)?)
+    }
+
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, state: Value<'static>) -> Result<()> {
+        self.set(checked_index(x, y, z, 16)?, state)
+    }
+
+    pub fn get_block_id<R: RegistryResolver + ?Sized>(
+        &self,
+        x: usize,
+        y: usize,
+        z: usize,
+        resolver: &R,
+    ) -> Result<u32> {
+        self.get_id(checked_index(x, y, z, 16)?, resolver)
+    }
+
+    pub fn set_block_id<R: RegistryResolver + ?Sized>(
+        &mut self,
+        x: usize,
+        y: usize,
+        z: usize,
+        id: u32,
+        resolver: &R,
+    ) -> Result<()> {
+        self.set_id(checked_index(x, y, z, 16)?, id, resolver)
+    }
+}
+
+impl BiomePalette {
+    pub fn get_biome(&self, x: usize, y: usize, z: usize) -> Option<&str> {
+        self.get(cell_index(x, y, z, 4)?).map(String::as_str)
+    }
+
+    pub fn set_biome<S: Into<String>>(
+        &mut self,
+        x: usize,
+        y: usize,
+        z: usize,
+        biome: S,
+    ) -> Result<()> {
+        self.set(checked_index(x, y, z, 4)?, biome.into())
+    }
+
+    pub fn get_biome_id<R: RegistryResolver + ?Sized>(
+        &self,
+        x: usize,
+        y: usize,
+        z: usize,
+        resolver: &R,
+    ) -> Result<u32> {
+        self.get_id(checked_index(x, y, z, 4)?, resolver)
+    }
+
+    pub fn set_biome_id<R: RegistryResolver + ?Sized>(
+        &mut self,
+        x: usize,
+        y: usize,
+        z: usize,
+        id: u32,
+        resolver: &R,
+    ) -> Result<()> {
+        self.set_id(checked_index(x, y, z, 4)?, id, resolver)
+    }
+}
+
+pub fn block_state(name: &str) -> Value<'static> {
+    let mut state = Value::compound();
+    state.insert("Name", name.to_owned()).unwrap();
+    state
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkSection {
+    pub y: i8,
+    pub block_states: Option<BlockStatePalette>,
+    pub biomes: Option<BiomePalette>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl ChunkSection {
+    pub fn new(y: i8) -> Self {
+        Self {
+            y,
+            block_states: None,
+            biomes: None,
+            extra: CompoundMap::new(),
+        }
+    }
+
+    pub fn from_value(value: &Value<'_>) -> Result<Self> {
+        let Value::Compound(map) = value.clone().into_owned() else {
+            return Err(NBTError::custom_msg(format!(
+                "Section: expected Compound, got {:?}",
+                value.tag()
+            )));
+        };
+        let mut extra = map;
+
+        let y = match extra.remove("Y") {
+            Some(Value::Byte(y)) => y,
+            _ => return Err(NBTError::custom_msg("Y: missing or not a Byte")),
+        };
+        let block_states = extra
+            .remove("block_states")
+            .map(|v| BlockStatePalette::from_value(&v))
+            .transpose()?;
+        let biomes = extra
+            .remove("biomes")
+            .map(|v| BiomePalette::from_value(&v))
+            .transpose()?;
+
+        Ok(Self {
+            y,
+            block_states,
+            biomes,
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("Y"), Value::Byte(self.y));
+        if let Some(block_states) = &self.block_states {
+            map.insert(Cow::Borrowed("block_states"), block_states.to_value());
+        }
+        if let Some(biomes) = &self.biomes {
+            map.insert(Cow::Borrowed("biomes"), biomes.to_value());
+        }
+        Value::Compound(map)
+    }
+
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> Option<&Value<'static>> {
+        self.block_states.as_ref()?.get_block(x, y, z)
+    }
+
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, state: Value<'static>) -> Result<()> {
+        checked_index(x, y, z, 16)?;
+        self.block_states
+            .get_or_insert_with(|| BlockStatePalette::filled(block_state("minecraft:air")))
+            .set_block(x, y, z, state)
+    }
+
+    pub fn get_biome(&self, x: usize, y: usize, z: usize) -> Option<&str> {
+        self.biomes.as_ref()?.get_biome(x, y, z)
+    }
+}
@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+
+use crate::{
+    error::{NBTError, Result},
+    file::NbtFile,
+    java::data::{join_data, split_data},
+    value::{CompoundMap, Value},
+};
+
+pub fn namespace_from_file_name(file_name: &str) -> Option<&str> {
+    let name = file_name.rsplit(['/', '\\']).next()?;
+    name.strip_prefix("command_storage_")?.strip_suffix(".dat")
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandStorage {
+    pub namespace: Option<String>,
+    pub contents: CompoundMap<'static>,
+    pub data_extra: CompoundMap<'static>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl CommandStorage {
+    pub fn new(namespace: &str) -> Self {
+        Self {
+            namespace: Some(namespace.to_owned()),
+            ..Self::default()
+        }
+    }
+
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let (mut data, extra) = split_data(value)?;
+        let contents = data.take_compound("contents").unwrap_or_default();
+        if let Some((key, other)) = contents.iter().find(|(_, v)| !v.is_compound()) {
+            return Err(NBTError::custom_msg(format!(
+                "contents.{}: expected Compound, got {:?}",
+                key,
+                other.tag()
+            )));
+        }
+        let data_extra = data.finish()?;
+
+        Ok(Self {
+            namespace: None,
+            contents,
+            data_extra,
+            extra,
+        })
+    }
+
+    pub fn from_file(file: &NbtFile, file_name: &str) -> Result<Self> {
+        let mut storage = Self::from_value(file.value.clone())?;
+        storage.namespace = namespace_from_file_name(file_name).map(str::to_owned);
+        Ok(storage)
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut data = self.data_extra.clone();
+        data.insert(
+            Cow::Borrowed("contents"),
+            Value::Compound(self.contents.clone()),
+        );
+        join_data(data, &self.extra)
+    }
+
+    pub fn file_name(&self) -> Option<String> {
+        self.namespace
+            .as_ref()
+            .map(|namespace| format!("command_storage_{}.dat", namespace))
+    }
+
+    fn key<'i>(&self, id: &'i str) -> Result<&'i str> {
+        let Some((namespace, path)) = id.split_once(':') else {
+            return Ok(id);
+        };
+        match &self.namespace {
+            Some(own) if own != namespace => Err(NBTError::custom_msg(format!(
+                "storage {}: belongs to namespace {}, not {}",
+                id, namespace, own
+            ))),
+            _ => Ok(path),
+        }
+    }
+
+    pub fn ids(&self) -> Vec<String> {
+        self.contents
+            .keys()
+            .map(|path| match &self.namespace {
+                Some(namespace) => format!("{}:{}", namespace, path),
+                None => path.to_string(),
+            })
+            .collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Value<'static>> {
+        self.contents.get(self.key(id).ok()?)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Value<'static>> {
+        let key = self.key(id).ok()?;
+        self.contents.get_mut(key)
+    }
+
+    pub fn set(&mut self, id: &str, value: Value<'static>) -> Result<Option<Value<'static>>> {
+        if !value.is_compound() {
+            return Err(NBTError::custom_msg(format!(
+                "storage {}: expected Compound, got {:?}",
+                id,
+                value.tag()
+            )));
+        }
+        let key = self.key(id)?.to_owned();
+        Ok(self.contents.insert(Cow::Owned(key), value))
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Value<'static>> {
+        let key = self.key(id).ok()?;
+        self.contents.remove(key)
+    }
+}
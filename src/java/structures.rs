@@ -0,0 +1,277 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{
+    error::{NBTError, Result},
+    extract::Fields,
+    file::NbtFile,
+    java::data::{join_data, split_data},
+    value::{CompoundMap, Value},
+};
+
+pub const LEGACY_STRUCTURE_FILES: &[&str] = &[
+    "EndCity",
+    "Fortress",
+    "Mansion",
+    "Mineshaft",
+    "Monument",
+    "Stronghold",
+    "Temple",
+    "Village",
+];
+
+pub fn kind_from_file_name(file_name: &str) -> Option<&str> {
+    let name = file_name.rsplit(['/', '\\']).next()?.strip_suffix(".dat")?;
+    LEGACY_STRUCTURE_FILES
+        .iter()
+        .copied()
+        .find(|kind| *kind == name)
+}
+
+pub fn pack_chunk_pos(x: i32, z: i32) -> i64 {
+    (x as u32 as i64) | ((z as i64) << 32)
+}
+
+pub fn unpack_chunk_pos(packed: i64) -> (i32, i32) {
+    (packed as i32, (packed >> 32) as i32)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: [i32; 3],
+    pub max: [i32; 3],
+}
+
+impl BoundingBox {
+    pub fn from_value(value: &Value<'_>) -> Result<Self> {
+        match value {
+            Value::IntArray(bb) if bb.len() == 6 => Ok(Self {
+                min: [bb[0], bb[1], bb[2]],
+                max: [bb[3], bb[4], bb[5]],
+            }),
+            other => Err(NBTError::custom_msg(format!(
+                "BB: expected IntArray of 6, got {:?}",
+                other.tag()
+            ))),
+        }
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        Value::IntArray([self.min, self.max].concat())
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = (i32, i32)> + use<> {
+        let [x0, _, z0] = self.min.map(|v| v >> 4);
+        let [x1, _, z1] = self.max.map(|v| v >> 4);
+        (x0..=x1).flat_map(move |x| (z0..=z1).map(move |z| (x, z)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructurePiece {
+    pub id: String,
+    pub bounding_box: BoundingBox,
+    pub extra: CompoundMap<'static>,
+}
+
+impl StructurePiece {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let id = fields.take("id");
+        let bounding_box = fields.map.remove("BB");
+        let extra = fields.finish()?;
+
+        Ok(Self {
+            id: id.unwrap(),
+            bounding_box: BoundingBox::from_value(&bounding_box.unwrap_or(Value::End))?,
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("id"), self.id.clone().into());
+        map.insert(Cow::Borrowed("BB"), self.bounding_box.to_value());
+        Value::Compound(map)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructureStart {
+    pub id: String,
+    pub chunk: (i32, i32),
+    pub bounding_box: Option<BoundingBox>,
+    pub children: Vec<StructurePiece>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl StructureStart {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let mut fields = Fields::new(value)?;
+        let id = fields.take("id");
+        let x = fields.take("ChunkX");
+        let z = fields.take("ChunkZ");
+        let bounding_box = fields.map.remove("BB");
+        let children = fields
+            .take_list("Children")
+            .into_iter()
+            .map(StructurePiece::from_value)
+            .collect::<Result<_>>()?;
+        let extra = fields.finish()?;
+
+        Ok(Self {
+            id: id.unwrap(),
+            chunk: (x.unwrap(), z.unwrap()),
+            bounding_box: bounding_box
+                .as_ref()
+                .map(BoundingBox::from_value)
+                .transpose()?,
+            children,
+            extra,
+        })
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let mut map = self.extra.clone();
+        map.insert(Cow::Borrowed("id"), self.id.clone().into());
+        map.insert(Cow::Borrowed("ChunkX"), self.chunk.0.into());
+        map.insert(Cow::Borrowed("ChunkZ"), self.chunk.1.into());
+        if let Some(bounding_box) = &self.bounding_box {
+            map.insert(Cow::Borrowed("BB"), bounding_box.to_value());
+        }
+        map.insert(
+            Cow::Borrowed("Children"),
+            Value::List(self.children.iter().map(StructurePiece::to_value).collect()),
+        );
+        Value::Compound(map)
+    }
+
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        let mut boxes = self
+            .bounding_box
+            .iter()
+            .chain(self.children.iter().map(|c| &c.bounding_box));
+        let first = *boxes.next()?;
+        Some(boxes.fold(first, |acc, bb| BoundingBox {
+            min: [0, 1, 2].map(|i| acc.min[i].min(bb.min[i])),
+            max: [0, 1, 2].map(|i| acc.max[i].max(bb.max[i])),
+        }))
+    }
+
+    pub fn modern_id(&self) -> Option<&'static str> {
+        let first_child = self.children.first().map(|c| c.id.as_str());
+        Some(match (self.id.as_str(), first_child) {
+            ("Fortress", _) => "minecraft:fortress",
+            ("Mineshaft", _) => "minecraft:mineshaft",
+            ("Monument", _) => "minecraft:monument",
+            ("Stronghold", _) => "minecraft:stronghold",
+            ("EndCity", _) => "minecraft:end_city",
+            ("Mansion", _) => "minecraft:mansion",
+            ("Village", _) => "minecraft:village",
+            ("Temple", Some("TeDP")) => "minecraft:desert_pyramid",
+            ("Temple", Some("TeJP")) => "minecraft:jungle_pyramid",
+            ("Temple", Some("TeSH")) => "minecraft:swamp_hut",
+            ("Temple", Some("Iglu")) => "minecraft:igloo",
+            _ => return None,
+        })
+    }
+
+    pub fn to_modern(&self) -> Result<Value<'static>> {
+        let id = self.modern_id().ok_or_else(|| {
+            NBTError::custom_msg(format!("structure {}: no modern equivalent", self.id))
+        })?;
+        let mut start = self.to_value();
+        start.insert("id", id.to_owned())?;
+        start.insert("references", Value::Int(0))?;
+        Ok(start)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LegacyStructures {
+    pub kind: Option<String>,
+    pub starts: Vec<StructureStart>,
+    pub data_extra: CompoundMap<'static>,
+    pub extra: CompoundMap<'static>,
+}
+
+impl LegacyStructures {
+    pub fn from_value(value: Value<'static>) -> Result<Self> {
+        let (mut data, extra) = split_data(value)?;
+        let starts = data
+            .take_compound("Features")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, start)| StructureStart::from_value(start))
+            .collect::<Result<Vec<_>>>()?;
+        let data_extra = data.finish()?;
+
+        Ok(Self {
+            kind: starts.first().map(|start| start.id.clone()),
+            starts,
+            data_extra,
+            extra,
+        })
+    }
+
+    pub fn from_file(file: &NbtFile, file_name: &str) -> Result<Self> {
+        let mut structures = Self::from_value(file.value.clone())?;
+        if let Some(kind) = kind_from_file_name(file_name) {
+            structures.kind = Some(kind.to_owned());
+        }
+        Ok(structures)
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        let features = self
+            .starts
+            .iter()
+            .map(|start| {
+                let key = format!("[{},{}]", start.chunk.0, start.chunk.1);
+                (Cow::Owned(key), start.to_value())
+            })
+            .collect();
+
+        let mut data = self.data_extra.clone();
+        data.insert(Cow::Borrowed("Features"), Value::Compound(features));
+        join_data(data, &self.extra)
+    }
+
+    pub fn start_at(&self, chunk_x: i32, chunk_z: i32) -> Option<&StructureStart> {
+        self.starts
+            .iter()
+            .find(|start| start.chunk == (chunk_x, chunk_z))
+    }
+
+    pub fn references(&self) -> BTreeMap<(i32, i32), Vec<&StructureStart>> {
+        let mut references: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for start in &self.starts {
+            for chunk in start.bounds().iter().flat_map(BoundingBox::chunks) {
+                references.entry(chunk).or_default().push(start);
+            }
+        }
+        references
+    }
+
+    pub fn modern_references(&self, chunk_x: i32, chunk_z: i32) -> Value<'static> {
+        let mut references: BTreeMap<&str, Vec<i64>> = BTreeMap::new();
+        let starts = self
+            .references()
+            .remove(&(chunk_x, chunk_z))
+            .unwrap_or_default();
+        for start in starts {
+            if let Some(id) = start.modern_id() {
+                references
+                    .entry(id)
+                    .or_default()
+                    .push(pack_chunk_pos(start.chunk.0, start.chunk.1));
+            }
+        }
+
+        Value::Compound(
+            references
+                .into_iter()
+                .map(|(id, packed)| (Cow::Borrowed(id), Value::LongArray(packed)))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::Display;
+
+use crate::{
+    error::{NBTError, Result},
+    file::NbtFile,
+    value::Value,
+};
+
+pub const RELEASES: &[(i32, &str)] = &[
+    (169, "1.9"),
+    (175, "1.9.1"),
+    (176, "1.9.2"),
+    (184, "1.9.4"),
+    (510, "1.10"),
+    (511, "1.10.1"),
+    (512, "1.10.2"),
+    (819, "1.11"),
+    (921, "1.11.1"),
+    (922, "1.11.2"),
+    (1139, "1.12"),
+    (1241, "1.12.1"),
+    (1343, "1.12.2"),
+    (1519, "1.13"),
+    (1628, "1.13.1"),
+    (1631, "1.13.2"),
+    (1952, "1.14"),
+    (1957, "1.14.1"),
+    (1963, "1.14.2"),
+    (1968, "1.14.3"),
+    (1976, "1.14.4"),
+    (2225, "1.15"),
+    (2227, "1.15.1"),
+    (2230, "1.15.2"),
+    (2566, "1.16"),
+    (2567, "1.16.1"),
+    (2578, "1.16.2"),
+    (2580, "1.16.3"),
+    (2584, "1.16.4"),
+    (2586, "1.16.5"),
+    (2724, "1.17"),
+    (2730, "1.17.1"),
+    (2860, "1.18"),
+    (2865, "1.18.1"),
+    (2975, "1.18.2"),
+    (3105, "1.19"),
+    (3117, "1.19.1"),
+    (3120, "1.19.2"),
+    (3218, "1.19.3"),
+    (3337, "1.19.4"),
+    (3463, "1.20"),
+    (3465, "1.20.1"),
+    (3578, "1.20.2"),
+    (3698, "1.20.3"),
+    (3700, "1.20.4"),
+    (3837, "1.20.5"),
+    (3839, "1.20.6"),
+    (3953, "1.21"),
+    (3955, "1.21.1"),
+    (4080, "1.21.2"),
+    (4082, "1.21.3"),
+    (4189, "1.21.4"),
+    (4325, "1.21.5"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DataVersion(pub i32);
+
+impl DataVersion {
+    pub fn from_value(root: &Value<'_>) -> Result<Self> {
+        let version = root
+            .get("DataVersion")
+            .or_else(|| root.get("Data").and_then(|data| data.get("DataVersion")));
+
+        match version {
+            Some(Value::Int(version)) => Ok(DataVersion(*version)),
+            Some(other) => Err(NBTError::custom_msg(format!(
+                "DataVersion: expected Int, got {:?}",
+                other.tag()
+            ))),
+            None => Err(NBTError::custom_msg("DataVersion: missing")),
+        }
+    }
+
+    pub fn from_file(file: &NbtFile) -> Result<Self> {
+        Self::from_value(&file.value)
+    }
+
+    pub fn from_release(name: &str) -> Option<Self> {
+        RELEASES
+            .iter()
+            .find(|(_, release)| *release == name)
+            .map(|&(version, _)| DataVersion(version))
+    }
+
+    pub fn release(self) -> Option<&'static str> {
+        RELEASES
+            .binary_search_by_key(&self.0, |&(version, _)| version)
+            .ok()
+            .map(|i| RELEASES[i].1)
+    }
+
+    pub fn nearest_release(self) -> Option<&'static str> {
+        let index = RELEASES.partition_point(|&(version, _)| version <= self.0);
+        index.checked_sub(1).map(|i| RELEASES[i].1)
+    }
+
+    pub fn is_at_least(self, release: &str) -> bool {
+        Self::from_release(release).is_some_and(|version| self >= version)
+    }
+}
+
+impl Display for DataVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.release(), self.nearest_release()) {
+            (Some(release), _) => write!(f, "{} ({})", release, self.0),
+            (None, Some(release)) => write!(f, "after {} ({})", release, self.0),
+            (None, None) => write!(f, "{}", self.0),
+        }
+    }
+}
@@ -1,4 +1,35 @@
+pub mod bedrock;
+pub mod bitpack;
 pub mod codec;
+pub mod codegen;
+pub mod dedup;
+pub mod detect;
+pub mod diff;
+pub mod digest;
+pub mod editor;
+pub mod encoding;
 pub mod error;
+pub mod extract;
+pub mod file;
+pub mod gamerules;
+pub mod generate;
+pub mod inventory;
+pub mod io;
+pub mod java;
+pub mod list;
+pub mod map;
+pub mod migrate;
+pub mod nibble;
+pub mod path;
+pub mod registry;
+pub mod schema;
+pub mod shared;
+#[cfg(feature = "test-support")]
+pub mod snapshot;
+pub mod snbt;
 pub mod tag;
+#[cfg(feature = "serde")]
+pub mod transcode;
 pub mod value;
+mod varint;
+pub mod xml;
@@ -0,0 +1,148 @@
+use std::marker::PhantomData;
+
+use crate::{
+    error::{NBTError, Result},
+    tag::Tag,
+    value::Value,
+};
+
+pub trait NbtScalar: Copy + 'static {
+    const TAG: Tag;
+
+    fn from_value(value: &Value<'_>) -> Option<Self>;
+}
+
+macro_rules! impl_nbt_scalar {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl NbtScalar for $ty {
+                const TAG: Tag = Tag::$variant;
+
+                fn from_value(value: &Value<'_>) -> Option<Self> {
+                    match value {
+                        Value::$variant(v) => Some(*v),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_nbt_scalar!(
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+);
+
+#[derive(Debug, Clone, Copy)]
+pub struct NbtList<'v, 'a, T: NbtScalar> {
+    items: &'v [Value<'a>],
+    _marker: PhantomData<T>,
+}
+
+impl<'v, 'a, T: NbtScalar> NbtList<'v, 'a, T> {
+    pub fn new(value: &'v Value<'a>) -> Result<Self> {
+        let Some(items) = value.as_list() else {
+            return Err(NBTError::custom_msg(format!(
+                "Expected List, got {:?}",
+                value.tag()
+            )));
+        };
+
+        for (i, item) in items.iter().enumerate() {
+            if item.tag() != T::TAG {
+                return Err(NBTError::custom_msg(format!(
+                    "List type mismatch at index {}: expected {:?}, got {:?}",
+                    i,
+                    T::TAG,
+                    item.tag()
+                )));
+            }
+        }
+
+        Ok(Self {
+            items,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.items.get(index).and_then(T::from_value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = T> + 'v {
+        self.items.iter().filter_map(T::from_value)
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn as_typed_list<T: NbtScalar>(&self) -> Result<NbtList<'_, 'a, T>> {
+        NbtList::new(self)
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn wrap_heterogeneous_lists(&mut self) {
+        match self {
+            Value::Compound(map) => map.values_mut().for_each(Value::wrap_heterogeneous_lists),
+            Value::List(list) => {
+                list.iter_mut().for_each(Value::wrap_heterogeneous_lists);
+
+                let Some(first) = list.first().map(Value::tag) else {
+                    return;
+                };
+                if list.iter().all(|v| v.tag() == first) {
+                    return;
+                }
+
+                for item in list.iter_mut() {
+                    if item.tag() != Tag::Compound || is_wrapper(item) {
+                        let inner = std::mem::replace(item, Value::compound());
+                        item.insert("", inner).unwrap();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn unwrap_heterogeneous_lists(&mut self) {
+        match self {
+            Value::Compound(map) => map.values_mut().for_each(Value::unwrap_heterogeneous_lists),
+            Value::List(list) => {
+                if list.iter().all(|v| v.tag() == Tag::Compound) {
+                    for item in list.iter_mut() {
+                        if is_wrapper(item)
+                            && let Value::Compound(map) = item
+                        {
+                            *item = map.remove("").unwrap();
+                        }
+                    }
+                }
+
+                list.iter_mut().for_each(Value::unwrap_heterogeneous_lists);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_wrapper(value: &Value<'_>) -> bool {
+    matches!(value, Value::Compound(map) if map.len() == 1 && map.contains_key(""))
+}
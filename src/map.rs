@@ -0,0 +1,538 @@
+use std::{
+    borrow::{Borrow, Cow},
+    collections::{BTreeMap, btree_map},
+    fmt::Debug,
+    ops::Index,
+    slice,
+};
+
+use crate::value::Value;
+
+#[cfg(feature = "preserve-order")]
+mod ordered;
+
+#[cfg(feature = "preserve-order")]
+pub use ordered::{Ordered, OrderedMap};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub trait MapBackend:
+    sealed::Sealed + Debug + Clone + Copy + PartialEq + Eq + Default + 'static
+{
+    type Map<'a>: CompoundOps<'a, Self>;
+}
+
+pub trait CompoundOps<'a, M: MapBackend>:
+    Clone
+    + Debug
+    + PartialEq
+    + Default
+    + IntoIterator<Item = (Cow<'a, str>, Value<'a, M>)>
+    + FromIterator<(Cow<'a, str>, Value<'a, M>)>
+{
+    type Iter<'m>: DoubleEndedIterator<Item = (&'m Cow<'a, str>, &'m Value<'a, M>)>
+        + ExactSizeIterator
+    where
+        Self: 'm,
+        'a: 'm;
+
+    type IterMut<'m>: DoubleEndedIterator<Item = (&'m Cow<'a, str>, &'m mut Value<'a, M>)>
+        + ExactSizeIterator
+    where
+        Self: 'm,
+        'a: 'm;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, key: &str) -> Option<&Value<'a, M>>;
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Value<'a, M>>;
+
+    fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn insert(&mut self, key: Cow<'a, str>, value: Value<'a, M>) -> Option<Value<'a, M>>;
+
+    fn remove_entry(&mut self, key: &str) -> Option<(Cow<'a, str>, Value<'a, M>)>;
+
+    fn take_entry(&mut self, key: &str) -> Option<(Cow<'a, str>, Value<'a, M>)>;
+
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sorted;
+
+impl sealed::Sealed for Sorted {}
+
+impl MapBackend for Sorted {
+    type Map<'a> = SmallMap<Cow<'a, str>, Value<'a, Sorted>>;
+}
+
+impl<'a, M: MapBackend> CompoundOps<'a, M> for SmallMap<Cow<'a, str>, Value<'a, M>> {
+    type Iter<'m>
+        = Iter<'m, Cow<'a, str>, Value<'a, M>>
+    where
+        Self: 'm,
+        'a: 'm;
+
+    type IterMut<'m>
+        = IterMut<'m, Cow<'a, str>, Value<'a, M>>
+    where
+        Self: 'm,
+        'a: 'm;
+
+    fn len(&self) -> usize {
+        SmallMap::len(self)
+    }
+
+    fn get(&self, key: &str) -> Option<&Value<'a, M>> {
+        SmallMap::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Value<'a, M>> {
+        SmallMap::get_mut(self, key)
+    }
+
+    fn insert(&mut self, key: Cow<'a, str>, value: Value<'a, M>) -> Option<Value<'a, M>> {
+        SmallMap::insert(self, key, value)
+    }
+
+    fn remove_entry(&mut self, key: &str) -> Option<(Cow<'a, str>, Value<'a, M>)> {
+        SmallMap::remove_entry(self, key)
+    }
+
+    fn take_entry(&mut self, key: &str) -> Option<(Cow<'a, str>, Value<'a, M>)> {
+        SmallMap::remove_entry(self, key)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        SmallMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        SmallMap::iter_mut(self)
+    }
+}
+
+pub const SMALL_MAP_THRESHOLD: usize = 12;
+
+#[derive(Clone)]
+enum Repr<K, V> {
+    Vec(Vec<(K, V)>),
+    Tree(BTreeMap<K, V>),
+}
+
+#[derive(Clone)]
+pub struct SmallMap<K, V> {
+    repr: Repr<K, V>,
+}
+
+impl<K, V> SmallMap<K, V> {
+    pub const fn new() -> Self {
+        Self {
+            repr: Repr::Vec(Vec::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Vec(v) => v.len(),
+            Repr::Tree(t) => t.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Vec(_))
+    }
+
+    pub fn clear(&mut self) {
+        self.repr = Repr::Vec(Vec::new());
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        match &self.repr {
+            Repr::Vec(v) => Iter(IterRepr::Vec(v.iter())),
+            Repr::Tree(t) => Iter(IterRepr::Tree(t.iter())),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        match &mut self.repr {
+            Repr::Vec(v) => IterMut(IterMutRepr::Vec(v.iter_mut())),
+            Repr::Tree(t) => IterMut(IterMutRepr::Tree(t.iter_mut())),
+        }
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> {
+        self.iter_mut().map(|(_, v)| v)
+    }
+
+    pub fn iter_sorted(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+}
+
+impl<K: Ord, V> SmallMap<K, V> {
+    fn position<Q>(entries: &[(K, V)], key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        entries.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match &self.repr {
+            Repr::Vec(v) => Self::position(v, key).ok().map(|i| (&v[i].0, &v[i].1)),
+            Repr::Tree(t) => t.get_key_value(key),
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match &mut self.repr {
+            Repr::Vec(v) => Self::position(v, key).ok().map(|i| &mut v[i].1),
+            Repr::Tree(t) => t.get_mut(key),
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match &mut self.repr {
+            Repr::Vec(v) => match Self::position(v, &key) {
+                Ok(i) => Some(std::mem::replace(&mut v[i].1, value)),
+                Err(i) if v.len() < SMALL_MAP_THRESHOLD => {
+                    v.insert(i, (key, value));
+                    None
+                }
+                Err(_) => {
+                    self.promote();
+                    self.insert(key, value)
+                }
+            },
+            Repr::Tree(t) => t.insert(key, value),
+        }
+    }
+
+    fn promote(&mut self) {
+        if let Repr::Vec(v) = &mut self.repr {
+            self.repr = Repr::Tree(std::mem::take(v).into_iter().collect());
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match &mut self.repr {
+            Repr::Vec(v) => Self::position(v, key).ok().map(|i| v.remove(i)),
+            Repr::Tree(t) => t.remove_entry(key),
+        }
+    }
+
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        match &mut self.repr {
+            Repr::Vec(v) => v.retain_mut(|(k, v)| f(k, v)),
+            Repr::Tree(t) => t.retain(f),
+        }
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry { map: self, key }
+    }
+}
+
+pub struct Entry<'m, K, V> {
+    map: &'m mut SmallMap<K, V>,
+    key: K,
+}
+
+impl<'m, K: Ord, V> Entry<'m, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn or_insert(self, default: V) -> &'m mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'m mut V {
+        let Entry { map, key } = self;
+        let promote = match &map.repr {
+            Repr::Vec(v) => v.len() >= SMALL_MAP_THRESHOLD && !map.contains_key(&key),
+            Repr::Tree(_) => false,
+        };
+        if promote {
+            map.promote();
+        }
+
+        match &mut map.repr {
+            Repr::Vec(v) => match SmallMap::position(v, &key) {
+                Ok(i) => &mut v[i].1,
+                Err(i) => {
+                    v.insert(i, (key, default()));
+                    &mut v[i].1
+                }
+            },
+            Repr::Tree(t) => t.entry(key).or_insert_with(default),
+        }
+    }
+
+    pub fn or_default(self) -> &'m mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<K, V> Default for SmallMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for SmallMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for SmallMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, Q, V> Index<&Q> for SmallMap<K, V>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SmallMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SmallMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SmallMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K: Ord, V> From<BTreeMap<K, V>> for SmallMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        if map.len() <= SMALL_MAP_THRESHOLD {
+            Self {
+                repr: Repr::Vec(map.into_iter().collect()),
+            }
+        } else {
+            Self {
+                repr: Repr::Tree(map),
+            }
+        }
+    }
+}
+
+enum IterRepr<'m, K, V> {
+    Vec(slice::Iter<'m, (K, V)>),
+    Tree(btree_map::Iter<'m, K, V>),
+}
+
+pub struct Iter<'m, K, V>(IterRepr<'m, K, V>);
+
+impl<'m, K, V> Iterator for Iter<'m, K, V> {
+    type Item = (&'m K, &'m V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterRepr::Vec(it) => it.next().map(|(k, v)| (k, v)),
+            IterRepr::Tree(it) => it.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            IterRepr::Vec(it) => it.size_hint(),
+            IterRepr::Tree(it) => it.size_hint(),
+        }
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterRepr::Vec(it) => it.next_back().map(|(k, v)| (k, v)),
+            IterRepr::Tree(it) => it.next_back(),
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+enum IterMutRepr<'m, K, V> {
+    Vec(slice::IterMut<'m, (K, V)>),
+    Tree(btree_map::IterMut<'m, K, V>),
+}
+
+pub struct IterMut<'m, K, V>(IterMutRepr<'m, K, V>);
+
+impl<'m, K, V> Iterator for IterMut<'m, K, V> {
+    type Item = (&'m K, &'m mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterMutRepr::Vec(it) => it.next().map(|(k, v)| (&*k, v)),
+            IterMutRepr::Tree(it) => it.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            IterMutRepr::Vec(it) => it.size_hint(),
+            IterMutRepr::Tree(it) => it.size_hint(),
+        }
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterMutRepr::Vec(it) => it.next_back().map(|(k, v)| (&*k, v)),
+            IterMutRepr::Tree(it) => it.next_back(),
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+enum IntoIterRepr<K, V> {
+    Vec(std::vec::IntoIter<(K, V)>),
+    Tree(btree_map::IntoIter<K, V>),
+}
+
+pub struct IntoIter<K, V>(IntoIterRepr<K, V>);
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IntoIterRepr::Vec(it) => it.next(),
+            IntoIterRepr::Tree(it) => it.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            IntoIterRepr::Vec(it) => it.size_hint(),
+            IntoIterRepr::Tree(it) => it.size_hint(),
+        }
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IntoIterRepr::Vec(it) => it.next_back(),
+            IntoIterRepr::Tree(it) => it.next_back(),
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> IntoIterator for SmallMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        match self.repr {
+            Repr::Vec(v) => IntoIter(IntoIterRepr::Vec(v.into_iter())),
+            Repr::Tree(t) => IntoIter(IntoIterRepr::Tree(t.into_iter())),
+        }
+    }
+}
+
+impl<'m, K, V> IntoIterator for &'m SmallMap<K, V> {
+    type Item = (&'m K, &'m V);
+    type IntoIter = Iter<'m, K, V>;
+
+    fn into_iter(self) -> Iter<'m, K, V> {
+        self.iter()
+    }
+}
+
+impl<'m, K, V> IntoIterator for &'m mut SmallMap<K, V> {
+    type Item = (&'m K, &'m mut V);
+    type IntoIter = IterMut<'m, K, V>;
+
+    fn into_iter(self) -> IterMut<'m, K, V> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    borrow::{Borrow, Cow},
+    collections::BTreeMap,
+    fmt::Debug,
+    hash::Hash,
+    ops::Index,
+};
+
+use indexmap::{IndexMap, map};
+
+use super::{CompoundOps, MapBackend, sealed};
+use crate::value::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ordered;
+
+impl sealed::Sealed for Ordered {}
+
+impl MapBackend for Ordered {
+    type Map<'a> = OrderedMap<Cow<'a, str>, Value<'a, Ordered>>;
+}
+
+impl<'a, M: MapBackend> CompoundOps<'a, M> for OrderedMap<Cow<'a, str>, Value<'a, M>> {
+    type Iter<'m>
+        = map::Iter<'m, Cow<'a, str>, Value<'a, M>>
+    where
+        Self: 'm,
+        'a: 'm;
+
+    type IterMut<'m>
+        = map::IterMut<'m, Cow<'a, str>, Value<'a, M>>
+    where
+        Self: 'm,
+        'a: 'm;
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn get(&self, key: &str) -> Option<&Value<'a, M>> {
+        self.inner.get(key)
+    }
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Value<'a, M>> {
+        self.inner.get_mut(key)
+    }
+
+    fn insert(&mut self, key: Cow<'a, str>, value: Value<'a, M>) -> Option<Value<'a, M>> {
+        self.inner.insert(key, value)
+    }
+
+    fn remove_entry(&mut self, key: &str) -> Option<(Cow<'a, str>, Value<'a, M>)> {
+        self.inner.shift_remove_entry(key)
+    }
+
+    fn take_entry(&mut self, key: &str) -> Option<(Cow<'a, str>, Value<'a, M>)> {
+        self.inner.swap_remove_entry(key)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.inner.iter()
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.inner.iter_mut()
+    }
+}
+
+#[derive(Clone)]
+pub struct OrderedMap<K, V> {
+    inner: IndexMap<K, V>,
+}
+
+impl<K, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            inner: IndexMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    pub fn iter(&self) -> map::Iter<'_, K, V> {
+        self.inner.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> map::IterMut<'_, K, V> {
+        self.inner.iter_mut()
+    }
+
+    pub fn keys(&self) -> map::Keys<'_, K, V> {
+        self.inner.keys()
+    }
+
+    pub fn values(&self) -> map::Values<'_, K, V> {
+        self.inner.values()
+    }
+
+    pub fn values_mut(&mut self) -> map::ValuesMut<'_, K, V> {
+        self.inner.values_mut()
+    }
+
+    pub fn iter_sorted(&self) -> std::vec::IntoIter<(&K, &V)>
+    where
+        K: Ord,
+    {
+        let mut entries: Vec<_> = self.inner.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.into_iter()
+    }
+
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.inner.sort_keys();
+    }
+}
+
+impl<K: Hash + Eq, V> OrderedMap<K, V> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.get(key)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.get_key_value(key)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.get_mut(key)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.contains_key(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.inner.insert(key, value)
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.shift_remove(key)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.shift_remove_entry(key)
+    }
+
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, f: F) {
+        self.inner.retain(f);
+    }
+
+    pub fn entry(&mut self, key: K) -> map::Entry<'_, K, V> {
+        self.inner.entry(key)
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for OrderedMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq> PartialEq for OrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<K, Q, V> Index<&Q> for OrderedMap<K, V>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            inner: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for OrderedMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.inner.extend(iter);
+    }
+}
+
+impl<K: Hash + Eq, V> From<BTreeMap<K, V>> for OrderedMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = map::IntoIter<K, V>;
+
+    fn into_iter(self) -> map::IntoIter<K, V> {
+        self.inner.into_iter()
+    }
+}
+
+impl<'m, K, V> IntoIterator for &'m OrderedMap<K, V> {
+    type Item = (&'m K, &'m V);
+    type IntoIter = map::Iter<'m, K, V>;
+
+    fn into_iter(self) -> map::Iter<'m, K, V> {
+        self.iter()
+    }
+}
+
+impl<'m, K, V> IntoIterator for &'m mut OrderedMap<K, V> {
+    type Item = (&'m K, &'m mut V);
+    type IntoIter = map::IterMut<'m, K, V>;
+
+    fn into_iter(self) -> map::IterMut<'m, K, V> {
+        self.iter_mut()
+    }
+}
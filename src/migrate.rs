@@ -0,0 +1,95 @@
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+use crate::{
+    error::Result,
+    path::{NbtPath, PathPattern},
+    value::Value,
+};
+
+pub type ConvertFn = Box<dyn for<'a> Fn(&mut Value<'a>) -> Result<()> + Send + Sync>;
+
+struct Converter {
+    versions: RangeInclusive<i32>,
+    pattern: PathPattern,
+    convert: ConvertFn,
+}
+
+#[derive(Default)]
+pub struct MigrationRegistry {
+    converters: Vec<Converter>,
+}
+
+impl MigrationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<R, F>(&mut self, versions: R, pattern: PathPattern, convert: F) -> &mut Self
+    where
+        R: RangeBounds<i32>,
+        F: for<'a> Fn(&mut Value<'a>) -> Result<()> + Send + Sync + 'static,
+    {
+        let start = match versions.start_bound() {
+            Bound::Included(&v) => v,
+            Bound::Excluded(&v) => v.saturating_add(1),
+            Bound::Unbounded => i32::MIN,
+        };
+        let end = match versions.end_bound() {
+            Bound::Included(&v) => v,
+            Bound::Excluded(&v) => v.saturating_sub(1),
+            Bound::Unbounded => i32::MAX,
+        };
+
+        let index = self
+            .converters
+            .partition_point(|c| *c.versions.start() <= start);
+        self.converters.insert(
+            index,
+            Converter {
+                versions: start..=end,
+                pattern,
+                convert: Box::new(convert),
+            },
+        );
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.converters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.converters.is_empty()
+    }
+
+    pub fn migrate(&self, value: &mut Value<'_>, from: i32, to: i32) -> Result<usize> {
+        let mut applied = 0;
+
+        for converter in &self.converters {
+            let (start, end) = (*converter.versions.start(), *converter.versions.end());
+            if start > end || end <= from || start > to {
+                continue;
+            }
+
+            let paths: Vec<NbtPath> = value
+                .query(&converter.pattern)
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect();
+            for path in paths {
+                if let Some(target) = value.get_path_mut(&path) {
+                    (converter.convert)(target)?;
+                    applied += 1;
+                }
+            }
+        }
+
+        if let Some(Value::Int(version)) = value.get_mut("DataVersion")
+            && *version < to
+        {
+            *version = to;
+        }
+
+        Ok(applied)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    error::{NBTError, Result},
+    value::Value,
+};
+
+pub const NIBBLE_ARRAY_LEN: usize = 2048;
+
+pub fn nibble_index(x: usize, y: usize, z: usize) -> Option<usize> {
+    (x < 16 && y < 16 && z < 16).then_some(y << 8 | z << 4 | x)
+}
+
+fn check_len(len: usize) -> Result<()> {
+    match len {
+        NIBBLE_ARRAY_LEN => Ok(()),
+        _ => Err(NBTError::custom_msg(format!(
+            "nibble array: expected {} bytes, got {}",
+            NIBBLE_ARRAY_LEN, len
+        ))),
+    }
+}
+
+fn get_nibble(bytes: &[u8], index: usize) -> Option<u8> {
+    let byte = bytes.get(index >> 1)?;
+    Some(match index & 1 {
+        0 => byte & 0x0f,
+        _ => byte >> 4,
+    })
+}
+
+fn set_nibble(bytes: &mut [u8], index: usize, value: u8) -> Result<()> {
+    let byte = match bytes.get_mut(index >> 1) {
+        Some(byte) if value <= 15 => byte,
+        _ => {
+            return Err(NBTError::custom_msg(format!(
+                "nibble array: cannot set index {} to {}",
+                index, value
+            )));
+        }
+    };
+    *byte = match index & 1 {
+        0 => (*byte & 0xf0) | value,
+        _ => (*byte & 0x0f) | (value << 4),
+    };
+    Ok(())
+}
+
+fn coordinate_index(x: usize, y: usize, z: usize) -> Result<usize> {
+    nibble_index(x, y, z).ok_or_else(|| {
+        NBTError::custom_msg(format!(
+            "nibble array: ({}, {}, {}) is outside the 16x16x16 section",
+            x, y, z
+        ))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NibbleView<'b>(&'b [u8]);
+
+impl<'b> NibbleView<'b> {
+    pub fn new(bytes: &'b [u8]) -> Result<Self> {
+        check_len(bytes.len())?;
+        Ok(Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &'b [u8] {
+        self.0
+    }
+
+    pub fn get(&self, index: usize) -> Option<u8> {
+        get_nibble(self.0, index)
+    }
+
+    pub fn get_at(&self, x: usize, y: usize, z: usize) -> Option<u8> {
+        self.get(nibble_index(x, y, z)?)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NibbleViewMut<'b>(&'b mut [u8]);
+
+impl<'b> NibbleViewMut<'b> {
+    pub fn new(bytes: &'b mut [u8]) -> Result<Self> {
+        check_len(bytes.len())?;
+        Ok(Self(bytes))
+    }
+
+    pub fn as_view(&self) -> NibbleView<'_> {
+        NibbleView(self.0)
+    }
+
+    pub fn get(&self, index: usize) -> Option<u8> {
+        get_nibble(self.0, index)
+    }
+
+    pub fn get_at(&self, x: usize, y: usize, z: usize) -> Option<u8> {
+        self.get(nibble_index(x, y, z)?)
+    }
+
+    pub fn set(&mut self, index: usize, value: u8) -> Result<()> {
+        set_nibble(self.0, index, value)
+    }
+
+    pub fn set_at(&mut self, x: usize, y: usize, z: usize, value: u8) -> Result<()> {
+        self.set(coordinate_index(x, y, z)?, value)
+    }
+
+    pub fn fill(&mut self, value: u8) {
+        self.0.fill((value & 0x0f) * 0x11);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NibbleArray(Vec<u8>);
+
+impl Default for NibbleArray {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NibbleArray {
+    pub const NIBBLES: usize = NIBBLE_ARRAY_LEN * 2;
+
+    pub fn new() -> Self {
+        Self(vec![0; NIBBLE_ARRAY_LEN])
+    }
+
+    pub fn filled(value: u8) -> Self {
+        let mut array = Self::new();
+        array.view_mut().fill(value);
+        array
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        check_len(bytes.len())?;
+        Ok(Self(bytes))
+    }
+
+    pub fn from_value(value: &Value<'_>) -> Result<Self> {
+        match value {
+            Value::ByteArray(bytes) => Self::from_bytes(bytes.clone()),
+            other => Err(NBTError::custom_msg(format!(
+                "nibble array: expected ByteArray, got {:?}",
+                other.tag()
+            ))),
+        }
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        Value::ByteArray(self.0.clone())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn view(&self) -> NibbleView<'_> {
+        NibbleView(&self.0)
+    }
+
+    pub fn view_mut(&mut self) -> NibbleViewMut<'_> {
+        NibbleViewMut(&mut self.0)
+    }
+
+    pub fn get(&self, index: usize) -> Option<u8> {
+        get_nibble(&self.0, index)
+    }
+
+    pub fn get_at(&self, x: usize, y: usize, z: usize) -> Option<u8> {
+        self.get(nibble_index(x, y, z)?)
+    }
+
+    pub fn set(&mut self, index: usize, value: u8) -> Result<()> {
+        set_nibble(&mut self.0, index, value)
+    }
+
+    pub fn set_at(&mut self, x: usize, y: usize, z: usize, value: u8) -> Result<()> {
+        self.set(coordinate_index(x, y, z)?, value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.iter().flat_map(|&byte| [byte & 0x0f, byte >> 4])
+    }
+}
+
+impl TryFrom<Vec<u8>> for NibbleArray {
+    type Error = NBTError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<NibbleArray> for Vec<u8> {
+    fn from(array: NibbleArray) -> Self {
+        array.0
+    }
+}
+
+impl From<NibbleArray> for Value<'_> {
+    fn from(array: NibbleArray) -> Self {
+        Value::ByteArray(array.0)
+    }
+}
+
+impl From<NibbleView<'_>> for NibbleArray {
+    fn from(view: NibbleView<'_>) -> Self {
+        Self(view.0.to_vec())
+    }
+}
@@ -0,0 +1,556 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt::Display, str::FromStr};
+
+use crate::{
+    error::{NBTError, Result},
+    value::Value,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NbtPath {
+    pub segments: Vec<PathSegment>,
+}
+
+impl NbtPath {
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn key<S: Into<String>>(mut self, key: S) -> Self {
+        self.segments.push(PathSegment::Key(key.into()));
+        self
+    }
+
+    pub fn index(mut self, index: usize) -> Self {
+        self.segments.push(PathSegment::Index(index));
+        self
+    }
+
+    pub fn parent(&self) -> Option<NbtPath> {
+        let (_, parent) = self.segments.split_last()?;
+        Some(NbtPath {
+            segments: parent.to_vec(),
+        })
+    }
+
+    pub fn last(&self) -> Option<&PathSegment> {
+        self.segments.last()
+    }
+}
+
+fn needs_quotes(key: &str) -> bool {
+    key.is_empty()
+        || key == "*"
+        || key == "**"
+        || key
+            .chars()
+            .any(|c| matches!(c, '.' | '[' | ']' | '"' | '\\') || c.is_whitespace())
+}
+
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) => {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+
+                    if needs_quotes(key) {
+                        f.write_str("\"")?;
+                        for c in key.chars() {
+                            if matches!(c, '"' | '\\') {
+                                f.write_str("\\")?;
+                            }
+                            write!(f, "{}", c)?;
+                        }
+                        f.write_str("\"")?;
+                    } else {
+                        f.write_str(key)?;
+                    }
+                }
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for NbtPath {
+    type Err = NBTError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let pattern: PathPattern = s.parse()?;
+
+        let segments = pattern
+            .segments
+            .into_iter()
+            .map(|segment| match segment {
+                PatternSegment::Key(key) => Ok(PathSegment::Key(key)),
+                PatternSegment::Index(index) => Ok(PathSegment::Index(index)),
+                _ => Err(NBTError::invalid_path(format!(
+                    "wildcards are not allowed in a concrete path: {:?}",
+                    s
+                ))),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(NbtPath { segments })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PatternSegment {
+    Key(String),
+    Index(usize),
+    AnyKey,
+    AnyIndex,
+    Deep,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PathPattern {
+    pub segments: Vec<PatternSegment>,
+}
+
+impl From<NbtPath> for PathPattern {
+    fn from(path: NbtPath) -> Self {
+        let segments = path
+            .segments
+            .into_iter()
+            .map(|segment| match segment {
+                PathSegment::Key(key) => PatternSegment::Key(key),
+                PathSegment::Index(index) => PatternSegment::Index(index),
+            })
+            .collect();
+        PathPattern { segments }
+    }
+}
+
+impl FromStr for PathPattern {
+    type Err = NBTError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut chars = s.chars().peekable();
+        let invalid = |msg: &str| NBTError::invalid_path(format!("{}: {:?}", msg, s));
+
+        while let Some(&c) = chars.peek() {
+            match c {
+                '[' => {
+                    chars.next();
+                    let mut digits = String::new();
+                    for c in chars.by_ref() {
+                        if c == ']' {
+                            break;
+                        }
+                        digits.push(c);
+                    }
+                    if digits == "*" {
+                        segments.push(PatternSegment::AnyIndex);
+                        continue;
+                    }
+                    let index = digits
+                        .parse::<usize>()
+                        .map_err(|_| invalid("invalid list index"))?;
+                    segments.push(PatternSegment::Index(index));
+                }
+                '.' if !segments.is_empty() => {
+                    chars.next();
+                    if matches!(chars.peek(), None | Some('.') | Some('[')) {
+                        return Err(invalid("expected key after '.'"));
+                    }
+                }
+                '"' => {
+                    chars.next();
+                    let mut key = String::new();
+                    let mut closed = false;
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '\\' => {
+                                key.push(chars.next().ok_or_else(|| invalid("dangling escape"))?)
+                            }
+                            '"' => {
+                                closed = true;
+                                break;
+                            }
+                            c => key.push(c),
+                        }
+                    }
+                    if !closed {
+                        return Err(invalid("unterminated quoted key"));
+                    }
+                    segments.push(PatternSegment::Key(key));
+                }
+                ']' | '.' => return Err(invalid("unexpected character")),
+                _ => {
+                    let mut key = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if matches!(c, '.' | '[' | ']' | '"') {
+                            break;
+                        }
+                        key.push(c);
+                        chars.next();
+                    }
+                    segments.push(match key.as_str() {
+                        "*" => PatternSegment::AnyKey,
+                        "**" => PatternSegment::Deep,
+                        _ => PatternSegment::Key(key),
+                    });
+                }
+            }
+        }
+
+        Ok(PathPattern { segments })
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn get_path(&self, path: &NbtPath) -> Option<&Value<'a>> {
+        path.segments
+            .iter()
+            .try_fold(self, |value, segment| match (segment, value) {
+                (PathSegment::Key(key), Value::Compound(map)) => map.get(key.as_str()),
+                (PathSegment::Index(index), Value::List(list)) => list.get(*index),
+                _ => None,
+            })
+    }
+
+    pub fn get_path_mut(&mut self, path: &NbtPath) -> Option<&mut Value<'a>> {
+        path.segments
+            .iter()
+            .try_fold(self, |value, segment| match (segment, value) {
+                (PathSegment::Key(key), Value::Compound(map)) => map.get_mut(key.as_str()),
+                (PathSegment::Index(index), Value::List(list)) => list.get_mut(*index),
+                _ => None,
+            })
+    }
+
+    pub fn set_path<V: Into<Value<'a>>>(
+        &mut self,
+        path: &NbtPath,
+        value: V,
+    ) -> Result<Option<Value<'a>>> {
+        let value = value.into();
+        let Some((last, parents)) = path.segments.split_last() else {
+            return Ok(Some(std::mem::replace(self, value)));
+        };
+        self.check_set_path(path, &value)?;
+
+        let mut current = self;
+        for (depth, segment) in parents.iter().enumerate() {
+            current = match (segment, current) {
+                (PathSegment::Key(key), Value::Compound(map)) => map
+                    .entry(Cow::Owned(key.clone()))
+                    .or_insert_with(Value::compound),
+                (PathSegment::Index(index), Value::List(list)) => {
+                    let len = list.len();
+                    list.get_mut(*index)
+                        .ok_or_else(|| out_of_bounds(path, depth + 1, len))?
+                }
+                (segment, other) => return Err(wrong_container(path, depth, segment, other)),
+            };
+        }
+
+        match (last, current) {
+            (PathSegment::Key(key), Value::Compound(map)) => {
+                Ok(map.insert(Cow::Owned(key.clone()), value))
+            }
+            (PathSegment::Index(index), Value::List(list)) => {
+                let len = list.len();
+                let slot = list
+                    .get_mut(*index)
+                    .ok_or_else(|| out_of_bounds(path, path.segments.len(), len))?;
+                if slot.tag() != value.tag() && len > 1 {
+                    return Err(NBTError::invalid_path(format!(
+                        "{}: list holds {:?}, cannot store {:?}",
+                        path,
+                        slot.tag(),
+                        value.tag()
+                    )));
+                }
+                Ok(Some(std::mem::replace(slot, value)))
+            }
+            (segment, other) => Err(wrong_container(path, parents.len(), segment, other)),
+        }
+    }
+
+    fn check_set_path(&self, path: &NbtPath, value: &Value<'_>) -> Result<()> {
+        let created = Value::compound();
+        let mut current = Some(self);
+        for (depth, segment) in path.segments.iter().enumerate() {
+            let last = depth + 1 == path.segments.len();
+            current = match (segment, current.unwrap_or(&created)) {
+                (PathSegment::Key(_), _) if current.is_none() => None,
+                (PathSegment::Key(key), Value::Compound(map)) => map.get(key.as_str()),
+                (PathSegment::Index(index), Value::List(list)) => {
+                    let slot = list
+                        .get(*index)
+                        .ok_or_else(|| out_of_bounds(path, depth + 1, list.len()))?;
+                    if last && slot.tag() != value.tag() && list.len() > 1 {
+                        return Err(NBTError::invalid_path(format!(
+                            "{}: list holds {:?}, cannot store {:?}",
+                            path,
+                            slot.tag(),
+                            value.tag()
+                        )));
+                    }
+                    Some(slot)
+                }
+                (segment, other) => return Err(wrong_container(path, depth, segment, other)),
+            };
+        }
+        Ok(())
+    }
+
+    pub fn query<'v>(&'v self, pattern: &PathPattern) -> Vec<(NbtPath, &'v Value<'a>)> {
+        let mut matches = Vec::new();
+        query_into(self, NbtPath::root(), &pattern.segments, &mut matches);
+        matches
+    }
+
+    pub fn search_strings(&self, pattern: &str) -> Vec<NbtPath> {
+        let mut found = Vec::new();
+        walk(self, NbtPath::root(), &mut |path, value| {
+            if let Value::String(s) = value
+                && s.contains(pattern)
+            {
+                found.push(path.clone());
+            }
+        });
+        found
+    }
+
+    pub fn search_numbers<F: FnMut(f64) -> bool>(&self, mut predicate: F) -> Vec<NbtPath> {
+        let mut found = Vec::new();
+        walk(self, NbtPath::root(), &mut |path, value| {
+            let hit = match value {
+                Value::Byte(v) => predicate(*v as f64),
+                Value::Short(v) => predicate(*v as f64),
+                Value::Int(v) => predicate(*v as f64),
+                Value::Long(v) => predicate(*v as f64),
+                Value::Float(v) => predicate(*v as f64),
+                Value::Double(v) => predicate(*v),
+                Value::ByteArray(v) => v.iter().any(|&v| predicate(v as i8 as f64)),
+                Value::IntArray(v) => v.iter().any(|&v| predicate(v as f64)),
+                Value::LongArray(v) => v.iter().any(|&v| predicate(v as f64)),
+                _ => false,
+            };
+            if hit {
+                found.push(path.clone());
+            }
+        });
+        found
+    }
+
+    pub fn flatten(&self) -> BTreeMap<NbtPath, Value<'a>> {
+        let mut leaves = BTreeMap::new();
+        flatten_into(self, NbtPath::root(), &mut leaves);
+        leaves
+    }
+
+    pub fn unflatten(leaves: BTreeMap<NbtPath, Value<'a>>) -> Result<Value<'a>> {
+        let mut root: Option<Value<'a>> = None;
+
+        for (path, leaf) in leaves {
+            insert_leaf(&mut root, &path.segments, leaf, &path)?;
+        }
+
+        Ok(root.unwrap_or_else(Value::compound))
+    }
+}
+
+fn query_into<'v, 'a>(
+    value: &'v Value<'a>,
+    path: NbtPath,
+    segments: &[PatternSegment],
+    matches: &mut Vec<(NbtPath, &'v Value<'a>)>,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        matches.push((path, value));
+        return;
+    };
+
+    match (segment, value) {
+        (PatternSegment::Key(key), Value::Compound(map)) => {
+            if let Some(child) = map.get(key.as_str()) {
+                query_into(child, path.key(key.as_str()), rest, matches);
+            }
+        }
+        (PatternSegment::Index(index), Value::List(list)) => {
+            if let Some(child) = list.get(*index) {
+                query_into(child, path.index(*index), rest, matches);
+            }
+        }
+        (PatternSegment::AnyKey, Value::Compound(map)) => {
+            for (key, child) in map {
+                query_into(child, path.clone().key(key.as_ref()), rest, matches);
+            }
+        }
+        (PatternSegment::AnyIndex, Value::List(list)) => {
+            for (index, child) in list.iter().enumerate() {
+                query_into(child, path.clone().index(index), rest, matches);
+            }
+        }
+        (PatternSegment::Deep, _) => {
+            query_into(value, path.clone(), rest, matches);
+            match value {
+                Value::Compound(map) => {
+                    for (key, child) in map {
+                        query_into(child, path.clone().key(key.as_ref()), segments, matches);
+                    }
+                }
+                Value::List(list) => {
+                    for (index, child) in list.iter().enumerate() {
+                        query_into(child, path.clone().index(index), segments, matches);
+                    }
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk<'a, F: FnMut(&NbtPath, &Value<'a>)>(value: &Value<'a>, path: NbtPath, f: &mut F) {
+    f(&path, value);
+
+    match value {
+        Value::Compound(map) => {
+            for (key, child) in map {
+                walk(child, path.clone().key(key.as_ref()), f);
+            }
+        }
+        Value::List(list) => {
+            for (index, child) in list.iter().enumerate() {
+                walk(child, path.clone().index(index), f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn flatten_into<'a>(value: &Value<'a>, path: NbtPath, leaves: &mut BTreeMap<NbtPath, Value<'a>>) {
+    match value {
+        Value::Compound(map) if !map.is_empty() => {
+            for (key, child) in map {
+                flatten_into(child, path.clone().key(key.as_ref()), leaves);
+            }
+        }
+        Value::List(list) if !list.is_empty() => {
+            for (index, child) in list.iter().enumerate() {
+                flatten_into(child, path.clone().index(index), leaves);
+            }
+        }
+        _ => {
+            leaves.insert(path, value.clone());
+        }
+    }
+}
+
+fn insert_leaf<'a>(
+    slot: &mut Option<Value<'a>>,
+    segments: &[PathSegment],
+    leaf: Value<'a>,
+    path: &NbtPath,
+) -> Result<()> {
+    let conflict = || NBTError::invalid_path(format!("conflicting entry at {}", path));
+
+    let Some((segment, rest)) = segments.split_first() else {
+        return match slot {
+            None => {
+                *slot = Some(leaf);
+                Ok(())
+            }
+            Some(_) => Err(conflict()),
+        };
+    };
+
+    match segment {
+        PathSegment::Key(key) => {
+            let Value::Compound(map) = slot.get_or_insert_with(Value::compound) else {
+                return Err(conflict());
+            };
+
+            let mut child = map.remove(key.as_str());
+            insert_leaf(&mut child, rest, leaf, path)?;
+            if let Some(child) = child {
+                map.insert(key.clone().into(), child);
+            }
+        }
+        PathSegment::Index(index) => {
+            let Some(list) = slot.get_or_insert_with(|| Value::list(0)).make_list_mut() else {
+                return Err(conflict());
+            };
+
+            if *index > list.len() {
+                return Err(NBTError::invalid_path(format!(
+                    "missing list element before {}",
+                    path
+                )));
+            }
+
+            let mut child = if *index < list.len() {
+                Some(std::mem::replace(&mut list[*index], Value::End))
+            } else {
+                None
+            };
+            insert_leaf(&mut child, rest, leaf, path)?;
+            if let Some(child) = child {
+                if *index < list.len() {
+                    list[*index] = child;
+                } else {
+                    list.push(child);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn prefix(path: &NbtPath, len: usize) -> String {
+    match len {
+        0 => "<root>".to_owned(),
+        _ => NbtPath {
+            segments: path.segments[..len].to_vec(),
+        }
+        .to_string(),
+    }
+}
+
+fn out_of_bounds(path: &NbtPath, len: usize, list_len: usize) -> NBTError {
+    NBTError::invalid_path(format!(
+        "{}: index out of bounds for list of length {}",
+        prefix(path, len),
+        list_len
+    ))
+}
+
+fn wrong_container(
+    path: &NbtPath,
+    len: usize,
+    segment: &PathSegment,
+    found: &Value<'_>,
+) -> NBTError {
+    let expected = match segment {
+        PathSegment::Key(_) => "Compound",
+        PathSegment::Index(_) => "List",
+    };
+    NBTError::invalid_path(format!(
+        "{}: expected {}, found {:?}",
+        prefix(path, len),
+        expected,
+        found.tag()
+    ))
+}
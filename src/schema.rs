@@ -0,0 +1,360 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    error::{NBTError, Result},
+    path::NbtPath,
+    tag::Tag,
+    value::Value,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    Unknown,
+    Byte,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    ByteArray,
+    String,
+    IntArray,
+    LongArray,
+    List(Box<Schema>),
+    Compound(BTreeMap<String, Field>),
+    Mixed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub schema: Schema,
+    pub optional: bool,
+}
+
+impl Schema {
+    pub fn of(value: &Value<'_>) -> Schema {
+        match value {
+            Value::End => Schema::Unknown,
+            Value::Byte(_) => Schema::Byte,
+            Value::Short(_) => Schema::Short,
+            Value::Int(_) => Schema::Int,
+            Value::Long(_) => Schema::Long,
+            Value::Float(_) => Schema::Float,
+            Value::Double(_) => Schema::Double,
+            Value::ByteArray(_) => Schema::ByteArray,
+            Value::String(_) => Schema::String,
+            Value::IntArray(_) => Schema::IntArray,
+            Value::LongArray(_) => Schema::LongArray,
+            Value::Unknown(..) => Schema::Mixed,
+            Value::EmptyList(tag) => Schema::List(Box::new(Schema::of_tag(*tag))),
+            Value::List(list) => Schema::List(Box::new(
+                list.iter()
+                    .map(Schema::of)
+                    .fold(Schema::Unknown, Schema::merge),
+            )),
+            Value::Compound(map) => Schema::Compound(
+                map.iter()
+                    .map(|(k, v)| {
+                        let field = Field {
+                            schema: Schema::of(v),
+                            optional: false,
+                        };
+                        (k.to_string(), field)
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn of_tag(tag: Tag) -> Schema {
+        match tag {
+            Tag::End => Schema::Unknown,
+            Tag::Byte => Schema::Byte,
+            Tag::Short => Schema::Short,
+            Tag::Int => Schema::Int,
+            Tag::Long => Schema::Long,
+            Tag::Float => Schema::Float,
+            Tag::Double => Schema::Double,
+            Tag::ByteArray => Schema::ByteArray,
+            Tag::String => Schema::String,
+            Tag::List => Schema::List(Box::new(Schema::Unknown)),
+            Tag::Compound => Schema::Compound(BTreeMap::new()),
+            Tag::IntArray => Schema::IntArray,
+            Tag::LongArray => Schema::LongArray,
+        }
+    }
+
+    pub fn infer(samples: &[Value<'_>]) -> Schema {
+        samples
+            .iter()
+            .map(Schema::of)
+            .fold(Schema::Unknown, Schema::merge)
+    }
+
+    fn rank(&self) -> Option<u8> {
+        match self {
+            Schema::Byte => Some(0),
+            Schema::Short => Some(1),
+            Schema::Int => Some(2),
+            Schema::Long => Some(3),
+            _ => None,
+        }
+    }
+
+    pub fn merge(self, other: Schema) -> Schema {
+        match (self, other) {
+            (Schema::Unknown, other) | (other, Schema::Unknown) => other,
+            (a, b) if a == b => a,
+            (a, b) if a.rank().is_some() && b.rank().is_some() => {
+                if a.rank() > b.rank() {
+                    a
+                } else {
+                    b
+                }
+            }
+            (Schema::Float | Schema::Double, Schema::Float | Schema::Double) => Schema::Double,
+            (Schema::List(a), Schema::List(b)) => Schema::List(Box::new(a.merge(*b))),
+            (Schema::Compound(mut a), Schema::Compound(b)) => {
+                for (_, field) in a.iter_mut().filter(|(k, _)| !b.contains_key(*k)) {
+                    field.optional = true;
+                }
+                for (key, field) in b {
+                    let merged = match a.remove(&key) {
+                        Some(existing) => Field {
+                            schema: existing.schema.merge(field.schema),
+                            optional: existing.optional || field.optional,
+                        },
+                        None => Field {
+                            schema: field.schema,
+                            optional: true,
+                        },
+                    };
+                    a.insert(key, merged);
+                }
+                Schema::Compound(a)
+            }
+            _ => Schema::Mixed,
+        }
+    }
+
+    pub fn accepts(&self, value: &Value<'_>) -> bool {
+        let mut errors = Vec::new();
+        self.check(value, NbtPath::root(), &mut errors);
+        errors.is_empty()
+    }
+
+    pub fn validate(&self, value: &Value<'_>) -> Result<()> {
+        let mut errors = Vec::new();
+        self.check(value, NbtPath::root(), &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(NBTError::invalid_fields(errors))
+        }
+    }
+
+    fn check(&self, value: &Value<'_>, path: NbtPath, errors: &mut Vec<String>) {
+        let at = |path: &NbtPath| {
+            if path.is_root() {
+                "<root>".to_owned()
+            } else {
+                path.to_string()
+            }
+        };
+
+        match (self, value) {
+            (Schema::Unknown | Schema::Mixed, _) => {}
+            (Schema::List(element), Value::List(list)) => {
+                for (i, item) in list.iter().enumerate() {
+                    element.check(item, path.clone().index(i), errors);
+                }
+            }
+            (Schema::Compound(fields), Value::Compound(map)) => {
+                for (key, field) in fields {
+                    match map.get(key.as_str()) {
+                        Some(item) => field.schema.check(item, path.clone().key(key), errors),
+                        None if !field.optional => {
+                            errors.push(format!("{}: missing", at(&path.clone().key(key))));
+                        }
+                        None => {}
+                    }
+                }
+                for key in map.keys().filter(|k| !fields.contains_key(k.as_ref())) {
+                    errors.push(format!(
+                        "{}: unexpected key",
+                        at(&path.clone().key(key.as_ref()))
+                    ));
+                }
+            }
+            (Schema::Double, Value::Float(_)) => {}
+            _ => {
+                let actual = Schema::of(value);
+                let widens = matches!((self.rank(), actual.rank()), (Some(a), Some(b)) if b <= a);
+                if actual != *self && !widens {
+                    errors.push(format!(
+                        "{}: expected {}, found {:?}",
+                        at(&path),
+                        self.kind_name(),
+                        value.tag()
+                    ));
+                }
+            }
+        }
+    }
+
+    fn kind_name(&self) -> &'static str {
+        match self {
+            Schema::Unknown => "Unknown",
+            Schema::Byte => "Byte",
+            Schema::Short => "Short",
+            Schema::Int => "Int",
+            Schema::Long => "Long",
+            Schema::Float => "Float",
+            Schema::Double => "Double",
+            Schema::ByteArray => "ByteArray",
+            Schema::String => "String",
+            Schema::IntArray => "IntArray",
+            Schema::LongArray => "LongArray",
+            Schema::List(_) => "List",
+            Schema::Compound(_) => "Compound",
+            Schema::Mixed => "Mixed",
+        }
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl Schema {
+    const DIALECT: &'static str = "https://json-schema.org/draft/2020-12/schema";
+
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        let mut document = self.json_node();
+        if let serde_json::Value::Object(map) = &mut document {
+            map.insert("$schema".to_owned(), Schema::DIALECT.into());
+        }
+        document
+    }
+
+    fn json_node(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        let integer =
+            |min: i64, max: i64| json!({ "type": "integer", "minimum": min, "maximum": max });
+        let array = |tag: &str, min: i64, max: i64| json!({ "type": "array", "items": integer(min, max), "x-nbt-tag": tag });
+
+        match self {
+            Schema::Unknown | Schema::Mixed => json!({}),
+            Schema::Byte => integer(i8::MIN.into(), i8::MAX.into()),
+            Schema::Short => integer(i16::MIN.into(), i16::MAX.into()),
+            Schema::Int => integer(i32::MIN.into(), i32::MAX.into()),
+            Schema::Long => integer(i64::MIN, i64::MAX),
+            Schema::Float => json!({ "type": "number", "format": "float" }),
+            Schema::Double => json!({ "type": "number", "format": "double" }),
+            Schema::String => json!({ "type": "string" }),
+            Schema::ByteArray => array("ByteArray", i8::MIN.into(), i8::MAX.into()),
+            Schema::IntArray => array("IntArray", i32::MIN.into(), i32::MAX.into()),
+            Schema::LongArray => array("LongArray", i64::MIN, i64::MAX),
+            Schema::List(element) => json!({ "type": "array", "items": element.json_node() }),
+            Schema::Compound(fields) => {
+                let properties: serde_json::Map<_, _> = fields
+                    .iter()
+                    .map(|(key, field)| (key.clone(), field.schema.json_node()))
+                    .collect();
+                let required: Vec<_> = fields
+                    .iter()
+                    .filter(|(_, field)| !field.optional)
+                    .map(|(key, _)| key.as_str())
+                    .collect();
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                    "additionalProperties": false,
+                })
+            }
+        }
+    }
+
+    pub fn from_json_schema(document: &serde_json::Value) -> Result<Schema> {
+        use serde_json::Value as Json;
+
+        let node = match document {
+            Json::Bool(true) => return Ok(Schema::Unknown),
+            Json::Object(node) => node,
+            _ => {
+                return Err(NBTError::invalid_syntax(format!(
+                    "unsupported JSON Schema node: {}",
+                    document
+                )));
+            }
+        };
+
+        if ["anyOf", "oneOf"].iter().any(|k| node.contains_key(*k)) {
+            return Ok(Schema::Mixed);
+        }
+
+        let bounds = |node: &serde_json::Map<String, Json>| {
+            let min = node
+                .get("minimum")
+                .and_then(Json::as_i64)
+                .unwrap_or(i64::MIN);
+            let max = node
+                .get("maximum")
+                .and_then(Json::as_i64)
+                .unwrap_or(i64::MAX);
+            if min >= i8::MIN.into() && max <= i8::MAX.into() {
+                Schema::Byte
+            } else if min >= i16::MIN.into() && max <= i16::MAX.into() {
+                Schema::Short
+            } else if min >= i32::MIN.into() && max <= i32::MAX.into() {
+                Schema::Int
+            } else {
+                Schema::Long
+            }
+        };
+
+        match node.get("type").and_then(Json::as_str) {
+            None => Ok(Schema::Unknown),
+            Some("integer") => Ok(bounds(node)),
+            Some("number") => Ok(match node.get("format").and_then(Json::as_str) {
+                Some("float") => Schema::Float,
+                _ => Schema::Double,
+            }),
+            Some("string") => Ok(Schema::String),
+            Some("array") => match node.get("x-nbt-tag").and_then(Json::as_str) {
+                Some("ByteArray") => Ok(Schema::ByteArray),
+                Some("IntArray") => Ok(Schema::IntArray),
+                Some("LongArray") => Ok(Schema::LongArray),
+                _ => {
+                    let element = match node.get("items") {
+                        Some(items) => Schema::from_json_schema(items)?,
+                        None => Schema::Unknown,
+                    };
+                    Ok(Schema::List(Box::new(element)))
+                }
+            },
+            Some("object") => {
+                let required: Vec<&str> = node
+                    .get("required")
+                    .and_then(Json::as_array)
+                    .map(|keys| keys.iter().filter_map(Json::as_str).collect())
+                    .unwrap_or_default();
+                let mut fields = BTreeMap::new();
+                if let Some(properties) = node.get("properties").and_then(Json::as_object) {
+                    for (key, property) in properties {
+                        let field = Field {
+                            schema: Schema::from_json_schema(property)?,
+                            optional: !required.contains(&key.as_str()),
+                        };
+                        fields.insert(key.clone(), field);
+                    }
+                }
+                Ok(Schema::Compound(fields))
+            }
+            Some(other) => Err(NBTError::invalid_syntax(format!(
+                "unsupported JSON Schema type: {}",
+                other
+            ))),
+        }
+    }
+}
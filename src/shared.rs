@@ -0,0 +1,198 @@
+use std::{borrow::Cow, collections::BTreeMap, sync::Arc};
+
+use crate::{
+    error::{NBTError, Result},
+    tag::Tag,
+    value::Value,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArcValue {
+    End,
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Arc<Vec<u8>>),
+    String(Arc<str>),
+    List(Arc<Vec<ArcValue>>),
+    EmptyList(Tag),
+    Compound(Arc<BTreeMap<Arc<str>, ArcValue>>),
+    IntArray(Arc<Vec<i32>>),
+    LongArray(Arc<Vec<i64>>),
+    Unknown(u8, Arc<Vec<u8>>),
+}
+
+impl ArcValue {
+    pub fn tag(&self) -> Tag {
+        match self {
+            ArcValue::End => Tag::End,
+            ArcValue::Byte(_) => Tag::Byte,
+            ArcValue::Short(_) => Tag::Short,
+            ArcValue::Int(_) => Tag::Int,
+            ArcValue::Long(_) => Tag::Long,
+            ArcValue::Float(_) => Tag::Float,
+            ArcValue::Double(_) => Tag::Double,
+            ArcValue::ByteArray(_) => Tag::ByteArray,
+            ArcValue::String(_) => Tag::String,
+            ArcValue::List(_) | ArcValue::EmptyList(_) => Tag::List,
+            ArcValue::Compound(_) => Tag::Compound,
+            ArcValue::IntArray(_) => Tag::IntArray,
+            ArcValue::LongArray(_) => Tag::LongArray,
+            ArcValue::Unknown(..) => Tag::End,
+        }
+    }
+
+    pub fn compound() -> Self {
+        ArcValue::Compound(Arc::default())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ArcValue> {
+        match self {
+            ArcValue::Compound(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut ArcValue> {
+        match self {
+            ArcValue::Compound(map) => Arc::make_mut(map).get_mut(key),
+            _ => None,
+        }
+    }
+
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Result<Option<ArcValue>>
+    where
+        K: Into<Arc<str>>,
+        V: Into<ArcValue>,
+    {
+        match self {
+            ArcValue::Compound(map) => Ok(Arc::make_mut(map).insert(key.into(), value.into())),
+            _ => Err(NBTError::custom_msg("Not a compound")),
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[ArcValue]> {
+        match self {
+            ArcValue::List(list) => Some(list),
+            ArcValue::EmptyList(_) => Some(&[]),
+            _ => None,
+        }
+    }
+
+    pub fn as_list_mut(&mut self) -> Option<&mut Vec<ArcValue>> {
+        if let ArcValue::EmptyList(_) = self {
+            *self = ArcValue::List(Arc::default());
+        }
+        match self {
+            ArcValue::List(list) => Some(Arc::make_mut(list)),
+            _ => None,
+        }
+    }
+
+    pub fn ptr_eq(&self, other: &ArcValue) -> bool {
+        match (self, other) {
+            (ArcValue::ByteArray(a), ArcValue::ByteArray(b)) => Arc::ptr_eq(a, b),
+            (ArcValue::String(a), ArcValue::String(b)) => Arc::ptr_eq(a, b),
+            (ArcValue::List(a), ArcValue::List(b)) => Arc::ptr_eq(a, b),
+            (ArcValue::Compound(a), ArcValue::Compound(b)) => Arc::ptr_eq(a, b),
+            (ArcValue::IntArray(a), ArcValue::IntArray(b)) => Arc::ptr_eq(a, b),
+            (ArcValue::LongArray(a), ArcValue::LongArray(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    pub fn to_value(&self) -> Value<'static> {
+        match self {
+            ArcValue::End => Value::End,
+            ArcValue::Byte(v) => Value::Byte(*v),
+            ArcValue::Short(v) => Value::Short(*v),
+            ArcValue::Int(v) => Value::Int(*v),
+            ArcValue::Long(v) => Value::Long(*v),
+            ArcValue::Float(v) => Value::Float(*v),
+            ArcValue::Double(v) => Value::Double(*v),
+            ArcValue::ByteArray(v) => Value::ByteArray(v.to_vec()),
+            ArcValue::String(v) => Value::String(Cow::Owned(v.to_string())),
+            ArcValue::List(v) => Value::List(v.iter().map(ArcValue::to_value).collect()),
+            ArcValue::EmptyList(tag) => Value::EmptyList(*tag),
+            ArcValue::Compound(v) => Value::Compound(
+                v.iter()
+                    .map(|(k, v)| (Cow::Owned(k.to_string()), v.to_value()))
+                    .collect(),
+            ),
+            ArcValue::IntArray(v) => Value::IntArray(v.to_vec()),
+            ArcValue::LongArray(v) => Value::LongArray(v.to_vec()),
+            ArcValue::Unknown(id, v) => Value::Unknown(*id, v.to_vec()),
+        }
+    }
+}
+
+impl<'a> From<Value<'a>> for ArcValue {
+    fn from(value: Value<'a>) -> Self {
+        match value {
+            Value::End => ArcValue::End,
+            Value::Byte(v) => ArcValue::Byte(v),
+            Value::Short(v) => ArcValue::Short(v),
+            Value::Int(v) => ArcValue::Int(v),
+            Value::Long(v) => ArcValue::Long(v),
+            Value::Float(v) => ArcValue::Float(v),
+            Value::Double(v) => ArcValue::Double(v),
+            Value::ByteArray(v) => ArcValue::ByteArray(Arc::new(v)),
+            Value::String(v) => ArcValue::String(Arc::from(v.as_ref())),
+            Value::List(v) => ArcValue::List(Arc::new(v.into_iter().map(Into::into).collect())),
+            Value::EmptyList(tag) => ArcValue::EmptyList(tag),
+            Value::Compound(v) => ArcValue::Compound(Arc::new(
+                v.into_iter()
+                    .map(|(k, v)| (Arc::from(k.as_ref()), v.into()))
+                    .collect(),
+            )),
+            Value::IntArray(v) => ArcValue::IntArray(Arc::new(v)),
+            Value::LongArray(v) => ArcValue::LongArray(Arc::new(v)),
+            Value::Unknown(id, v) => ArcValue::Unknown(id, Arc::new(v)),
+        }
+    }
+}
+
+impl From<&ArcValue> for Value<'static> {
+    fn from(value: &ArcValue) -> Self {
+        value.to_value()
+    }
+}
+
+impl From<bool> for ArcValue {
+    fn from(value: bool) -> Self {
+        ArcValue::Byte(value as i8)
+    }
+}
+
+impl From<i16> for ArcValue {
+    fn from(value: i16) -> Self {
+        ArcValue::Short(value)
+    }
+}
+
+impl From<i32> for ArcValue {
+    fn from(value: i32) -> Self {
+        ArcValue::Int(value)
+    }
+}
+
+impl From<i64> for ArcValue {
+    fn from(value: i64) -> Self {
+        ArcValue::Long(value)
+    }
+}
+
+impl From<&str> for ArcValue {
+    fn from(value: &str) -> Self {
+        ArcValue::String(Arc::from(value))
+    }
+}
+
+impl From<String> for ArcValue {
+    fn from(value: String) -> Self {
+        ArcValue::String(Arc::from(value))
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{snbt, value::Value};
+
+pub const UPDATE_ENV: &str = "BNBT_UPDATE_SNAPSHOTS";
+
+pub fn snapshot_dir() -> PathBuf {
+    let root = env::var_os("CARGO_MANIFEST_DIR").unwrap_or_else(|| ".".into());
+    Path::new(&root).join("tests").join("snapshots")
+}
+
+pub fn assert_matches_snapshot(name: &str, value: &Value<'_>) {
+    assert_matches_snapshot_in(snapshot_dir(), name, value)
+}
+
+pub fn assert_matches_snapshot_in<P: AsRef<Path>>(dir: P, name: &str, value: &Value<'_>) {
+    let path = dir.as_ref().join(format!("{}.snbt", name));
+    let mut actual = snbt::to_string_pretty(value);
+    actual.push('\n');
+
+    let update = env::var_os(UPDATE_ENV).is_some_and(|v| v != "0");
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) if !update => expected,
+        _ => {
+            fs::create_dir_all(dir.as_ref()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            return;
+        }
+    };
+
+    if expected == actual {
+        return;
+    }
+
+    let mut message = format!(
+        "snapshot {:?} does not match {} (set {}=1 to update)",
+        name,
+        path.display(),
+        UPDATE_ENV
+    );
+    let (expected, actual): (Vec<_>, Vec<_>) =
+        (expected.lines().collect(), actual.lines().collect());
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => {}
+            (e, a) => {
+                message.push_str(&format!("\n  line {}:", i + 1));
+                if let Some(e) = e {
+                    message.push_str(&format!("\n    - {}", e));
+                }
+                if let Some(a) = a {
+                    message.push_str(&format!("\n    + {}", a));
+                }
+            }
+        }
+    }
+
+    panic!("{}", message);
+}
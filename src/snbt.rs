@@ -0,0 +1,396 @@
+use std::{borrow::Cow, fmt::Write};
+
+use crate::{
+    codec::DEFAULT_MAX_DEPTH,
+    error::{NBTError, Result},
+    tag::Tag,
+    value::{CompoundMap, Value, bytes_as_i8_slice, i8_vec_into_bytes},
+};
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+'))
+}
+
+fn write_quoted(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_key(out: &mut String, key: &str) {
+    if is_bare_key(key) {
+        out.push_str(key);
+    } else {
+        write_quoted(out, key);
+    }
+}
+
+fn write_array<T: ToString>(out: &mut String, prefix: &str, suffix: &str, items: &[T]) {
+    out.push('[');
+    out.push_str(prefix);
+    out.push(';');
+    for (i, item) in items.iter().enumerate() {
+        out.push_str(if i == 0 { "" } else { "," });
+        out.push_str(&item.to_string());
+        out.push_str(suffix);
+    }
+    out.push(']');
+}
+
+pub fn to_string(value: &Value<'_>) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, None);
+    out
+}
+
+pub fn to_string_pretty(value: &Value<'_>) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, Some(0));
+    out
+}
+
+fn newline(out: &mut String, indent: Option<usize>) {
+    if let Some(indent) = indent {
+        out.push('\n');
+        out.push_str(&"  ".repeat(indent));
+    }
+}
+
+fn write_value(out: &mut String, value: &Value<'_>, indent: Option<usize>) {
+    let inner = indent.map(|i| i + 1);
+
+    match value {
+        Value::End => {}
+        Value::Byte(v) => {
+            let _ = write!(out, "{}b", v);
+        }
+        Value::Short(v) => {
+            let _ = write!(out, "{}s", v);
+        }
+        Value::Int(v) => {
+            let _ = write!(out, "{}", v);
+        }
+        Value::Long(v) => {
+            let _ = write!(out, "{}L", v);
+        }
+        Value::Float(v) => {
+            let _ = write!(out, "{}f", v);
+        }
+        Value::Double(v) => {
+            let _ = write!(out, "{}d", v);
+        }
+        Value::String(v) => write_quoted(out, v),
+        Value::ByteArray(v) => write_array(out, "B", "b", bytes_as_i8_slice(v)),
+        Value::Unknown(id, v) => write_array(out, &format!("U{}", id), "b", bytes_as_i8_slice(v)),
+        Value::IntArray(v) => write_array(out, "I", "", v),
+        Value::LongArray(v) => write_array(out, "L", "L", v),
+        Value::EmptyList(_) => out.push_str("[]"),
+        Value::List(list) => {
+            out.push('[');
+            for (i, child) in list.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                newline(out, inner);
+                write_value(out, child, inner);
+            }
+            if !list.is_empty() {
+                newline(out, indent);
+            }
+            out.push(']');
+        }
+        Value::Compound(map) => {
+            out.push('{');
+            for (i, (key, child)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                newline(out, inner);
+                write_key(out, key);
+                out.push_str(if indent.is_some() { ": " } else { ":" });
+                write_value(out, child, inner);
+            }
+            if !map.is_empty() {
+                newline(out, indent);
+            }
+            out.push('}');
+        }
+    }
+}
+
+pub fn from_str(input: &str) -> Result<Value<'static>> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        depth: 0,
+    };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if !parser.rest().is_empty() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(value)
+}
+
+struct Parser<'s> {
+    input: &'s str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn error(&self, msg: &str) -> NBTError {
+        NBTError::invalid_syntax(format!("{} at byte {}", msg, self.pos))
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {:?}", c)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value<'static>> {
+        self.skip_whitespace();
+        match self.rest().chars().next() {
+            Some('{') => self.parse_nested(Self::parse_compound),
+            Some('[') => self.parse_nested(Self::parse_list),
+            Some('"' | '\'') => Ok(Value::String(Cow::Owned(self.parse_quoted()?))),
+            Some(_) => {
+                let token = self.parse_bare()?;
+                Ok(scalar(token))
+            }
+            None => Err(self.error("expected value")),
+        }
+    }
+
+    fn parse_nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Value<'static>>,
+    ) -> Result<Value<'static>> {
+        if self.depth >= DEFAULT_MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_compound(&mut self) -> Result<Value<'static>> {
+        self.expect('{')?;
+        let mut map = CompoundMap::new();
+        if self.eat('}') {
+            return Ok(Value::Compound(map));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = match self.rest().chars().next() {
+                Some('"' | '\'') => self.parse_quoted()?,
+                _ => self.parse_bare()?.to_owned(),
+            };
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            map.insert(Cow::Owned(key), value);
+
+            if self.eat('}') {
+                return Ok(Value::Compound(map));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Value<'static>> {
+        self.expect('[')?;
+
+        let rest = self.rest();
+        if let Some(kind @ ('B' | 'I' | 'L')) = rest.chars().next()
+            && rest[1..].trim_start().starts_with(';')
+        {
+            self.pos += 1;
+            self.expect(';')?;
+            return self.parse_array(kind);
+        }
+
+        let digits = rest.strip_prefix('U').map_or(0, |tail| {
+            tail.len() - tail.trim_start_matches(|c: char| c.is_ascii_digit()).len()
+        });
+        if digits > 0 && rest[1 + digits..].trim_start().starts_with(';') {
+            let id = rest[1..1 + digits]
+                .parse::<u8>()
+                .ok()
+                .filter(|&id| Tag::try_from(id).is_err())
+                .ok_or_else(|| self.error("invalid unknown tag id"))?;
+            self.pos += 1 + digits;
+            self.expect(';')?;
+            return match self.parse_array('B')? {
+                Value::ByteArray(bytes) => Ok(Value::Unknown(id, bytes)),
+                _ => unreachable!("[B;] arrays parse to ByteArray"),
+            };
+        }
+
+        let mut items = Vec::new();
+        if self.eat(']') {
+            return Ok(Value::List(items));
+        }
+        loop {
+            let item = self.parse_value()?;
+            if let Some(first) = items.first()
+                && Value::tag(first) != item.tag()
+            {
+                return Err(self.error(&format!(
+                    "list holds {:?}, found {:?}",
+                    Value::tag(first),
+                    item.tag()
+                )));
+            }
+            items.push(item);
+
+            if self.eat(']') {
+                return Ok(Value::List(items));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn parse_array(&mut self, kind: char) -> Result<Value<'static>> {
+        let mut items = Vec::new();
+        if !self.eat(']') {
+            loop {
+                self.skip_whitespace();
+                items.push(scalar(self.parse_bare()?));
+                if self.eat(']') {
+                    break;
+                }
+                self.expect(',')?;
+            }
+        }
+
+        let mismatch =
+            |item: &Value<'_>| self.error(&format!("{:?} in a [{};] array", item.tag(), kind));
+        let values = items.into_iter();
+        Ok(match kind {
+            'B' => Value::ByteArray(i8_vec_into_bytes(
+                values
+                    .map(|item| match item {
+                        Value::Byte(v) => Ok(v),
+                        other => Err(mismatch(&other)),
+                    })
+                    .collect::<Result<_>>()?,
+            )),
+            'I' => Value::IntArray(
+                values
+                    .map(|item| match item {
+                        Value::Int(v) => Ok(v),
+                        other => Err(mismatch(&other)),
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            _ => Value::LongArray(
+                values
+                    .map(|item| match item {
+                        Value::Long(v) => Ok(v),
+                        other => Err(mismatch(&other)),
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+        })
+    }
+
+    fn parse_quoted(&mut self) -> Result<String> {
+        let mut chars = self.rest().char_indices();
+        let (_, quote) = chars.next().ok_or_else(|| self.error("expected string"))?;
+        let mut out = String::new();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    let (_, escaped) = chars.next().ok_or_else(|| self.error("dangling escape"))?;
+                    out.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        c => c,
+                    });
+                }
+                c if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                c => out.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn parse_bare(&mut self) -> Result<&'s str> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected value"));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+}
+
+fn scalar(token: &str) -> Value<'static> {
+    let (body, suffix) = token.split_at(token.len() - 1);
+    let number = match suffix {
+        "b" | "B" => body.parse().ok().map(Value::Byte),
+        "s" | "S" => body.parse().ok().map(Value::Short),
+        "l" | "L" => body.parse().ok().map(Value::Long),
+        "f" | "F" => body.parse().ok().map(Value::Float),
+        "d" | "D" => body.parse().ok().map(Value::Double),
+        _ => None,
+    };
+
+    number
+        .or_else(|| token.parse().ok().map(Value::Int))
+        .or_else(|| {
+            token
+                .contains(['.', 'e', 'E'])
+                .then(|| token.parse().ok().map(Value::Double))
+                .flatten()
+        })
+        .unwrap_or_else(|| match token {
+            "true" => Value::Byte(1),
+            "false" => Value::Byte(0),
+            _ => Value::String(Cow::Owned(token.to_owned())),
+        })
+}
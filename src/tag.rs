@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Tag {
     End,
@@ -38,3 +38,79 @@ impl TryFrom<u8> for Tag {
         }
     }
 }
+
+impl Tag {
+    pub fn fixed_payload_size(self) -> Option<usize> {
+        match self {
+            Tag::End => Some(0),
+            Tag::Byte => Some(1),
+            Tag::Short => Some(2),
+            Tag::Int | Tag::Float => Some(4),
+            Tag::Long | Tag::Double => Some(8),
+            _ => None,
+        }
+    }
+
+    pub fn array_element_tag(self) -> Option<Tag> {
+        match self {
+            Tag::ByteArray => Some(Tag::Byte),
+            Tag::IntArray => Some(Tag::Int),
+            Tag::LongArray => Some(Tag::Long),
+            _ => None,
+        }
+    }
+
+    pub fn is_container(self) -> bool {
+        matches!(self, Tag::List | Tag::Compound)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TagKind {
+    Known(Tag),
+    Extension(u8),
+}
+
+impl TagKind {
+    pub fn from_id(id: u8) -> Self {
+        match Tag::try_from(id) {
+            Ok(tag) => TagKind::Known(tag),
+            Err(_) => TagKind::Extension(id),
+        }
+    }
+
+    pub fn id(self) -> u8 {
+        match self {
+            TagKind::Known(tag) => tag as u8,
+            TagKind::Extension(id) => id,
+        }
+    }
+
+    pub fn known(self) -> Option<Tag> {
+        match self {
+            TagKind::Known(tag) => Some(tag),
+            TagKind::Extension(_) => None,
+        }
+    }
+}
+
+impl From<Tag> for TagKind {
+    fn from(tag: Tag) -> Self {
+        TagKind::Known(tag)
+    }
+}
+
+impl PartialEq<Tag> for TagKind {
+    fn eq(&self, other: &Tag) -> bool {
+        *self == TagKind::Known(*other)
+    }
+}
+
+impl std::fmt::Debug for TagKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagKind::Known(tag) => tag.fmt(f),
+            TagKind::Extension(id) => write!(f, "Extension({})", id),
+        }
+    }
+}
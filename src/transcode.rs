@@ -0,0 +1,317 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    io::{Read, Write},
+};
+
+use serde::{
+    Deserializer, Serialize, Serializer,
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{self, SerializeMap, SerializeSeq},
+};
+
+use crate::{
+    codec::{ConfiguredCodec, NBTCodecTrait, Session},
+    error::{NBTError, Result},
+    tag::{Tag, TagKind},
+};
+
+pub fn transcode<'de, D, W>(
+    codec: &ConfiguredCodec,
+    deserializer: D,
+    writer: &mut W,
+    root_name: &str,
+) -> Result<()>
+where
+    D: Deserializer<'de>,
+    W: Write,
+{
+    let seed = EntrySeed {
+        codec,
+        writer,
+        name: Some(root_name),
+    };
+
+    match seed.deserialize(deserializer) {
+        Ok(Tag::End) => Err(NBTError::custom_msg("Cannot transcode a null root")),
+        Ok(_) => Ok(()),
+        Err(e) => Err(NBTError::custom_msg(e.to_string())),
+    }
+}
+
+pub fn transcode_to_serde<R, S>(
+    codec: &ConfiguredCodec,
+    reader: &mut R,
+    serializer: S,
+) -> Result<S::Ok>
+where
+    R: Read,
+    S: Serializer,
+{
+    let session = codec.session();
+    let tag = session.read_kind(reader)?;
+    session.read_name(reader)?;
+
+    let payload = Payload {
+        session: &session,
+        reader: &RefCell::new(reader),
+        tag,
+    };
+
+    payload
+        .serialize(serializer)
+        .map_err(|e| NBTError::custom_msg(e.to_string()))
+}
+
+struct EntrySeed<'c, 'n, W> {
+    codec: &'c ConfiguredCodec,
+    writer: &'c mut W,
+    name: Option<&'n str>,
+}
+
+impl<W: Write> EntrySeed<'_, '_, W> {
+    fn header<E: de::Error>(&mut self, tag: Tag) -> std::result::Result<(), E> {
+        if let Some(name) = self.name {
+            self.codec
+                .write_u8(self.writer, tag as u8)
+                .and_then(|_| self.codec.write_name(self.writer, name))
+                .map_err(de_error)?;
+        }
+        Ok(())
+    }
+
+    fn scalar<E, F>(mut self, tag: Tag, write: F) -> std::result::Result<Tag, E>
+    where
+        E: de::Error,
+        F: FnOnce(&ConfiguredCodec, &mut W) -> Result<()>,
+    {
+        self.header(tag)?;
+        write(self.codec, self.writer).map_err(de_error)?;
+        Ok(tag)
+    }
+}
+
+fn de_error<E: de::Error>(err: NBTError) -> E {
+    E::custom(format!("{:?}", err.kind))
+}
+
+impl<'de, W: Write> DeserializeSeed<'de> for EntrySeed<'_, '_, W> {
+    type Value = Tag;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Tag, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, W: Write> Visitor<'de> for EntrySeed<'_, '_, W> {
+    type Value = Tag;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a value representable as NBT")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::Byte, |c, w| c.write_i8(w, v as i8))
+    }
+
+    fn visit_i8<E: de::Error>(self, v: i8) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::Byte, |c, w| c.write_i8(w, v))
+    }
+
+    fn visit_i16<E: de::Error>(self, v: i16) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::Short, |c, w| c.write_i16(w, v))
+    }
+
+    fn visit_i32<E: de::Error>(self, v: i32) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::Int, |c, w| c.write_i32(w, v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Tag, E> {
+        match i32::try_from(v) {
+            Ok(v) => self.visit_i32(v),
+            Err(_) => self.scalar(Tag::Long, |c, w| c.write_i64(w, v)),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Tag, E> {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Err(E::custom(format!("{} does not fit in a TAG_Long", v))),
+        }
+    }
+
+    fn visit_f32<E: de::Error>(self, v: f32) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::Float, |c, w| c.write_f32(w, v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::Double, |c, w| c.write_f64(w, v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::String, |c, w| c.write_string(w, v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> std::result::Result<Tag, E> {
+        self.scalar(Tag::ByteArray, |c, w| c.write_byte_array(w, v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Tag, E> {
+        Ok(Tag::End)
+    }
+
+    fn visit_none<E: de::Error>(self) -> std::result::Result<Tag, E> {
+        Ok(Tag::End)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Tag, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> std::result::Result<Tag, A::Error> {
+        let mut buf = Vec::new();
+        let mut element_tag = Tag::End;
+        let mut len = 0i32;
+
+        loop {
+            let seed = EntrySeed {
+                codec: self.codec,
+                writer: &mut buf,
+                name: None,
+            };
+            let Some(tag) = seq.next_element_seed(seed)? else {
+                break;
+            };
+
+            if tag == Tag::End {
+                return Err(de::Error::custom("null is not allowed in a list"));
+            }
+            if len > 0 && tag != element_tag {
+                return Err(de::Error::custom(format!(
+                    "List type mismatch at index {}: expected {:?}, got {:?}",
+                    len, element_tag, tag
+                )));
+            }
+            element_tag = tag;
+            len += 1;
+        }
+
+        self.header(Tag::List)?;
+        self.codec
+            .write_u8(self.writer, element_tag as u8)
+            .and_then(|_| self.codec.write_i32(self.writer, len))
+            .map_err(de_error)?;
+        self.writer
+            .write_all(&buf)
+            .map_err(|e| de_error(NBTError::io(e)))?;
+
+        Ok(Tag::List)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> std::result::Result<Tag, A::Error> {
+        self.header(Tag::Compound)?;
+
+        while let Some(key) = map.next_key::<String>()? {
+            map.next_value_seed(EntrySeed {
+                codec: self.codec,
+                writer: &mut *self.writer,
+                name: Some(&key),
+            })?;
+        }
+
+        self.codec
+            .write_u8(self.writer, Tag::End as u8)
+            .map_err(de_error)?;
+        Ok(Tag::Compound)
+    }
+}
+
+struct Payload<'s, 'c, 'r, R> {
+    session: &'s Session<'c>,
+    reader: &'r RefCell<&'r mut R>,
+    tag: TagKind,
+}
+
+impl<R: Read> Payload<'_, '_, '_, R> {
+    fn read<T, E, F>(&self, read: F) -> std::result::Result<T, E>
+    where
+        E: ser::Error,
+        F: FnOnce(&Session<'_>, &mut R) -> Result<T>,
+    {
+        read(self.session, *self.reader.borrow_mut())
+            .map_err(|e| E::custom(format!("{:?}", e.kind)))
+    }
+}
+
+impl<R: Read> Serialize for Payload<'_, '_, '_, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let tag = match self.tag {
+            TagKind::Known(tag) => tag,
+            TagKind::Extension(id) => {
+                return serializer
+                    .serialize_bytes(&self.read(|c, r| c.read_unknown_payload(r, id))?);
+            }
+        };
+
+        match tag {
+            Tag::End => serializer.serialize_unit(),
+            Tag::Byte => serializer.serialize_i8(self.read(|c, r| c.read_i8(r))?),
+            Tag::Short => serializer.serialize_i16(self.read(|c, r| c.read_i16(r))?),
+            Tag::Int => serializer.serialize_i32(self.read(|c, r| c.read_i32(r))?),
+            Tag::Long => serializer.serialize_i64(self.read(|c, r| c.read_i64(r))?),
+            Tag::Float => serializer.serialize_f32(self.read(|c, r| c.read_f32(r))?),
+            Tag::Double => serializer.serialize_f64(self.read(|c, r| c.read_f64(r))?),
+            Tag::String => serializer.serialize_str(&self.read(|c, r| c.read_string(r))?),
+            Tag::ByteArray => serializer.serialize_bytes(&self.read(|c, r| c.read_byte_array(r))?),
+            Tag::IntArray => serializer.collect_seq(self.read(|c, r| c.read_int_array(r))?),
+            Tag::LongArray => serializer.collect_seq(self.read(|c, r| c.read_long_array(r))?),
+            Tag::List => {
+                let (element_tag, len) = self.read(|c, r| {
+                    let tag = c.read_kind(r)?;
+                    Ok((tag, c.read_i32(r)?))
+                })?;
+                let len = usize::try_from(len)
+                    .map_err(|_| ser::Error::custom(format!("negative list length {}", len)))?;
+
+                let mut seq = serializer.serialize_seq(Some(len))?;
+                for _ in 0..len {
+                    seq.serialize_element(&Payload {
+                        session: self.session,
+                        reader: self.reader,
+                        tag: element_tag,
+                    })?;
+                }
+                seq.end()
+            }
+            Tag::Compound => {
+                let mut map = serializer.serialize_map(None)?;
+                let mut entries = 0;
+                loop {
+                    let tag = self.read(|c, r| c.read_kind(r))?;
+                    if tag == Tag::End {
+                        break;
+                    }
+                    self.read(|c, _| c.check_compound_entries(entries))?;
+                    entries += 1;
+
+                    let name = self.read(|c, r| c.read_name(r))?;
+                    map.serialize_entry(
+                        &name,
+                        &Payload {
+                            session: self.session,
+                            reader: self.reader,
+                            tag,
+                        },
+                    )?;
+                }
+                map.end()
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
-use std::{borrow::Cow, collections::BTreeMap};
+use std::{borrow::Cow, cmp::Ordering, collections::BTreeMap, fmt::Debug};
 
 use crate::{
     error::{NBTError, Result},
-    tag::Tag,
+    map::{CompoundOps, MapBackend, SmallMap, Sorted},
+    tag::{Tag, TagKind},
 };
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum Value<'a> {
+pub type CompoundMap<'a> = SmallMap<Cow<'a, str>, Value<'a>>;
+
+pub enum Value<'a, M: MapBackend = Sorted> {
     End,
     Byte(i8),
     Short(i16),
@@ -14,17 +16,86 @@ pub enum Value<'a> {
     Long(i64),
     Float(f32),
     Double(f64),
-    ByteArray(Vec<i8>),
+    ByteArray(Vec<u8>),
     String(Cow<'a, str>),
-    List(Vec<Value<'a>>),
-    Compound(BTreeMap<Cow<'a, str>, Value<'a>>),
+    List(Vec<Value<'a, M>>),
+    EmptyList(Tag),
+    Compound(M::Map<'a>),
     IntArray(Vec<i32>),
     LongArray(Vec<i64>),
+    Unknown(u8, Vec<u8>),
 }
 
-impl<'a> Value<'a> {
-    pub fn tag(&self) -> Tag {
+impl<M: MapBackend> Clone for Value<'_, M> {
+    fn clone(&self) -> Self {
+        match self {
+            Value::End => Value::End,
+            Value::Byte(v) => Value::Byte(*v),
+            Value::Short(v) => Value::Short(*v),
+            Value::Int(v) => Value::Int(*v),
+            Value::Long(v) => Value::Long(*v),
+            Value::Float(v) => Value::Float(*v),
+            Value::Double(v) => Value::Double(*v),
+            Value::ByteArray(v) => Value::ByteArray(v.clone()),
+            Value::String(v) => Value::String(v.clone()),
+            Value::List(v) => Value::List(v.clone()),
+            Value::EmptyList(v) => Value::EmptyList(*v),
+            Value::Compound(v) => Value::Compound(v.clone()),
+            Value::IntArray(v) => Value::IntArray(v.clone()),
+            Value::LongArray(v) => Value::LongArray(v.clone()),
+            Value::Unknown(id, v) => Value::Unknown(*id, v.clone()),
+        }
+    }
+}
+
+impl<M: MapBackend> Debug for Value<'_, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Value::End => f.write_str("End"),
+            Value::Byte(v) => f.debug_tuple("Byte").field(v).finish(),
+            Value::Short(v) => f.debug_tuple("Short").field(v).finish(),
+            Value::Int(v) => f.debug_tuple("Int").field(v).finish(),
+            Value::Long(v) => f.debug_tuple("Long").field(v).finish(),
+            Value::Float(v) => f.debug_tuple("Float").field(v).finish(),
+            Value::Double(v) => f.debug_tuple("Double").field(v).finish(),
+            Value::ByteArray(v) => f.debug_tuple("ByteArray").field(v).finish(),
+            Value::String(v) => f.debug_tuple("String").field(v).finish(),
+            Value::List(v) => f.debug_tuple("List").field(v).finish(),
+            Value::EmptyList(v) => f.debug_tuple("EmptyList").field(v).finish(),
+            Value::Compound(v) => f.debug_tuple("Compound").field(v).finish(),
+            Value::IntArray(v) => f.debug_tuple("IntArray").field(v).finish(),
+            Value::LongArray(v) => f.debug_tuple("LongArray").field(v).finish(),
+            Value::Unknown(id, v) => f.debug_tuple("Unknown").field(id).field(v).finish(),
+        }
+    }
+}
+
+impl<M: MapBackend> PartialEq for Value<'_, M> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::End, Value::End) => true,
+            (Value::Byte(a), Value::Byte(b)) => a == b,
+            (Value::Short(a), Value::Short(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Long(a), Value::Long(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::Double(a), Value::Double(b)) => a == b,
+            (Value::ByteArray(a), Value::ByteArray(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::List(a), Value::List(b)) => a == b,
+            (Value::EmptyList(a), Value::EmptyList(b)) => a == b,
+            (Value::Compound(a), Value::Compound(b)) => a == b,
+            (Value::IntArray(a), Value::IntArray(b)) => a == b,
+            (Value::LongArray(a), Value::LongArray(b)) => a == b,
+            (Value::Unknown(a, x), Value::Unknown(b, y)) => a == b && x == y,
+            _ => false,
+        }
+    }
+}
+
+impl<'a, M: MapBackend> Value<'a, M> {
+    pub fn tag(&self) -> TagKind {
+        TagKind::Known(match self {
             Value::End => Tag::End,
             Value::Byte(_) => Tag::Byte,
             Value::Short(_) => Tag::Short,
@@ -34,78 +105,324 @@ impl<'a> Value<'a> {
             Value::Double(_) => Tag::Double,
             Value::ByteArray(_) => Tag::ByteArray,
             Value::String(_) => Tag::String,
-            Value::List(_) => Tag::List,
+            Value::List(_) | Value::EmptyList(_) => Tag::List,
             Value::Compound(_) => Tag::Compound,
             Value::IntArray(_) => Tag::IntArray,
             Value::LongArray(_) => Tag::LongArray,
+            Value::Unknown(id, _) => return TagKind::Extension(*id),
+        })
+    }
+
+    pub fn tag_id(&self) -> u8 {
+        self.tag().id()
+    }
+
+    pub fn is_compound(&self) -> bool {
+        matches!(self, Value::Compound(_))
+    }
+
+    pub fn is_list(&self) -> bool {
+        matches!(self, Value::List(_) | Value::EmptyList(_))
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Value::Byte(_)
+                | Value::Short(_)
+                | Value::Int(_)
+                | Value::Long(_)
+                | Value::Float(_)
+                | Value::Double(_)
+        )
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(
+            self,
+            Value::ByteArray(_) | Value::IntArray(_) | Value::LongArray(_)
+        )
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Value::Unknown(..))
+    }
+
+    pub fn into_owned(self) -> Value<'static, M> {
+        match self {
+            Value::End => Value::End,
+            Value::Byte(v) => Value::Byte(v),
+            Value::Short(v) => Value::Short(v),
+            Value::Int(v) => Value::Int(v),
+            Value::Long(v) => Value::Long(v),
+            Value::Float(v) => Value::Float(v),
+            Value::Double(v) => Value::Double(v),
+            Value::ByteArray(v) => Value::ByteArray(v),
+            Value::String(v) => Value::String(Cow::Owned(v.into_owned())),
+            Value::List(v) => Value::List(v.into_iter().map(Value::into_owned).collect()),
+            Value::EmptyList(tag) => Value::EmptyList(tag),
+            Value::Compound(v) => Value::Compound(
+                v.into_iter()
+                    .map(|(k, v)| (Cow::Owned(k.into_owned()), v.into_owned()))
+                    .collect(),
+            ),
+            Value::IntArray(v) => Value::IntArray(v),
+            Value::LongArray(v) => Value::LongArray(v),
+            Value::Unknown(id, v) => Value::Unknown(id, v),
         }
     }
 
-    pub fn compound() -> Self {
-        Value::Compound(BTreeMap::new())
+    pub fn into_backend<N: MapBackend>(self) -> Value<'a, N> {
+        match self {
+            Value::End => Value::End,
+            Value::Byte(v) => Value::Byte(v),
+            Value::Short(v) => Value::Short(v),
+            Value::Int(v) => Value::Int(v),
+            Value::Long(v) => Value::Long(v),
+            Value::Float(v) => Value::Float(v),
+            Value::Double(v) => Value::Double(v),
+            Value::ByteArray(v) => Value::ByteArray(v),
+            Value::String(v) => Value::String(v),
+            Value::List(v) => Value::List(v.into_iter().map(Value::into_backend).collect()),
+            Value::EmptyList(tag) => Value::EmptyList(tag),
+            Value::Compound(v) => {
+                Value::Compound(v.into_iter().map(|(k, v)| (k, v.into_backend())).collect())
+            }
+            Value::IntArray(v) => Value::IntArray(v),
+            Value::LongArray(v) => Value::LongArray(v),
+            Value::Unknown(id, v) => Value::Unknown(id, v),
+        }
     }
 
-    pub fn insert<K, V>(&mut self, key: K, value: V) -> Result<()>
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Result<Option<Value<'a, M>>>
     where
         K: Into<Cow<'a, str>>,
-        V: Into<Value<'a>>,
+        V: Into<Value<'a, M>>,
     {
         match self {
-            Value::Compound(map) => {
-                map.insert(key.into(), value.into());
-                Ok(())
-            }
+            Value::Compound(map) => Ok(map.insert(key.into(), value.into())),
             _ => Err(NBTError::custom_msg("Not a compound")),
         }
     }
 
-    pub fn list(capacity: usize) -> Self {
-        Value::List(Vec::with_capacity(capacity))
+    pub fn get(&self, key: &str) -> Option<&Value<'a, M>> {
+        match self {
+            Value::Compound(map) => map.get(key),
+            _ => None,
+        }
     }
 
-    pub fn push<V: Into<Value<'a>>>(&mut self, value: V) -> Result<()> {
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value<'a, M>> {
         match self {
-            Value::List(vec) => {
+            Value::Compound(map) => map.get_mut(key),
+            _ => None,
+        }
+    }
+
+    pub fn get_ignore_case(&self, key: &str) -> Option<&Value<'a, M>> {
+        match self {
+            Value::Compound(map) => map.get(key).or_else(|| {
+                map.iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                    .map(|(_, v)| v)
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn get_ignore_case_mut(&mut self, key: &str) -> Option<&mut Value<'a, M>> {
+        match self {
+            Value::Compound(map) => {
+                let exact = map.contains_key(key);
+                map.iter_mut()
+                    .find(|(k, _)| match exact {
+                        true => k.as_ref() == key,
+                        false => k.eq_ignore_ascii_case(key),
+                    })
+                    .map(|(_, v)| v)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn push<V: Into<Value<'a, M>>>(&mut self, value: V) -> Result<()> {
+        match self.make_list_mut() {
+            Some(vec) => {
                 vec.push(value.into());
                 Ok(())
             }
-            _ => Err(NBTError::custom_msg("Not a list")),
+            None => Err(NBTError::custom_msg("Not a list")),
         }
     }
 
     pub fn extend<I, V>(&mut self, iter: I) -> Result<()>
     where
         I: IntoIterator<Item = V>,
-        V: Into<Value<'a>>,
+        V: Into<Value<'a, M>>,
     {
-        match self {
-            Value::List(vec) => {
+        match self.make_list_mut() {
+            Some(vec) => {
                 vec.extend(iter.into_iter().map(Into::into));
                 Ok(())
             }
-            _ => Err(NBTError::custom_msg("Not a list")),
+            None => Err(NBTError::custom_msg("Not a list")),
         }
     }
 
-    pub fn list_from_iter<I, V>(iter: I) -> Self
-    where
-        I: IntoIterator<Item = V>,
-        V: Into<Self>,
-    {
-        Value::List(iter.into_iter().map(Into::into).collect())
+    pub fn push_promoted<V: Into<Value<'a, M>>>(&mut self, value: V) -> Result<()> {
+        self.push(value)?;
+        self.promote_numbers().inspect_err(|_| {
+            self.as_list_mut().and_then(Vec::pop);
+        })
+    }
+
+    pub fn promote_numbers(&mut self) -> Result<()> {
+        if let Value::EmptyList(_) = self {
+            return Ok(());
+        }
+        let Some(vec) = self.as_list_mut() else {
+            return Err(NBTError::custom_msg("Not a list"));
+        };
+
+        let Some(first) = vec.first().map(Value::tag) else {
+            return Ok(());
+        };
+        if vec.iter().all(|v| v.tag() == first) {
+            return Ok(());
+        }
+
+        if let Some((i, value)) = vec
+            .iter()
+            .enumerate()
+            .find(|(_, v)| !v.tag().known().is_some_and(is_number))
+        {
+            return Err(NBTError::custom_msg(format!(
+                "List type mismatch at index {}: cannot promote {:?} to a numeric tag",
+                i,
+                value.tag()
+            )));
+        }
+
+        let widest_int = vec
+            .iter()
+            .filter_map(|v| v.tag().known())
+            .filter(|&tag| is_integer(tag))
+            .max_by_key(|&tag| tag as u8);
+        let has_float = vec.iter().any(|v| v.tag() == Tag::Float);
+        let has_double = vec.iter().any(|v| v.tag() == Tag::Double);
+
+        let target = match widest_int {
+            _ if has_double => Tag::Double,
+            Some(Tag::Byte | Tag::Short) | None if has_float => Tag::Float,
+            _ if has_float => Tag::Double,
+            Some(tag) => tag,
+            None => unreachable!("mixed list has at least one numeric tag"),
+        };
+
+        for value in vec.iter_mut() {
+            if value.tag() != target {
+                *value = promote_number(value, target);
+            }
+        }
+
+        Ok(())
     }
 
-    pub fn list_tag(&self) -> Option<Tag> {
+    pub fn typed_list(tag: Tag, values: Vec<Value<'a, M>>) -> Result<Self> {
+        if let Some((i, value)) = values.iter().enumerate().find(|(_, v)| v.tag() != tag) {
+            return Err(NBTError::custom_msg(format!(
+                "List type mismatch at index {}: expected {:?}, got {:?}",
+                i,
+                tag,
+                value.tag()
+            )));
+        }
+
+        match values.is_empty() && tag != Tag::End {
+            true => Ok(Value::EmptyList(tag)),
+            false => Ok(Value::List(values)),
+        }
+    }
+
+    pub fn list_tag(&self) -> Option<TagKind> {
         match self {
             Value::List(vec) if !vec.is_empty() => Some(vec[0].tag()),
-            Value::List(_) => None,
+            Value::EmptyList(tag) => Some(TagKind::Known(*tag)),
             _ => None,
         }
     }
 
-    pub fn as_list(&self) -> Option<&Vec<Value<'a>>> {
+    pub fn as_list(&self) -> Option<&[Value<'a, M>]> {
         match self {
             Value::List(vec) => Some(vec),
+            Value::EmptyList(_) => Some(&[]),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(&self) -> Option<u8> {
+        match self {
+            Value::Byte(v) => Some(*v as u8),
+            _ => None,
+        }
+    }
+
+    pub fn as_u16(&self) -> Option<u16> {
+        match self {
+            Value::Short(v) => Some(*v as u16),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            Value::Int(v) => Some(*v as u32),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Long(v) => Some(*v as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::ByteArray(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes_mut(&mut self) -> Option<&mut Vec<u8>> {
+        match self {
+            Value::ByteArray(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_i8_slice(&self) -> Option<&[i8]> {
+        match self {
+            Value::ByteArray(vec) => Some(bytes_as_i8_slice(vec)),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32_slice(&self) -> Option<&[i32]> {
+        match self {
+            Value::IntArray(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64_slice(&self) -> Option<&[i64]> {
+        match self {
+            Value::LongArray(vec) => Some(vec),
             _ => None,
         }
     }
@@ -116,6 +433,159 @@ impl<'a> Value<'a> {
             _ => None,
         }
     }
+
+    pub fn make_list_mut(&mut self) -> Option<&mut Vec<Self>> {
+        if let Value::EmptyList(_) = self {
+            *self = Value::List(Vec::new());
+        }
+        self.as_list_mut()
+    }
+
+    pub fn sort_list<F>(&mut self, compare: F) -> Result<()>
+    where
+        F: FnMut(&Value<'a, M>, &Value<'a, M>) -> Ordering,
+    {
+        match self {
+            Value::List(vec) => {
+                vec.sort_by(compare);
+                Ok(())
+            }
+            Value::EmptyList(_) => Ok(()),
+            _ => Err(NBTError::custom_msg("Not a list")),
+        }
+    }
+
+    pub fn sort_list_by_key(&mut self, key: &str) -> Result<()> {
+        self.sort_list(|a, b| match (a.get(key), b.get(key)) {
+            (Some(a), Some(b)) => compare_values(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn compound() -> Self {
+        Value::Compound(CompoundMap::new())
+    }
+
+    pub fn list(capacity: usize) -> Self {
+        Value::List(Vec::with_capacity(capacity))
+    }
+
+    pub fn list_from_iter<I, V>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Self>,
+    {
+        Value::List(iter.into_iter().map(Into::into).collect())
+    }
+
+    pub fn list_from_iter_promoted<I, V>(iter: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Self>,
+    {
+        let mut list = Self::list_from_iter(iter);
+        list.promote_numbers()?;
+        Ok(list)
+    }
+
+    pub fn from_u8_as_byte(value: u8) -> Self {
+        Value::Byte(value as i8)
+    }
+
+    pub fn from_u16_as_short(value: u16) -> Self {
+        Value::Short(value as i16)
+    }
+
+    pub fn from_u32_as_int(value: u32) -> Self {
+        Value::Int(value as i32)
+    }
+
+    pub fn from_u64_as_long(value: u64) -> Self {
+        Value::Long(value as i64)
+    }
+}
+
+fn is_integer(tag: Tag) -> bool {
+    matches!(tag, Tag::Byte | Tag::Short | Tag::Int | Tag::Long)
+}
+
+fn is_number(tag: Tag) -> bool {
+    is_integer(tag) || matches!(tag, Tag::Float | Tag::Double)
+}
+
+fn promote_number<'a, M: MapBackend>(value: &Value<'_, M>, target: Tag) -> Value<'a, M> {
+    let (int, float) = match *value {
+        Value::Byte(v) => (v as i64, v as f64),
+        Value::Short(v) => (v as i64, v as f64),
+        Value::Int(v) => (v as i64, v as f64),
+        Value::Long(v) => (v, v as f64),
+        Value::Float(v) => (v as i64, v as f64),
+        Value::Double(v) => (v as i64, v),
+        _ => unreachable!("only numeric tags are promoted"),
+    };
+
+    match target {
+        Tag::Short => Value::Short(int as i16),
+        Tag::Int => Value::Int(int as i32),
+        Tag::Long => Value::Long(int),
+        Tag::Float => Value::Float(float as f32),
+        _ => Value::Double(float),
+    }
+}
+
+pub fn compare_values<M: MapBackend>(a: &Value<'_, M>, b: &Value<'_, M>) -> Ordering {
+    fn as_f64<M: MapBackend>(value: &Value<'_, M>) -> Option<f64> {
+        match value {
+            Value::Byte(v) => Some(*v as f64),
+            Value::Short(v) => Some(*v as f64),
+            Value::Int(v) => Some(*v as f64),
+            Value::Long(v) => Some(*v as f64),
+            Value::Float(v) => Some(*v as f64),
+            Value::Double(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    match (a, b) {
+        (Value::Long(a), Value::Long(b)) => a.cmp(b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => match (as_f64(a), as_f64(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => a.tag_id().cmp(&b.tag_id()),
+        },
+    }
+}
+
+pub fn i8_slice_as_bytes(slice: &[i8]) -> &[u8] {
+    bytemuck::cast_slice(slice)
+}
+
+pub fn bytes_as_i8_slice(slice: &[u8]) -> &[i8] {
+    bytemuck::cast_slice(slice)
+}
+
+pub fn i8_vec_into_bytes(vec: Vec<i8>) -> Vec<u8> {
+    vec.into_iter().map(|b| b as u8).collect()
+}
+
+pub fn bytes_into_i8_vec(vec: Vec<u8>) -> Vec<i8> {
+    vec.into_iter().map(|b| b as i8).collect()
+}
+
+impl<'a> From<Vec<u8>> for Value<'a> {
+    fn from(value: Vec<u8>) -> Self {
+        Value::ByteArray(value)
+    }
+}
+
+impl<'a> From<Vec<i8>> for Value<'a> {
+    fn from(value: Vec<i8>) -> Self {
+        Value::ByteArray(i8_vec_into_bytes(value))
+    }
 }
 
 impl<'a> From<bool> for Value<'a> {
@@ -0,0 +1,49 @@
+use std::io::Read;
+
+use crate::error::{NBTError, Result};
+
+pub(crate) fn zigzag_decode_32(value: u32) -> i32 {
+    ((value >> 1) as i32) ^ -((value & 1) as i32)
+}
+
+pub(crate) fn zigzag_decode_64(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+macro_rules! gen_varint {
+    ($($name:ident: $ty:ty, $max_bytes:expr);* $(;)?) => {
+        $(
+            paste::paste! {
+                pub(crate) fn [<read_ $name>]<R: Read>(reader: &mut R) -> Result<$ty> {
+                    let mut result: $ty = 0;
+
+                    for i in 0..$max_bytes {
+                        let mut buf = [0u8; 1];
+                        reader.read_exact(&mut buf)?;
+
+                        result |= ((buf[0] & 0x7F) as $ty) << (7 * i);
+
+                        if buf[0] & 0x80 == 0 {
+                            return Ok(result);
+                        }
+                    }
+
+                    Err(NBTError::invalid_format())
+                }
+            }
+        )*
+    };
+}
+
+gen_varint!(
+    var_u32: u32, 5;
+    var_u64: u64, 10;
+);
+
+pub(crate) fn read_var_i32<R: Read>(reader: &mut R) -> Result<i32> {
+    Ok(zigzag_decode_32(read_var_u32(reader)?))
+}
+
+pub(crate) fn read_var_i64<R: Read>(reader: &mut R) -> Result<i64> {
+    Ok(zigzag_decode_64(read_var_u64(reader)?))
+}
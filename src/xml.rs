@@ -0,0 +1,456 @@
+use std::{borrow::Cow, fmt::Write, str::FromStr};
+
+use crate::{
+    codec::DEFAULT_MAX_DEPTH,
+    error::{NBTError, Result},
+    tag::{Tag, TagKind},
+    value::{CompoundMap, Value, bytes_as_i8_slice, i8_vec_into_bytes},
+};
+
+fn element_name(tag: Tag) -> &'static str {
+    match tag {
+        Tag::End => "TAG_End",
+        Tag::Byte => "TAG_Byte",
+        Tag::Short => "TAG_Short",
+        Tag::Int => "TAG_Int",
+        Tag::Long => "TAG_Long",
+        Tag::Float => "TAG_Float",
+        Tag::Double => "TAG_Double",
+        Tag::ByteArray => "TAG_Byte_Array",
+        Tag::String => "TAG_String",
+        Tag::List => "TAG_List",
+        Tag::Compound => "TAG_Compound",
+        Tag::IntArray => "TAG_Int_Array",
+        Tag::LongArray => "TAG_Long_Array",
+    }
+}
+
+fn kind_element_name(kind: TagKind) -> &'static str {
+    match kind {
+        TagKind::Known(tag) => element_name(tag),
+        TagKind::Extension(_) => "TAG_Unknown",
+    }
+}
+
+fn tag_from_element_name(name: &str) -> Result<Tag> {
+    (0..=12u8)
+        .filter_map(|id| Tag::try_from(id).ok())
+        .find(|tag| element_name(*tag) == name)
+        .ok_or_else(|| NBTError::invalid_syntax(format!("unknown element <{}>", name)))
+}
+
+fn escape(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn join<T: ToString>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn to_string(name: Option<&str>, value: &Value<'_>) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    write_element(&mut out, name, value, 0);
+    out
+}
+
+fn write_element(out: &mut String, name: Option<&str>, value: &Value<'_>, indent: usize) {
+    let element = kind_element_name(value.tag());
+
+    out.push_str(&"  ".repeat(indent));
+    out.push('<');
+    out.push_str(element);
+
+    if let Some(name) = name {
+        out.push_str(" name=\"");
+        escape(out, name);
+        out.push('"');
+    }
+
+    if let Value::Unknown(id, _) = value {
+        let _ = write!(out, " id=\"{}\"", id);
+    }
+
+    let element_tag = match value {
+        Value::List(list) => Some(list.first().map_or(TagKind::Known(Tag::End), Value::tag)),
+        Value::EmptyList(tag) => Some(TagKind::Known(*tag)),
+        _ => None,
+    };
+    if let Some(element_tag) = element_tag {
+        let _ = write!(out, " type=\"{}\"", kind_element_name(element_tag));
+    }
+
+    let text = match value {
+        Value::End => None,
+        Value::Byte(v) => Some(v.to_string()),
+        Value::Short(v) => Some(v.to_string()),
+        Value::Int(v) => Some(v.to_string()),
+        Value::Long(v) => Some(v.to_string()),
+        Value::Float(v) => Some(v.to_string()),
+        Value::Double(v) => Some(v.to_string()),
+        Value::ByteArray(v) | Value::Unknown(_, v) => Some(join(bytes_as_i8_slice(v))),
+        Value::String(v) => Some(v.to_string()),
+        Value::IntArray(v) => Some(join(v)),
+        Value::LongArray(v) => Some(join(v)),
+        Value::EmptyList(_) => {
+            out.push_str("/>\n");
+            return;
+        }
+        Value::List(list) => {
+            if list.is_empty() {
+                out.push_str("/>\n");
+            } else {
+                out.push_str(">\n");
+                for child in list {
+                    write_element(out, None, child, indent + 1);
+                }
+                close_element(out, element, indent);
+            }
+            return;
+        }
+        Value::Compound(map) => {
+            if map.is_empty() {
+                out.push_str("/>\n");
+            } else {
+                out.push_str(">\n");
+                for (key, child) in map {
+                    write_element(out, Some(key), child, indent + 1);
+                }
+                close_element(out, element, indent);
+            }
+            return;
+        }
+    };
+
+    match text {
+        Some(text) if !text.is_empty() => {
+            out.push('>');
+            escape(out, &text);
+            let _ = writeln!(out, "</{}>", element);
+        }
+        _ => out.push_str("/>\n"),
+    }
+}
+
+fn close_element(out: &mut String, element: &str, indent: usize) {
+    out.push_str(&"  ".repeat(indent));
+    let _ = writeln!(out, "</{}>", element);
+}
+
+pub fn from_str(input: &str) -> Result<(Option<String>, Value<'static>)> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        depth: 0,
+    };
+
+    parser.skip_misc()?;
+    let element = parser.parse_element()?;
+    parser.skip_misc()?;
+
+    if parser.pos != input.len() {
+        return Err(parser.error("trailing content after root element"));
+    }
+
+    let name = element.attribute("name").map(str::to_owned);
+    Ok((name, element.into_value()?))
+}
+
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn parse_text<T: FromStr>(&self) -> Result<T> {
+        self.text.trim().parse().map_err(|_| {
+            NBTError::invalid_syntax(format!("invalid <{}> content {:?}", self.name, self.text))
+        })
+    }
+
+    fn parse_array<T: FromStr>(&self) -> Result<Vec<T>> {
+        self.text
+            .split_whitespace()
+            .map(|item| {
+                item.parse().map_err(|_| {
+                    NBTError::invalid_syntax(format!("invalid <{}> element {:?}", self.name, item))
+                })
+            })
+            .collect()
+    }
+
+    fn into_value(self) -> Result<Value<'static>> {
+        if self.name == kind_element_name(TagKind::Extension(0)) {
+            let id = self
+                .attribute("id")
+                .and_then(|id| id.parse::<u8>().ok())
+                .filter(|&id| Tag::try_from(id).is_err())
+                .ok_or_else(|| NBTError::invalid_syntax("<TAG_Unknown> needs a non-standard id"))?;
+            return Ok(Value::Unknown(id, i8_vec_into_bytes(self.parse_array()?)));
+        }
+
+        let tag = tag_from_element_name(&self.name)?;
+
+        Ok(match tag {
+            Tag::End => Value::End,
+            Tag::Byte => Value::Byte(self.parse_text()?),
+            Tag::Short => Value::Short(self.parse_text()?),
+            Tag::Int => Value::Int(self.parse_text()?),
+            Tag::Long => Value::Long(self.parse_text()?),
+            Tag::Float => Value::Float(self.parse_text()?),
+            Tag::Double => Value::Double(self.parse_text()?),
+            Tag::ByteArray => Value::ByteArray(i8_vec_into_bytes(self.parse_array()?)),
+            Tag::String => Value::String(Cow::Owned(self.text)),
+            Tag::IntArray => Value::IntArray(self.parse_array()?),
+            Tag::LongArray => Value::LongArray(self.parse_array()?),
+            Tag::List => {
+                let element_tag = self.attribute("type").map(tag_from_element_name);
+                let list = self
+                    .children
+                    .into_iter()
+                    .map(Element::into_value)
+                    .collect::<Result<Vec<_>>>()?;
+                match (list.is_empty(), element_tag) {
+                    (true, Some(tag)) => Value::typed_list(tag?, list)?,
+                    _ => Value::List(list),
+                }
+            }
+            Tag::Compound => {
+                let mut map = CompoundMap::new();
+                for child in self.children {
+                    let key = child
+                        .attribute("name")
+                        .ok_or_else(|| {
+                            NBTError::invalid_syntax(format!(
+                                "<{}> inside TAG_Compound has no name",
+                                child.name
+                            ))
+                        })?
+                        .to_owned();
+                    map.insert(Cow::Owned(key), child.into_value()?);
+                }
+                Value::Compound(map)
+            }
+        })
+    }
+}
+
+struct Parser<'s> {
+    input: &'s str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn error(&self, msg: &str) -> NBTError {
+        NBTError::invalid_syntax(format!("{} at byte {}", msg, self.pos))
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn skip_until(&mut self, end: &str) -> Result<()> {
+        match self.rest().find(end) {
+            Some(i) => {
+                self.pos += i + end.len();
+                Ok(())
+            }
+            None => Err(self.error(&format!("expected {:?}", end))),
+        }
+    }
+
+    fn skip_misc(&mut self) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.rest().starts_with("<!") {
+                self.skip_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<()> {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {:?}", s)))
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | ':' | '.')))
+            .unwrap_or(rest.len());
+
+        if len == 0 {
+            return Err(self.error("expected name"));
+        }
+
+        self.pos += len;
+        Ok(rest[..len].to_owned())
+    }
+
+    fn parse_child(&mut self) -> Result<Element> {
+        if self.depth >= DEFAULT_MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let child = self.parse_element();
+        self.depth -= 1;
+        child
+    }
+
+    fn parse_element(&mut self) -> Result<Element> {
+        self.expect("<")?;
+        let name = self.parse_name()?;
+        let mut attributes = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(Element {
+                    name,
+                    attributes,
+                    text: String::new(),
+                    children: Vec::new(),
+                });
+            }
+
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+
+            let key = self.parse_name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+
+            let quote = match self.rest().chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err(self.error("expected quoted attribute value")),
+            };
+            self.pos += 1;
+
+            let end = self
+                .rest()
+                .find(quote)
+                .ok_or_else(|| self.error("unterminated attribute value"))?;
+            let raw = &self.rest()[..end];
+            self.pos += end + 1;
+
+            attributes.push((key, unescape(raw)?));
+        }
+
+        let mut text = String::new();
+        let mut children = Vec::new();
+
+        loop {
+            let rest = self.rest();
+
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let closing = self.parse_name()?;
+                if closing != name {
+                    return Err(self.error(&format!("expected </{}>, found </{}>", name, closing)));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                break;
+            } else if rest.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if rest.starts_with('<') {
+                children.push(self.parse_child()?);
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("unterminated <{}>", name)));
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                text.push_str(&unescape(&rest[..end])?);
+                self.pos += end;
+            }
+        }
+
+        if !children.is_empty() {
+            text.clear();
+        }
+
+        Ok(Element {
+            name,
+            attributes,
+            text,
+            children,
+        })
+    }
+}
+
+fn unescape(raw: &str) -> Result<String> {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = rest
+            .find(';')
+            .ok_or_else(|| NBTError::invalid_syntax(format!("unterminated entity in {:?}", raw)))?;
+        let entity = &rest[1..end];
+
+        let c = match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(|code| code.ok())
+                .and_then(char::from_u32)
+                .ok_or_else(|| NBTError::invalid_syntax(format!("unknown entity &{};", entity)))?,
+        };
+
+        out.push(c);
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
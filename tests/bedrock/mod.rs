@@ -0,0 +1,308 @@
+use bnbt::{
+    bedrock::{
+        actor,
+        legacy::{LegacyChunkStore, LegacyEntities, SECTOR_SIZE},
+        records::{self, DbRecord, LocalPlayer, MapData, MobEvents, Portals, Scoreboard},
+        storage::{ChunkFormat, StorageVersion, SubChunkFormat},
+    },
+    value::Value,
+};
+
+#[test]
+fn digp_and_actor_records() {
+    let key = actor::digp_key(-3, 7, 1);
+    assert_eq!(actor::parse_digp_key(&key), Some((-3, 7, 1)));
+    assert_eq!(
+        actor::parse_digp_key(&actor::digp_key(2, 5, 0)),
+        Some((2, 5, 0))
+    );
+
+    let ids = vec![1, -4294967295, i64::MAX];
+    let digest = actor::encode_digp(&ids);
+    assert_eq!(actor::decode_digp(&digest).unwrap(), ids);
+    assert!(actor::decode_digp(&digest[1..]).is_err());
+
+    let key = actor::actor_key(ids[1]);
+    assert!(key.starts_with(b"actorprefix"));
+    assert_eq!(actor::parse_actor_key(&key), Some(ids[1]));
+
+    let mut entity = Value::compound();
+    entity.insert("identifier", "minecraft:cow").unwrap();
+    entity.insert("UniqueID", ids[1]).unwrap();
+
+    let record = actor::write_actor(&entity).unwrap();
+    assert_eq!(actor::read_actor(&record).unwrap(), entity);
+}
+
+#[test]
+fn typed_db_records_keep_unknown_fields() {
+    let mut record = Value::compound();
+    record.insert("DimId", 1).unwrap();
+    record.insert("Span", Value::Byte(2)).unwrap();
+    record.insert("TpX", 10).unwrap();
+    record.insert("TpY", 64).unwrap();
+    record.insert("TpZ", -20).unwrap();
+    record.insert("Xa", Value::Byte(1)).unwrap();
+    record.insert("Za", Value::Byte(0)).unwrap();
+    record.insert("Future", "kept").unwrap();
+
+    let mut data = Value::compound();
+    data.insert("PortalRecords", Value::list_from_iter([record]))
+        .unwrap();
+    let mut root = Value::compound();
+    root.insert("data", data).unwrap();
+
+    let bytes = actor::write_actor(&root).unwrap();
+    let portals = Portals::decode(&bytes).unwrap();
+    let records = portals.records.as_deref().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].y, 64);
+    assert_eq!(records[0].extra.get("Future"), Some(&Value::from("kept")));
+    assert_eq!(portals.to_value(), root);
+    for untouched in [Value::compound(), {
+        let mut root = Value::compound();
+        root.insert("data", Value::compound()).unwrap();
+        root
+    }] {
+        let portals = Portals::from_value(untouched.clone()).unwrap();
+        assert_eq!(portals.records, None);
+        assert_eq!(portals.to_value(), untouched);
+    }
+
+    let mut events = Value::compound();
+    events.insert("events_enabled", true).unwrap();
+    events
+        .insert("minecraft:ender_dragon_event", false)
+        .unwrap();
+    let events = MobEvents::from_value(events).unwrap();
+    assert_eq!(events.events_enabled, Some(true));
+    assert_eq!(events.ender_dragon, Some(false));
+    assert_eq!(events.pillager_patrols, None);
+    assert_eq!(
+        MobEvents::decode(&events.encode().unwrap()).unwrap(),
+        events
+    );
+
+    let mut map = Value::compound();
+    map.insert("mapId", -12i64).unwrap();
+    map.insert("dimension", Value::Byte(0)).unwrap();
+    map.insert("scale", Value::Byte(2)).unwrap();
+    map.insert("width", 128i16).unwrap();
+    map.insert("height", 128i16).unwrap();
+    map.insert("xCenter", 64).unwrap();
+    map.insert("zCenter", -64).unwrap();
+    let record = MapData::from_value(map.clone()).unwrap();
+    assert_eq!(record.parent_map_id, None);
+    assert_eq!(record.colors, None);
+    assert_eq!(record.to_value(), map);
+
+    let mut scoreboard = Value::compound();
+    for key in ["Objectives", "Entries", "Criteria", "DisplayObjectives"] {
+        scoreboard.insert(key, Value::list(0)).unwrap();
+    }
+    assert_eq!(
+        Scoreboard::from_value(scoreboard.clone())
+            .unwrap()
+            .to_value(),
+        scoreboard
+    );
+    let mut partial = Value::compound();
+    partial.insert("LastUniqueID", 3i64).unwrap();
+    let record = Scoreboard::from_value(partial.clone()).unwrap();
+    assert_eq!(record.objectives, None);
+    assert_eq!(record.to_value(), partial);
+
+    assert_eq!(records::parse_map_key(&records::map_key(-12)), Some(-12));
+    assert!(records::parse_map_key(b"map_x").is_none());
+
+    let mut player = Value::compound();
+    player.insert("UniqueID", 5i64).unwrap();
+    assert!(LocalPlayer::from_value(player.clone()).is_err());
+    player
+        .insert(
+            "Pos",
+            Value::List([0.0, 64.0, 0.0].map(Value::Float).to_vec()),
+        )
+        .unwrap();
+    player
+        .insert(
+            "Rotation",
+            Value::List([0.0, 0.0].map(Value::Float).to_vec()),
+        )
+        .unwrap();
+    let local = LocalPlayer::from_value(player.clone()).unwrap();
+    assert_eq!(local.dimension_id, None);
+    assert_eq!(local.to_value(), player);
+}
+
+#[test]
+fn storage_version_selects_chunk_format() {
+    let path = std::env::current_dir()
+        .unwrap()
+        .join("tests/resources/level.dat");
+    let file = bnbt::file::NbtFile::open(path).unwrap();
+    let version = StorageVersion::from_file(&file).unwrap();
+    assert!(version.0 > 0);
+
+    assert_eq!(StorageVersion(3).chunk_format(), ChunkFormat::LegacyTerrain);
+    assert_eq!(StorageVersion(3).subchunk_format(), None);
+    assert_eq!(
+        StorageVersion(6).subchunk_format(),
+        Some(SubChunkFormat::Legacy)
+    );
+    assert_eq!(StorageVersion(10).chunk_format(), ChunkFormat::Palettized);
+    assert!(StorageVersion(10).has_negative_subchunks());
+
+    assert_eq!(
+        SubChunkFormat::from_record(&[9, 2, 0xfc]).unwrap(),
+        SubChunkFormat::MultiLayerIndexed
+    );
+    assert!(SubChunkFormat::from_record(&[]).is_err());
+    assert!(SubChunkFormat::from_version(42).is_none());
+    assert!(StorageVersion::from_level_dat(&Value::compound()).is_err());
+}
+
+#[test]
+fn legacy_entities_dat_round_trips() {
+    let mut zombie = Value::compound();
+    zombie.insert("id", Value::Int(32)).unwrap();
+    let mut root = Value::compound();
+    root.insert("Entities", Value::list_from_iter(vec![zombie.clone()]))
+        .unwrap();
+    root.insert("TileEntities", Value::List(Vec::new()))
+        .unwrap();
+
+    let entities = LegacyEntities {
+        version: 1,
+        value: root,
+    };
+    let bytes = entities.to_bytes().unwrap();
+    assert_eq!(&bytes[..4], b"ENT\0");
+
+    let parsed = LegacyEntities::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed, entities);
+    assert_eq!(parsed.entities(), [zombie]);
+    assert!(parsed.tile_entities().is_empty());
+    assert!(LegacyEntities::from_bytes(&bytes[4..]).is_err());
+}
+
+#[test]
+fn legacy_chunks_dat_reads_located_chunks() {
+    let chunk_len = 16 * 16 * 128 * 5 / 2 + 256;
+    let mut data = vec![0u8; SECTOR_SIZE * 2 + chunk_len + 4];
+
+    let (x, z) = (1, 2);
+    let entry = (1u32 << 8) | 21;
+    data[(x + z * 32) * 4..][..4].copy_from_slice(&entry.to_le_bytes());
+    data[SECTOR_SIZE..][..4].copy_from_slice(&((chunk_len + 4) as u32).to_le_bytes());
+
+    let payload = SECTOR_SIZE + 4;
+    data[payload + (3 * 16 + 4) * 128 + 60] = 7;
+    data[payload + 32768 + ((3 * 16 + 4) * 128 + 61) / 2] = 0x50;
+
+    let store = LegacyChunkStore::new(&data).unwrap();
+    assert!(store.location(0, 0).is_none());
+    assert!(store.chunk(0, 0).unwrap().is_none());
+    assert_eq!(store.location(x, z).unwrap().sectors, 21);
+
+    let chunk = store.chunk(x, z).unwrap().unwrap();
+    assert_eq!(chunk.block_id(3, 60, 4), Some(7));
+    assert_eq!(chunk.block_data(3, 61, 4), Some(5));
+    assert_eq!(chunk.block_data(3, 60, 4), Some(0));
+    assert_eq!(chunk.block_id(0, 128, 0), None);
+    assert_eq!(chunk.block_id(16, 0, 0), None);
+    assert_eq!(chunk.sky_light(0, 0, 16), None);
+    assert_eq!(chunk.block_light(15, 127, 15), Some(0));
+    assert_eq!(chunk.dirty_columns.len(), 256);
+}
+
+#[cfg(feature = "mcworld")]
+#[test]
+fn mcworld_archive_exposes_level_dat_and_db() {
+    use std::io::{Cursor, Write};
+
+    use bnbt::{
+        bedrock::archive::WorldArchive,
+        codec::Endian,
+        file::{BedrockHeader, NbtFile},
+    };
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    let mut level = Value::compound();
+    level.insert("LevelName", "Archive World").unwrap();
+    let mut file = NbtFile::new(level.clone(), Endian::Little);
+    file.header = Some(BedrockHeader { version: 10 });
+    let level_dat = file.to_bytes().unwrap();
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for (name, bytes) in [
+        ("world/level.dat", level_dat.as_slice()),
+        ("world/levelname.txt", b"Archive World\n".as_slice()),
+        ("world/db/CURRENT", b"MANIFEST-000001\n".as_slice()),
+        ("world/db/000003.log", b"\x01\x02".as_slice()),
+    ] {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(bytes).unwrap();
+    }
+    let bytes = zip.finish().unwrap().into_inner();
+
+    let mut archive = WorldArchive::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(archive.root(), "world/");
+    assert_eq!(archive.level_dat().unwrap().value, level);
+    assert_eq!(
+        archive.level_name().unwrap().as_deref(),
+        Some("Archive World")
+    );
+    assert_eq!(archive.db_files(), ["000003.log", "CURRENT"]);
+    assert_eq!(archive.read_db_file("000003.log").unwrap(), [1, 2]);
+
+    let dir = tempfile::tempdir().unwrap();
+    archive.extract_db(dir.path()).unwrap();
+    assert_eq!(
+        std::fs::read(dir.path().join("CURRENT")).unwrap(),
+        b"MANIFEST-000001\n"
+    );
+
+    let archive_with = |entries: &[(&str, &[u8])]| {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, bytes) in entries {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(bytes).unwrap();
+        }
+        WorldArchive::from_reader(Cursor::new(zip.finish().unwrap().into_inner())).unwrap()
+    };
+    let mut unnamed = archive_with(&[("level.dat", level_dat.as_slice())]);
+    assert_eq!(unnamed.level_name().unwrap(), None);
+    let mut garbled = archive_with(&[
+        ("level.dat", level_dat.as_slice()),
+        ("levelname.txt", b"\xff\xfe".as_slice()),
+    ]);
+    assert!(garbled.level_name().is_err());
+}
+
+#[test]
+fn find_entity_scans_actor_and_digp_records() {
+    let actor_record = |unique_id: i64| {
+        let mut actor = Value::compound();
+        actor.insert("identifier", "minecraft:cow").unwrap();
+        actor.insert("UniqueID", Value::Long(unique_id)).unwrap();
+        actor::write_actor(&actor).unwrap()
+    };
+
+    let records = vec![
+        (actor::actor_key(10), actor_record(-5)),
+        (actor::actor_key(11), actor_record(42)),
+        (actor::digp_key(3, -2, 1), actor::encode_digp(&[10, 11])),
+    ];
+
+    let found = actor::find_entity(records.clone(), 42).unwrap().unwrap();
+    assert_eq!(found.chunk, Some((3, -2, 1)));
+    assert_eq!(found.key, actor::actor_key(11));
+
+    let orphan = actor::find_entity(records[..2].to_vec(), -5)
+        .unwrap()
+        .unwrap();
+    assert_eq!(orphan.chunk, None);
+    assert!(actor::find_entity(records, 7).unwrap().is_none());
+}
@@ -0,0 +1,105 @@
+use std::process::Command;
+
+use bnbt::{codec::Endian, file::NbtFile, value::Value};
+
+fn bnbt(args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_bnbt"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+fn level_dat(dir: &tempfile::TempDir) -> String {
+    let mut data = Value::compound();
+    data.insert("RandomSeed", Value::Long(-42)).unwrap();
+    data.insert(
+        "Players",
+        Value::list_from_iter(vec![Value::from("Alex"), Value::from("Steve")]),
+    )
+    .unwrap();
+    let mut root = Value::compound();
+    root.insert("Data", data).unwrap();
+
+    let path = dir.path().join("level.dat");
+    NbtFile::new(root, Endian::Big).save(&path).unwrap();
+    path.to_str().unwrap().to_owned()
+}
+
+#[test]
+fn get_prints_matches_as_snbt_or_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = level_dat(&dir);
+
+    assert_eq!(
+        bnbt(&["get", &file, "Data.RandomSeed"]),
+        (0, "-42L\n".into())
+    );
+    assert_eq!(
+        bnbt(&["get", &file, "Data.Players[*]"]),
+        (0, "\"Alex\"\n\"Steve\"\n".into())
+    );
+    assert_eq!(
+        bnbt(&["get", &file, "Data.Players", "--json"]),
+        (0, "[\"Alex\",\"Steve\"]\n".into())
+    );
+
+    assert_eq!(bnbt(&["get", &file, "Data.Missing"]).0, 1);
+    assert_eq!(bnbt(&["get", &file]).0, 2);
+    assert_eq!(
+        bnbt(&["get", "--json", &file, "Data.RandomSeed"]).1,
+        "-42\n"
+    );
+    assert_eq!(
+        bnbt(&["--json", "get", &file, "Data.RandomSeed"]),
+        (0, "-42\n".into())
+    );
+    assert_eq!(bnbt(&["get", &file, "Data.RandomSeed", "--yaml"]).0, 2);
+    assert_eq!(bnbt(&["delete", &file, "Data.Players", "--json"]).0, 2);
+}
+
+#[test]
+fn set_and_delete_edit_the_file_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = level_dat(&dir);
+
+    assert_eq!(bnbt(&["set", &file, "Data.RandomSeed", "7L"]).0, 0);
+    assert_eq!(bnbt(&["set", &file, "Data.Spawn", "{x:1,y:64,z:-3}"]).0, 0);
+    assert_eq!(bnbt(&["delete", &file, "Data.Players[0]"]).0, 0);
+    assert_eq!(bnbt(&["set", &file, "--", "Data.Motto", "--yes"]).0, 0);
+
+    let nbt = NbtFile::open(&file).unwrap();
+    assert_eq!(nbt.endian, Endian::Big);
+    let data = nbt.value.get("Data").unwrap();
+    assert_eq!(data.get("RandomSeed"), Some(&Value::Long(7)));
+    assert_eq!(data.get("Motto"), Some(&Value::from("--yes")));
+    assert_eq!(
+        data.get("Spawn").and_then(|spawn| spawn.get("y")),
+        Some(&Value::Int(64))
+    );
+    assert_eq!(
+        data.get("Players"),
+        Some(&Value::list_from_iter(vec![Value::from("Steve")]))
+    );
+
+    assert_eq!(bnbt(&["delete", &file, "Data.Missing"]).0, 1);
+    assert_eq!(bnbt(&["set", &file, "Data.Bad", "[1,2b]"]).0, 1);
+}
+
+#[test]
+fn codegen_prints_structs_for_sample_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = level_dat(&dir);
+
+    let (code, out) = bnbt(&["codegen", "level", &file]);
+    assert_eq!(code, 0);
+    assert!(
+        out.contains("pub struct Level {\n    #[serde(rename = \"Data\")]\n    pub data: Data,\n}")
+    );
+    assert!(out.contains("    pub random_seed: i64,\n"));
+
+    assert_eq!(bnbt(&["codegen", "level"]).0, 2);
+}
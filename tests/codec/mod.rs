@@ -0,0 +1,1212 @@
+use bnbt::codec::{NBTCodec, NBTCodecTrait};
+
+#[test]
+fn long_array_chunked_read() {
+    let codec = NBTCodec::big_endian();
+    let values: Vec<i64> = (0..2000).collect();
+
+    let mut buf = Vec::new();
+    codec.write_long_array(&mut buf, &values).unwrap();
+
+    let mut chunks = 0;
+    let mut sum = 0;
+    let size = codec
+        .read_long_array_chunked(&mut buf.as_slice(), |chunk| {
+            chunks += 1;
+            sum += chunk.iter().sum::<i64>();
+        })
+        .unwrap();
+
+    assert_eq!(size, 2000);
+    assert_eq!(chunks, 4);
+    assert_eq!(sum, values.iter().sum::<i64>());
+}
+
+#[test]
+fn limited_reader_rejects_oversized_payload() {
+    use bnbt::{error::NBTErrorKind, io::LimitedReader, value::Value};
+
+    let codec = NBTCodec::little_endian();
+    let mut root = Value::compound();
+    root.insert("data", Value::from(vec![0u8; 256])).unwrap();
+
+    let mut buf = Vec::new();
+    codec.write_tag(&mut buf, None, &root).unwrap();
+
+    let mut reader = LimitedReader::new(buf.as_slice(), 64);
+    let err = codec.read_tag(&mut reader).unwrap_err();
+    assert!(matches!(err.kind, NBTErrorKind::PayloadTooLarge(64)));
+
+    let mut reader = LimitedReader::new(buf.as_slice(), buf.len() as u64);
+    assert!(codec.read_tag(&mut reader).is_ok());
+}
+
+#[test]
+fn counting_reader_reports_consumed_bytes() {
+    use bnbt::{io::CountingReader, value::Value};
+    use std::io::BufReader;
+
+    let codec = NBTCodec::little_endian();
+    let mut root = Value::compound();
+    root.insert("name", "counted").unwrap();
+
+    let mut buf = Vec::new();
+    codec.write_tag(&mut buf, None, &root).unwrap();
+    let nbt_len = buf.len() as u64;
+    buf.extend_from_slice(b"trailing record data");
+
+    let mut reader = CountingReader::new(buf.as_slice());
+    assert_eq!(codec.read_tag(&mut reader).unwrap().1, root);
+    assert_eq!(reader.position(), nbt_len);
+
+    let mut reader = CountingReader::new(BufReader::with_capacity(4, buf.as_slice()));
+    assert_eq!(codec.read_tag_buffered(&mut reader).unwrap().1, root);
+    assert_eq!(reader.position(), nbt_len);
+}
+
+#[test]
+fn progress_hook_reports_bytes_and_tags() {
+    use bnbt::{codec::ProgressHook, value::Value};
+    use std::sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    };
+
+    #[derive(Default)]
+    struct Counter {
+        bytes: AtomicU64,
+        tags: AtomicU64,
+    }
+
+    impl ProgressHook for Counter {
+        fn on_bytes_read(&self, total: u64) {
+            self.bytes.store(total, Ordering::Relaxed);
+        }
+
+        fn on_tag(&self, total: u64) {
+            self.tags.store(total, Ordering::Relaxed);
+        }
+    }
+
+    let mut root = Value::compound();
+    root.insert("a", 1).unwrap();
+    root.insert("b", Value::list_from_iter(vec![1i64, 2, 3]))
+        .unwrap();
+
+    let mut buf = Vec::new();
+    NBTCodec::big_endian()
+        .write_tag(&mut buf, None, &root)
+        .unwrap();
+
+    let counter = Arc::new(Counter::default());
+    let codec = NBTCodec::big_endian().with_progress(counter.clone());
+    codec.read_tag(&mut buf.as_slice()).unwrap();
+
+    assert_eq!(counter.bytes.load(Ordering::Relaxed), buf.len() as u64);
+    assert_eq!(counter.tags.load(Ordering::Relaxed), 6);
+}
+
+#[test]
+fn cancelled_decode_returns_cancelled() {
+    use bnbt::{error::NBTErrorKind, value::Value};
+    use std::sync::{Arc, atomic::AtomicBool};
+
+    let mut buf = Vec::new();
+    NBTCodec::big_endian()
+        .write_tag(&mut buf, None, &Value::compound())
+        .unwrap();
+
+    let token = Arc::new(AtomicBool::new(true));
+    let codec = NBTCodec::big_endian().with_cancellation(token);
+    let err = codec.read_tag(&mut buf.as_slice()).unwrap_err();
+    assert!(matches!(err.kind, NBTErrorKind::Cancelled));
+}
+
+#[test]
+fn stats_collected_alongside_value() {
+    use bnbt::{tag::Tag, value::Value};
+
+    let mut inner = Value::compound();
+    inner
+        .insert("heights", Value::LongArray(vec![1, 2, 3]))
+        .unwrap();
+
+    let mut root = Value::compound();
+    root.insert("name", "abc").unwrap();
+    root.insert("inner", inner).unwrap();
+
+    let codec = NBTCodec::big_endian();
+    let mut buf = Vec::new();
+    codec.write_tag(&mut buf, None, &root).unwrap();
+
+    let (_, value, stats) = codec.read_tag_with_stats(&mut buf.as_slice()).unwrap();
+    assert_eq!(value, root);
+    assert_eq!(stats.count(Tag::Compound), 2);
+    assert_eq!(stats.count(Tag::LongArray), 1);
+    assert_eq!(stats.total_tags(), 4);
+    assert_eq!(stats.max_depth, 2);
+    assert_eq!(stats.largest_long_array, 3);
+    assert_eq!(
+        stats.string_bytes,
+        ("abc".len() + "name".len() + "inner".len() + "heights".len()) as u64
+    );
+}
+
+#[test]
+fn write_list_as_keeps_element_tag_for_empty_lists() {
+    use bnbt::{tag::Tag, value::Value};
+
+    let codec = NBTCodec::big_endian();
+
+    let mut buf = Vec::new();
+    codec.write_list_as(&mut buf, &[], Tag::Compound).unwrap();
+    assert_eq!(buf, [Tag::Compound as u8, 0, 0, 0, 0]);
+
+    let ints = [Value::Int(1), Value::Int(2)];
+    assert!(
+        codec
+            .write_list_as(&mut Vec::new(), &ints, Tag::Long)
+            .is_err()
+    );
+
+    let list = Value::typed_list(Tag::Int, ints.to_vec()).unwrap();
+    assert_eq!(list.list_tag(), Some(Tag::Int.into()));
+    assert!(Value::typed_list(Tag::Short, ints.to_vec()).is_err());
+
+    let empty = Value::typed_list(Tag::Compound, Vec::new()).unwrap();
+    assert_eq!(empty.list_tag(), Some(Tag::Compound.into()));
+    assert_eq!(empty.as_list().map(<[_]>::len), Some(0));
+
+    let mut root = Value::compound();
+    root.insert("Passengers", empty.clone()).unwrap();
+    let bytes = codec.to_vec_exact(None, &root).unwrap();
+    let list_header = [Tag::Compound as u8, 0, 0, 0, 0];
+    assert!(bytes.windows(5).any(|w| w == list_header));
+
+    let mut vectored = Vec::new();
+    codec
+        .write_tag_vectored(&mut vectored, None, &root)
+        .unwrap();
+    assert_eq!(vectored, bytes);
+
+    let mut pushed = empty;
+    pushed.push(Value::compound()).unwrap();
+    assert_eq!(pushed.as_list().map(<[_]>::len), Some(1));
+}
+
+#[test]
+fn long_string_policies() {
+    use bnbt::{
+        codec::{MAX_STRING_LEN, ProgressHook, StringPolicy},
+        error::NBTErrorKind,
+        value::Value,
+    };
+    use std::sync::{Arc, Mutex};
+
+    let long = "é".repeat(MAX_STRING_LEN / 2 + 10);
+    let mut root = Value::compound();
+    root.insert("text", long.clone()).unwrap();
+
+    let codec = NBTCodec::big_endian();
+    let err = codec.write_tag(&mut Vec::new(), None, &root).unwrap_err();
+    assert!(matches!(err.kind, NBTErrorKind::StringTooLong(len) if len == long.len()));
+
+    struct Warnings(Mutex<Vec<String>>);
+
+    impl ProgressHook for Warnings {
+        fn on_warning(&self, message: &str) {
+            self.0.lock().unwrap().push(message.to_owned());
+        }
+    }
+
+    let warnings = Arc::new(Warnings(Mutex::new(Vec::new())));
+    let codec = NBTCodec::big_endian()
+        .with_string_policy(StringPolicy::Truncate)
+        .with_progress(warnings.clone());
+    let mut buf = Vec::new();
+    codec.write_tag(&mut buf, None, &root).unwrap();
+    assert_eq!(warnings.0.lock().unwrap().len(), 1);
+    let (_, value) = codec.read_tag(&mut buf.as_slice()).unwrap();
+    let Some(Value::String(text)) = value.get("text") else {
+        panic!("missing text");
+    };
+    assert_eq!(text.len(), MAX_STRING_LEN - 1);
+    assert!(long.starts_with(text.as_ref()));
+
+    let codec = NBTCodec::big_endian().with_string_policy(StringPolicy::Chunk);
+    let mut buf = Vec::new();
+    codec.write_tag(&mut buf, None, &root).unwrap();
+    let (_, value) = codec.read_tag(&mut buf.as_slice()).unwrap();
+    let chunks = value.get("text").unwrap().as_list().unwrap();
+    assert_eq!(chunks.len(), 2);
+    let joined: String = chunks
+        .iter()
+        .map(|chunk| match chunk {
+            Value::String(s) => s.as_ref(),
+            _ => panic!("chunk is not a string"),
+        })
+        .collect();
+    assert_eq!(joined, long);
+
+    let list = Value::list_from_iter(vec![long]);
+    assert!(codec.write_tag(&mut Vec::new(), None, &list).is_err());
+}
+
+#[test]
+fn repeated_reads_reuse_codec_buffers() {
+    use bnbt::value::Value;
+
+    let codec = NBTCodec::little_endian();
+    let mut root = Value::compound();
+    root.insert("name", "chunk").unwrap();
+    root.insert("ints", Value::IntArray((0..20_000).collect()))
+        .unwrap();
+    root.insert("longs", Value::LongArray(vec![i64::MIN, -1, 0, i64::MAX]))
+        .unwrap();
+
+    let mut buf = Vec::new();
+    codec.write_tag(&mut buf, None, &root).unwrap();
+
+    let session = codec.session();
+    for _ in 0..3 {
+        let (_, value) = session.read_tag(&mut buf.as_slice()).unwrap();
+        assert_eq!(value, root);
+    }
+    session.release_buffers();
+
+    let invalid = [0x08, 0x00, 0x00, 0x02, 0x00, 0xff, 0xfe];
+    assert!(session.read_tag(&mut invalid.as_slice()).is_err());
+}
+
+#[test]
+fn configured_codecs_are_shared_across_threads() {
+    use bnbt::{
+        codec::{ConfiguredCodec, Endian},
+        value::Value,
+    };
+
+    fn assert_sync<T: Send + Sync>(_: &T) {}
+
+    let plain = NBTCodec {
+        endian: Endian::Little,
+    };
+    let codec: ConfiguredCodec = plain.with_max_total_tags(16);
+    assert_sync(&plain);
+    assert_sync(&codec);
+
+    let mut root = Value::compound();
+    root.insert("name", "shared").unwrap();
+    let buf = codec.to_vec_exact(None, &root).unwrap();
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let (_, value) = codec.read_tag(&mut buf.as_slice()).unwrap();
+                assert_eq!(value, root);
+            });
+        }
+    });
+}
+
+#[test]
+fn read_tag_into_reuses_existing_value() {
+    use bnbt::value::Value;
+
+    let codec = NBTCodec::big_endian();
+    let mut first = Value::compound();
+    first.insert("name", "first").unwrap();
+    first.insert("stale", 1i32).unwrap();
+    first
+        .insert("list", Value::list_from_iter(vec![1i32, 2, 3]))
+        .unwrap();
+    first.insert("ints", Value::IntArray(vec![1; 64])).unwrap();
+
+    let mut second = Value::compound();
+    second.insert("name", "second").unwrap();
+    second
+        .insert("list", Value::list_from_iter(vec![9i32]))
+        .unwrap();
+    second.insert("ints", Value::IntArray(vec![7, 8])).unwrap();
+    second
+        .insert("bytes", Value::ByteArray(vec![1, 2, 3]))
+        .unwrap();
+
+    let mut target = Value::End;
+    for expected in [&first, &second, &first] {
+        let mut buf = Vec::new();
+        codec
+            .write_tag(&mut buf, Some("root".into()), expected)
+            .unwrap();
+
+        let name = codec
+            .read_tag_into(&mut buf.as_slice(), &mut target)
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("root"));
+        assert_eq!(&target, expected);
+    }
+
+    let mut buf = Vec::new();
+    codec.write_tag(&mut buf, None, &Value::Int(5)).unwrap();
+    codec
+        .read_tag_into(&mut buf.as_slice(), &mut target)
+        .unwrap();
+    assert_eq!(target, Value::Int(5));
+}
+
+#[test]
+fn to_vec_exact_matches_streamed_encoding() {
+    use bnbt::{codec::StringPolicy, generate::Generator, value::Value};
+
+    let codec = NBTCodec::little_endian();
+    let mut generator = Generator::new(7);
+    for _ in 0..8 {
+        let value = generator.compound();
+        let mut streamed = Vec::new();
+        codec
+            .write_tag(&mut streamed, Some("root".into()), &value)
+            .unwrap();
+
+        let exact = codec.to_vec_exact(Some("root"), &value).unwrap();
+        assert_eq!(codec.encoded_len(Some("root"), &value), exact.len());
+        assert_eq!(exact, streamed);
+    }
+
+    let long = "é".repeat(40_000);
+    for policy in [StringPolicy::Truncate, StringPolicy::Chunk] {
+        let codec = NBTCodec::big_endian().with_string_policy(policy);
+        let mut root = Value::compound();
+        root.insert("text", long.as_str()).unwrap();
+
+        let exact = codec.to_vec_exact(None, &root).unwrap();
+        assert_eq!(codec.encoded_len(None, &root), exact.len());
+    }
+}
+
+#[test]
+fn buffered_reads_match_generic_path() {
+    use bnbt::generate::Generator;
+    use std::io::BufReader;
+
+    let mut generator = Generator::new(11);
+    for codec in [NBTCodec::big_endian(), NBTCodec::little_endian()] {
+        for _ in 0..4 {
+            let value = generator.compound();
+            let buf = codec.to_vec_exact(Some("root"), &value).unwrap();
+
+            for capacity in [1, 7, 64, 1 << 16] {
+                let mut reader = BufReader::with_capacity(capacity, buf.as_slice());
+                let (name, decoded) = codec.read_tag_buffered(&mut reader).unwrap();
+                assert_eq!(name.as_deref(), Some("root"));
+                assert_eq!(decoded, value);
+            }
+        }
+
+        let value = generator.compound();
+        let buf = codec.to_vec_exact(None, &value).unwrap();
+        let mut truncated = BufReader::with_capacity(5, &buf[..buf.len() - 3]);
+        assert!(codec.read_tag_buffered(&mut truncated).is_err());
+    }
+}
+
+#[test]
+fn vectored_write_matches_plain_encoding() {
+    use bnbt::value::Value;
+    use std::io::{IoSlice, Write};
+
+    struct Trickle {
+        out: Vec<u8>,
+        calls: usize,
+    }
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.write_vectored(&[IoSlice::new(buf)])
+        }
+
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+            self.calls += 1;
+            let mut budget = 3000;
+            for buf in bufs {
+                let take = buf.len().min(budget);
+                self.out.extend_from_slice(&buf[..take]);
+                budget -= take;
+                if budget == 0 {
+                    break;
+                }
+            }
+            Ok(3000 - budget)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    for codec in [NBTCodec::big_endian(), NBTCodec::little_endian()] {
+        let mut root = Value::compound();
+        root.insert("bytes", Value::ByteArray(vec![7; 5000]))
+            .unwrap();
+        root.insert("ints", Value::IntArray((0..2000).collect()))
+            .unwrap();
+        root.insert(
+            "sections",
+            Value::list_from_iter(vec![
+                Value::LongArray((0..300).map(|i| i * -3).collect()),
+                Value::LongArray(vec![1, 2, 3]),
+            ]),
+        )
+        .unwrap();
+        root.insert("name", "vectored").unwrap();
+
+        let mut writer = Trickle {
+            out: Vec::new(),
+            calls: 0,
+        };
+        codec
+            .write_tag_vectored(&mut writer, Some("root"), &root)
+            .unwrap();
+
+        assert_eq!(writer.out, codec.to_vec_exact(Some("root"), &root).unwrap());
+        assert!(writer.calls > 1);
+    }
+}
+
+#[test]
+fn compound_entry_limit_stops_runaway_compounds() {
+    use bnbt::{error::NBTErrorKind, value::Value};
+    use std::io::BufReader;
+
+    let codec = NBTCodec::big_endian().with_max_compound_entries(16);
+
+    let mut hostile = vec![10, 0, 0];
+    for i in 0..1000u16 {
+        hostile.extend_from_slice(&[1, 0, 2]);
+        hostile.extend_from_slice(&i.to_be_bytes());
+        hostile.push(0);
+    }
+
+    let err = codec.read_tag(&mut hostile.as_slice()).unwrap_err();
+    assert!(matches!(err.kind, NBTErrorKind::TooManyEntries(16)));
+
+    let mut reader = BufReader::new(hostile.as_slice());
+    let err = codec.read_tag_buffered(&mut reader).unwrap_err();
+    assert!(matches!(err.kind, NBTErrorKind::TooManyEntries(16)));
+
+    let mut target = Value::End;
+    let err = codec
+        .read_tag_into(&mut hostile.as_slice(), &mut target)
+        .unwrap_err();
+    assert!(matches!(err.kind, NBTErrorKind::TooManyEntries(16)));
+
+    let mut root = Value::compound();
+    for i in 0..16 {
+        root.insert(i.to_string(), i).unwrap();
+    }
+    let buf = codec.to_vec_exact(None, &root).unwrap();
+    assert_eq!(codec.read_tag(&mut buf.as_slice()).unwrap().1, root);
+}
+
+#[test]
+fn total_tag_limit_bounds_whole_document() {
+    use bnbt::{error::NBTErrorKind, value::Value};
+
+    let mut root = Value::compound();
+    root.insert("a", Value::list_from_iter(vec![1i32, 2, 3]))
+        .unwrap();
+    root.insert("b", Value::list_from_iter(vec![4i32, 5, 6]))
+        .unwrap();
+
+    let buf = NBTCodec::big_endian().to_vec_exact(None, &root).unwrap();
+
+    let codec = NBTCodec::big_endian().with_max_total_tags(9);
+    assert_eq!(codec.read_tag(&mut buf.as_slice()).unwrap().1, root);
+
+    let codec = NBTCodec::big_endian().with_max_total_tags(8);
+    let err = codec.read_tag(&mut buf.as_slice()).unwrap_err();
+    assert!(matches!(err.kind, NBTErrorKind::TooManyTags(8)));
+    assert!(codec.read_tag(&mut buf.as_slice()).is_err());
+}
+
+#[test]
+fn truncated_input_reports_eof_context() {
+    use bnbt::{error::NBTErrorKind, tag::Tag, value::Value};
+    use std::io::BufReader;
+
+    let codec = NBTCodec::big_endian();
+    let mut root = Value::compound();
+    root.insert("ints", Value::IntArray(vec![1, 2, 3, 4]))
+        .unwrap();
+    let buf = codec.to_vec_exact(None, &root).unwrap();
+    let truncated = &buf[..buf.len() - 6];
+
+    let expect_eof = |kind: NBTErrorKind, expected: usize| match kind {
+        NBTErrorKind::UnexpectedEOF {
+            tag: Some(Tag::IntArray),
+            expected: Some(e),
+            offset: Some(offset),
+        } => {
+            assert_eq!(e, expected);
+            assert!(offset <= truncated.len() as u64);
+        }
+        other => panic!("unexpected error kind {:?}", other),
+    };
+
+    expect_eof(codec.read_tag(&mut &truncated[..]).unwrap_err().kind, 16);
+
+    let mut reader = BufReader::new(truncated);
+    expect_eof(codec.read_tag_buffered(&mut reader).unwrap_err().kind, 16);
+
+    let mut target = Value::End;
+    expect_eof(
+        codec
+            .read_tag_into(&mut &truncated[..], &mut target)
+            .unwrap_err()
+            .kind,
+        16,
+    );
+}
+
+#[test]
+fn oversized_array_lengths_fail_without_preallocating() {
+    use bnbt::{error::NBTErrorKind, tag::Tag, value::Value};
+    use std::io::BufReader;
+
+    let mut buf = vec![Tag::ByteArray as u8, 0, 0];
+    buf.extend_from_slice(&u32::MAX.to_be_bytes());
+    buf.extend_from_slice(&[1, 2, 3]);
+
+    let codec = NBTCodec::big_endian();
+    let is_eof = |kind: NBTErrorKind| {
+        matches!(
+            kind,
+            NBTErrorKind::UnexpectedEOF {
+                tag: Some(Tag::ByteArray),
+                ..
+            }
+        )
+    };
+
+    assert!(is_eof(
+        codec.read_tag(&mut buf.as_slice()).unwrap_err().kind
+    ));
+    let mut reader = BufReader::new(buf.as_slice());
+    assert!(is_eof(
+        codec.read_tag_buffered(&mut reader).unwrap_err().kind
+    ));
+    let mut target = Value::End;
+    assert!(is_eof(
+        codec
+            .read_tag_into(&mut buf.as_slice(), &mut target)
+            .unwrap_err()
+            .kind
+    ));
+}
+
+#[test]
+fn escaped_key_encoding_round_trips_invalid_utf8() {
+    use bnbt::{
+        codec::{KeyEncoding, escape_key, key_bytes},
+        value::Value,
+    };
+
+    let bytes = [10, 0, 0, 1, 2, 0, 0xFF, b'a', 5, 0];
+
+    assert!(
+        NBTCodec::little_endian()
+            .read_tag(&mut bytes.as_slice())
+            .is_err()
+    );
+
+    let codec = NBTCodec::little_endian().with_key_encoding(KeyEncoding::Escaped);
+    let (_, value) = codec.read_tag(&mut bytes.as_slice()).unwrap();
+    let Value::Compound(map) = &value else {
+        panic!("expected compound");
+    };
+    let (key, child) = map.iter().next().unwrap();
+    assert_eq!(key_bytes(key).as_ref(), [0xFF, b'a']);
+    assert_eq!(*child, Value::Byte(5));
+
+    let mut out = Vec::new();
+    codec.write_tag(&mut out, None, &value).unwrap();
+    assert_eq!(out, bytes);
+    assert_eq!(codec.encoded_len(None, &value), bytes.len());
+
+    let (_, buffered) = codec.read_tag_buffered(&mut bytes.as_slice()).unwrap();
+    assert_eq!(buffered, value);
+
+    let (_, partial) = codec
+        .read_compound_until(&mut bytes.as_slice(), |key| {
+            assert_eq!(key_bytes(key).as_ref(), [0xFF, b'a']);
+            bnbt::codec::Decision::Take
+        })
+        .unwrap();
+    assert_eq!(partial, value);
+
+    let tricky = "\u{10FF41}b".as_bytes();
+    assert_eq!(key_bytes(&escape_key(tricky)).as_ref(), tricky);
+    assert_eq!(escape_key(b"plain"), "plain");
+}
+
+#[test]
+fn dyn_codec_selects_endian_at_runtime() {
+    use bnbt::{codec::DynCodec, value::Value};
+
+    let codecs: Vec<Box<dyn DynCodec>> = vec![
+        Box::new(NBTCodec::big_endian()),
+        Box::new(NBTCodec::little_endian()),
+    ];
+
+    let mut root = Value::compound();
+    root.insert("Health", Value::Short(20)).unwrap();
+
+    let mut encoded = Vec::new();
+    for codec in &codecs {
+        let mut bytes = Vec::new();
+        codec
+            .write_tag_dyn(&mut bytes, Some("root"), &root)
+            .unwrap();
+        let (name, value) = codec.read_tag_dyn(&mut bytes.as_slice()).unwrap();
+        assert_eq!(name.as_deref(), Some("root"));
+        assert_eq!(value, root);
+        encoded.push(bytes);
+    }
+    assert_ne!(encoded[0], encoded[1]);
+
+    let mut payload = Vec::new();
+    codecs[1]
+        .write_value_dyn(&mut payload, &Value::Int(7))
+        .unwrap();
+    let value = codecs[1]
+        .read_value_dyn(&mut payload.as_slice(), bnbt::tag::Tag::Int)
+        .unwrap();
+    assert_eq!(value, Value::Int(7));
+}
+
+#[test]
+fn skip_value_seeks_past_arrays() {
+    use std::io::{Cursor, Read, Seek, SeekFrom};
+
+    use bnbt::{tag::Tag, value::Value};
+
+    struct Tracked {
+        inner: Cursor<Vec<u8>>,
+        read: usize,
+    }
+
+    impl Read for Tracked {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n;
+            Ok(n)
+        }
+    }
+
+    impl Seek for Tracked {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    let codec = NBTCodec::little_endian();
+    let mut root = Value::compound();
+    root.insert("Blocks", Value::ByteArray(vec![7; 4096]))
+        .unwrap();
+    root.insert("Heights", Value::IntArray(vec![1; 1024]))
+        .unwrap();
+    root.insert("Name", "chunk").unwrap();
+    root.insert(
+        "Entities",
+        Value::list_from_iter(vec![Value::compound(), Value::compound()]),
+    )
+    .unwrap();
+
+    let mut bytes = Vec::new();
+    codec.write_value(&mut bytes, &root).unwrap();
+    bytes.push(0xAA);
+
+    let mut reader = Tracked {
+        inner: Cursor::new(bytes.clone()),
+        read: 0,
+    };
+    codec.skip_value_seek(&mut reader, Tag::Compound).unwrap();
+    assert_eq!(reader.inner.position() as usize, bytes.len() - 1);
+    assert!(reader.read < 100);
+
+    let mut slice = bytes.as_slice();
+    codec.skip_value(&mut slice, Tag::Compound).unwrap();
+    assert_eq!(slice, [0xAA]);
+
+    let mut short = &bytes[..100];
+    assert!(codec.skip_value(&mut short, Tag::Compound).is_err());
+
+    let mut truncated = Cursor::new(bytes[..100].to_vec());
+    assert!(
+        codec
+            .skip_value_seek(&mut truncated, Tag::Compound)
+            .is_err()
+    );
+
+    let mut nested = Vec::new();
+    codec
+        .write_value(
+            &mut nested,
+            &Value::list_from_iter(vec![Value::list_from_iter(vec![Value::compound()])]),
+        )
+        .unwrap();
+    let shallow = NBTCodec::little_endian().with_max_depth(2);
+    assert!(
+        shallow
+            .skip_value(&mut nested.as_slice(), Tag::List)
+            .is_err()
+    );
+    assert!(
+        shallow
+            .read_value(&mut nested.as_slice(), &Tag::List)
+            .is_err()
+    );
+    let deep = NBTCodec::little_endian().with_max_depth(3);
+    deep.skip_value(&mut nested.as_slice(), Tag::List).unwrap();
+    deep.read_value(&mut nested.as_slice(), &Tag::List).unwrap();
+}
+
+#[test]
+fn read_compound_until_stops_after_wanted_keys() {
+    use bnbt::{codec::Decision, value::Value};
+
+    let codec = NBTCodec::little_endian();
+    let mut root = Value::compound();
+    root.insert("Biomes", Value::ByteArray(vec![1; 256]))
+        .unwrap();
+    root.insert("LastUpdate", Value::Long(99)).unwrap();
+    root.insert("xPos", Value::Int(3)).unwrap();
+    root.insert("zPos", Value::Int(-4)).unwrap();
+    root.insert("zzTail", Value::Byte(1)).unwrap();
+
+    let mut bytes = Vec::new();
+    codec
+        .write_tag(&mut bytes, Some("chunk".into()), &root)
+        .unwrap();
+
+    let mut seen = Vec::new();
+    let mut found = 0;
+    let (name, value) = codec
+        .read_compound_until(&mut bytes.as_slice(), |key| {
+            seen.push(key.to_owned());
+            match key {
+                _ if found == 2 => Decision::Stop,
+                "xPos" | "zPos" => {
+                    found += 1;
+                    Decision::Take
+                }
+                _ => Decision::Skip,
+            }
+        })
+        .unwrap();
+
+    assert_eq!(name.as_deref(), Some("chunk"));
+    assert_eq!(value.get("xPos"), Some(&Value::Int(3)));
+    assert_eq!(value.get("zPos"), Some(&Value::Int(-4)));
+    assert_eq!(value.get("Biomes"), None);
+    assert_eq!(value.get("zzTail"), None);
+    assert_eq!(seen, ["Biomes", "LastUpdate", "xPos", "zPos", "zzTail"]);
+
+    let mut scalar = Vec::new();
+    codec.write_tag(&mut scalar, None, &Value::Int(1)).unwrap();
+    assert!(
+        codec
+            .read_compound_until(&mut scalar.as_slice(), |_| Decision::Take)
+            .is_err()
+    );
+}
+
+#[test]
+fn read_projection_materializes_selected_paths() {
+    use bnbt::{path::NbtPath, value::Value};
+
+    let codec = NBTCodec::little_endian();
+    let mut player = Value::compound();
+    player.insert("Name", "Alex").unwrap();
+    player
+        .insert(
+            "Pos",
+            Value::list_from_iter(vec![
+                Value::Double(1.0),
+                Value::Double(64.0),
+                Value::Double(-2.0),
+            ]),
+        )
+        .unwrap();
+    player
+        .insert("Inventory", Value::ByteArray(vec![0; 512]))
+        .unwrap();
+
+    let mut root = Value::compound();
+    root.insert("Player", player).unwrap();
+    root.insert("Seed", Value::Long(42)).unwrap();
+
+    let mut bytes = Vec::new();
+    codec
+        .write_tag(&mut bytes, Some("level".into()), &root)
+        .unwrap();
+
+    let paths: Vec<NbtPath> = ["Player.Name", "Player.Pos[1]", "Missing.Key", "Seed.x"]
+        .iter()
+        .map(|p| p.parse().unwrap())
+        .collect();
+    let (name, value) = codec
+        .read_projection(&mut bytes.as_slice(), &paths)
+        .unwrap();
+
+    let mut expected_player = Value::compound();
+    expected_player.insert("Name", "Alex").unwrap();
+    expected_player
+        .insert(
+            "Pos",
+            Value::list_from_iter(vec![Value::Double(0.0), Value::Double(64.0)]),
+        )
+        .unwrap();
+    let mut expected = Value::compound();
+    expected.insert("Player", expected_player).unwrap();
+
+    assert_eq!(name.as_deref(), Some("level"));
+    assert_eq!(value, expected);
+
+    let (_, whole) = codec
+        .read_projection(&mut bytes.as_slice(), &[NbtPath::root()])
+        .unwrap();
+    assert_eq!(whole, root);
+
+    let mut list = Vec::new();
+    codec
+        .write_tag(&mut list, None, &Value::list_from_iter(vec![1, 2]))
+        .unwrap();
+    let (_, empty) = codec
+        .read_projection(&mut list.as_slice(), &["[0].x".parse().unwrap()])
+        .unwrap();
+    assert_eq!(empty, Value::EmptyList(bnbt::tag::Tag::End));
+}
+
+#[test]
+fn document_ranges_split_concatenated_roots() {
+    use bnbt::value::Value;
+
+    let codec = NBTCodec::little_endian();
+    let mut first = Value::compound();
+    first.insert("id", "minecraft:pig").unwrap();
+    let second = Value::IntArray(vec![1, 2, 3]);
+
+    let mut bytes = Vec::new();
+    codec
+        .write_tag(&mut bytes, Some("a".into()), &first)
+        .unwrap();
+    let boundary = bytes.len();
+    codec
+        .write_tag(&mut bytes, Some("b".into()), &second)
+        .unwrap();
+
+    let ranges = codec.document_ranges(&bytes).unwrap();
+    assert_eq!(ranges, [0..boundary, boundary..bytes.len()]);
+
+    let (_, value) = codec.read_tag(&mut &bytes[ranges[1].clone()]).unwrap();
+    assert_eq!(value, second);
+
+    assert!(codec.document_ranges(&bytes[..bytes.len() - 1]).is_err());
+    assert!(codec.document_ranges(&[]).unwrap().is_empty());
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn buf_round_trip_leaves_trailing_bytes() {
+    use bnbt::value::Value;
+    use bytes::{Buf, BufMut, BytesMut};
+
+    let codec = NBTCodec::little_endian();
+    let mut packet = Value::compound();
+    packet.insert("id", "minecraft:zombie").unwrap();
+    packet.insert("Health", Value::Float(20.0)).unwrap();
+
+    let mut buf = BytesMut::new();
+    codec.write_tag_buf(&mut buf, None, &packet).unwrap();
+    buf.put_u8(0xAB);
+
+    let mut frozen = buf.freeze();
+    let (_, value) = codec.read_tag_buf(&mut frozen).unwrap();
+    assert_eq!(value, packet);
+    assert_eq!(frozen.chunk(), [0xAB]);
+
+    let mut chained = (&[10u8, 0][..]).chain(&[0u8, 0][..]);
+    let (_, value) = codec.read_tag_buf(&mut chained).unwrap();
+    assert_eq!(value, Value::compound());
+
+    let mut small = [0u8; 4];
+    assert!(
+        codec
+            .write_tag_buf(&mut &mut small[..], None, &packet)
+            .is_err()
+    );
+}
+
+#[test]
+fn parse_prefix_returns_the_unconsumed_tail() {
+    use bnbt::value::Value;
+
+    let codec = NBTCodec::big_endian();
+    let mut item = Value::compound();
+    item.insert("Count", Value::Byte(3)).unwrap();
+
+    let mut packet = Vec::new();
+    codec.write_tag(&mut packet, None, &item).unwrap();
+    packet.extend_from_slice(&[0x01, 0x02]);
+
+    let (value, rest) = Value::parse_prefix(&packet).unwrap();
+    assert_eq!(value, item);
+    assert_eq!(rest, [0x01, 0x02]);
+
+    let little = NBTCodec::little_endian();
+    let bytes = little.to_vec_exact(None, &item).unwrap();
+    let (value, rest) = little.parse_prefix(&bytes).unwrap();
+    assert_eq!(value, item);
+    assert!(rest.is_empty());
+
+    assert!(Value::parse_prefix(&packet[..4]).is_err());
+}
+
+#[test]
+fn empty_typed_lists_round_trip_byte_exact() {
+    use bnbt::{tag::Tag, value::Value};
+
+    let bytes = [10, 0, 0, 9, 0, 1, b'l', 10, 0, 0, 0, 0, 0];
+    let codec = NBTCodec::big_endian();
+
+    let (_, value) = codec.read_tag(&mut bytes.as_slice()).unwrap();
+    assert_eq!(value.get("l"), Some(&Value::EmptyList(Tag::Compound)));
+    assert_eq!(codec.to_vec_exact(None, &value).unwrap(), bytes);
+
+    let (_, buffered) = codec.read_tag_buffered(&mut bytes.as_slice()).unwrap();
+    assert_eq!(buffered, value);
+
+    let mut target = Value::compound();
+    target
+        .insert("l", Value::list_from_iter(vec![Value::Int(1)]))
+        .unwrap();
+    codec
+        .read_tag_into(&mut bytes.as_slice(), &mut target)
+        .unwrap();
+    assert_eq!(target, value);
+
+    let mut list = Value::EmptyList(Tag::Compound);
+    assert!(list.as_list_mut().is_none());
+    assert_eq!(list, Value::EmptyList(Tag::Compound));
+    list.push(Value::compound()).unwrap();
+    assert_eq!(list.as_list().map(<[_]>::len), Some(1));
+}
+
+#[test]
+fn tag_extensions_round_trip_unknown_ids() {
+    use bnbt::{codec::ConfiguredCodec, error::Result, value::Value};
+    use std::io::Read;
+
+    fn four_bytes(_: &ConfiguredCodec, reader: &mut dyn Read) -> Result<Vec<u8>> {
+        let mut payload = vec![0; 4];
+        reader.read_exact(&mut payload)?;
+        Ok(payload)
+    }
+
+    let mut bytes = vec![10, 0, 0];
+    bytes.extend_from_slice(&[13, 0, 3, b'r', b'g', b'b', 0xde, 0xad, 0xbe, 0xef]);
+    bytes.extend_from_slice(&[9, 0, 1, b'l', 13, 0, 0, 0, 2, 1, 2, 3, 4, 5, 6, 7, 8]);
+    bytes.push(0);
+
+    let strict = NBTCodec::big_endian();
+    assert!(strict.read_tag(&mut bytes.as_slice()).is_err());
+
+    let codec = NBTCodec::big_endian().with_tag_extension(13, four_bytes);
+    let (_, value) = codec.read_tag(&mut bytes.as_slice()).unwrap();
+    assert_eq!(
+        value.get("rgb"),
+        Some(&Value::Unknown(13, vec![0xde, 0xad, 0xbe, 0xef]))
+    );
+    assert_eq!(
+        value.get("l").and_then(Value::as_list).map(<[_]>::len),
+        Some(2)
+    );
+
+    let mut out = Vec::new();
+    codec.write_tag(&mut out, None, &value).unwrap();
+    assert_eq!(codec.read_tag(&mut out.as_slice()).unwrap().1, value);
+    assert_eq!(codec.encoded_len(None, &value), out.len());
+
+    let mut payload = &bytes[3..];
+    assert!(
+        strict
+            .skip_value(&mut payload, bnbt::tag::Tag::Compound)
+            .is_err()
+    );
+    let mut payload = &bytes[3..];
+    codec
+        .skip_value(&mut payload, bnbt::tag::Tag::Compound)
+        .unwrap();
+    assert!(payload.is_empty());
+}
+
+#[test]
+fn tag_extensions_reach_every_decoder() {
+    use bnbt::{
+        codec::{ConfiguredCodec, Decision},
+        error::Result,
+        path::NbtPath,
+        tag::TagKind,
+        value::Value,
+    };
+    use std::io::Read;
+
+    fn four_bytes(_: &ConfiguredCodec, reader: &mut dyn Read) -> Result<Vec<u8>> {
+        let mut payload = vec![0; 4];
+        reader.read_exact(&mut payload)?;
+        Ok(payload)
+    }
+
+    let mut bytes = vec![10, 0, 0];
+    bytes.extend_from_slice(&[13, 0, 3, b'r', b'g', b'b', 0xde, 0xad, 0xbe, 0xef]);
+    bytes.extend_from_slice(&[9, 0, 1, b'l', 13, 0, 0, 0, 2, 1, 2, 3, 4, 5, 6, 7, 8]);
+    bytes.push(0);
+
+    let codec = NBTCodec::big_endian().with_tag_extension(13, four_bytes);
+    let (_, expected) = codec.read_tag(&mut bytes.as_slice()).unwrap();
+    let rgb = expected.get("rgb").unwrap();
+    assert_eq!(rgb.tag(), TagKind::Extension(13));
+    assert_eq!(
+        expected.get("l").and_then(Value::list_tag),
+        Some(TagKind::Extension(13))
+    );
+
+    let (_, buffered) = codec.read_tag_buffered(&mut bytes.as_slice()).unwrap();
+    assert_eq!(buffered, expected);
+
+    let mut target = Value::compound();
+    codec
+        .read_tag_into(&mut bytes.as_slice(), &mut target)
+        .unwrap();
+    assert_eq!(target, expected);
+
+    let paths: Vec<NbtPath> = ["rgb", "l[1]"].iter().map(|p| p.parse().unwrap()).collect();
+    let (_, projected) = codec
+        .read_projection(&mut bytes.as_slice(), &paths)
+        .unwrap();
+    let mut wanted = Value::compound();
+    wanted.insert("rgb", rgb.clone()).unwrap();
+    wanted
+        .insert(
+            "l",
+            Value::list_from_iter(vec![
+                Value::Unknown(13, Vec::new()),
+                Value::Unknown(13, vec![5, 6, 7, 8]),
+            ]),
+        )
+        .unwrap();
+    assert_eq!(projected, wanted);
+
+    let (_, taken) = codec
+        .read_compound_until(&mut bytes.as_slice(), |key| match key {
+            "rgb" => Decision::Skip,
+            _ => Decision::Take,
+        })
+        .unwrap();
+    assert_eq!(taken.get("rgb"), None);
+    assert_eq!(taken.get("l"), expected.get("l"));
+
+    let mut stream = bytes.clone();
+    stream.extend_from_slice(&bytes);
+    assert_eq!(
+        codec.document_ranges(&stream).unwrap(),
+        vec![0..bytes.len(), bytes.len()..stream.len()]
+    );
+
+    let limited = codec.clone().with_max_total_tags(5);
+    assert_eq!(limited.read_tag(&mut bytes.as_slice()).unwrap().1, expected);
+    let limited = codec.with_max_total_tags(4);
+    assert!(limited.read_tag(&mut bytes.as_slice()).is_err());
+    assert!(limited.read_tag_buffered(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn unknown_tag_length_hint_preserves_raw_payloads() {
+    use bnbt::value::Value;
+
+    let bytes = [10, 0, 0, 99, 0, 1, b'x', 1, 2, 3, 0];
+
+    let codec = NBTCodec::big_endian().with_unknown_tag_length(|id| (id == 99).then_some(3));
+    let (_, value) = codec.read_tag(&mut bytes.as_slice()).unwrap();
+    let x = value.get("x").unwrap();
+    assert!(x.is_unknown());
+    assert_eq!(x.tag_id(), 99);
+    assert_eq!(x, &Value::Unknown(99, vec![1, 2, 3]));
+
+    let mut out = Vec::new();
+    codec.write_tag(&mut out, None, &value).unwrap();
+    assert_eq!(out, bytes);
+
+    let other = NBTCodec::big_endian().with_unknown_tag_length(|id| (id == 98).then_some(3));
+    assert!(other.read_tag(&mut bytes.as_slice()).is_err());
+
+    let mut payload = std::io::Cursor::new(&bytes[3..]);
+    codec
+        .skip_value_seek(&mut payload, bnbt::tag::Tag::Compound)
+        .unwrap();
+    assert_eq!(payload.position() as usize, bytes.len() - 3);
+}
+
+#[test]
+fn read_pattern_projection_expands_wildcards() {
+    use bnbt::{path::PathPattern, snbt};
+
+    let codec = NBTCodec::big_endian();
+    let root = snbt::from_str(
+        r#"{Entities:[{id:"a",UUID:[I;1,2,3,4],Motion:[0d,0d,0d]},{id:"b",Tags:["x"]}],
+            Meta:{one:{v:1,w:2},two:{v:3}}}"#,
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    codec.write_tag(&mut bytes, None, &root).unwrap();
+
+    let patterns: Vec<PathPattern> = ["Entities[*].UUID", "Meta.*.v", "Meta.one.w"]
+        .iter()
+        .map(|p| p.parse().unwrap())
+        .collect();
+    let (_, value) = codec
+        .read_pattern_projection(&mut bytes.as_slice(), &patterns)
+        .unwrap();
+    assert_eq!(
+        value,
+        snbt::from_str("{Entities:[{UUID:[I;1,2,3,4]},{}],Meta:{one:{v:1,w:2},two:{v:3}}}")
+            .unwrap()
+    );
+
+    let (_, tags) = codec
+        .read_pattern_projection(
+            &mut bytes.as_slice(),
+            &["Entities[*].Tags".parse().unwrap()],
+        )
+        .unwrap();
+    assert_eq!(
+        tags,
+        snbt::from_str(r#"{Entities:[{},{Tags:["x"]}]}"#).unwrap()
+    );
+    let (_, motion) = codec
+        .read_pattern_projection(
+            &mut bytes.as_slice(),
+            &["Entities[0].Motion[2]".parse().unwrap()],
+        )
+        .unwrap();
+    assert_eq!(
+        motion,
+        snbt::from_str("{Entities:[{Motion:[0d,0d,0d]}]}").unwrap()
+    );
+
+    let deep: PathPattern = "**.UUID".parse().unwrap();
+    assert!(
+        codec
+            .read_pattern_projection(&mut bytes.as_slice(), &[deep])
+            .is_err()
+    );
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Sign {
+    #[serde(rename = "Color", default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(rename = "Text", default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<serde_json::Value>,
+}
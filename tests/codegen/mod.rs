@@ -0,0 +1,99 @@
+mod generated;
+
+use bnbt::{codegen::rust_structs, value::Value};
+
+fn villager(with_trades: bool, health: Value<'static>) -> Value<'static> {
+    let mut root = Value::compound();
+    root.insert("id", "minecraft:villager").unwrap();
+    root.insert("Health", health).unwrap();
+    root.insert(
+        "Pos",
+        Value::list_from_iter(vec![Value::Double(1.0), Value::Double(2.0)]),
+    )
+    .unwrap();
+
+    if with_trades {
+        let mut offer = Value::compound();
+        offer.insert("maxUses", 12).unwrap();
+        offer.insert("type", "emerald").unwrap();
+        root.insert("Offers", Value::list_from_iter(vec![offer]))
+            .unwrap();
+    }
+    root
+}
+
+#[test]
+fn structs_inferred_from_samples() {
+    let samples = [
+        villager(true, Value::Short(20)),
+        villager(false, Value::Int(18)),
+    ];
+
+    assert_eq!(
+        rust_structs("villager", &samples),
+        r#"#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Villager {
+    #[serde(rename = "Health")]
+    pub health: i32,
+    #[serde(rename = "Offers", default, skip_serializing_if = "Option::is_none")]
+    pub offers: Option<Vec<Offers>>,
+    #[serde(rename = "Pos")]
+    pub pos: Vec<f64>,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Offers {
+    #[serde(rename = "maxUses")]
+    pub max_uses: i32,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+"#
+    );
+
+    assert_eq!(
+        rust_structs("count", &[Value::Byte(1), Value::Long(2)]),
+        "pub type Count = i64;\n"
+    );
+}
+
+#[test]
+fn colliding_keys_get_distinct_fields() {
+    let mut root = Value::compound();
+    root.insert("id", 1).unwrap();
+    root.insert("Id", 2).unwrap();
+
+    let out = rust_structs("entity", &[root]);
+    assert!(out.contains("    #[serde(rename = \"Id\")]\n    pub id: i32,\n"));
+    assert!(out.contains("    #[serde(rename = \"id\")]\n    pub id_: i32,\n"));
+}
+
+fn sign(text: Value<'static>) -> Value<'static> {
+    let mut root = Value::compound();
+    root.insert("Text", text).unwrap();
+    root.insert("Color", "black").unwrap();
+    root
+}
+
+#[test]
+fn unknown_and_mixed_fields_fall_back_to_json_values() {
+    let samples = [
+        sign(Value::from("hi")),
+        sign(Value::Int(1)),
+        Value::compound(),
+    ];
+
+    assert_eq!(rust_structs("sign", &samples), include_str!("generated.rs"));
+
+    let sign = generated::Sign {
+        color: Some("black".into()),
+        text: Some(serde_json::json!(1)),
+    };
+    let json = serde_json::to_value(&sign).unwrap();
+    assert_eq!(json, serde_json::json!({ "Color": "black", "Text": 1 }));
+    assert_eq!(
+        serde_json::from_value::<generated::Sign>(json).unwrap(),
+        sign
+    );
+}
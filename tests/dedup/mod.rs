@@ -0,0 +1,89 @@
+use bnbt::value::Value;
+
+fn stack(id: &str, count: i8) -> Value<'static> {
+    let mut item = Value::compound();
+    item.insert("id", id.to_owned()).unwrap();
+    item.insert("Count", Value::Byte(count)).unwrap();
+    item
+}
+
+#[test]
+fn dedup_groups_identical_subtrees() {
+    let mut root = Value::compound();
+    root.insert(
+        "Inventory",
+        Value::list_from_iter(vec![
+            stack("minecraft:dirt", 64),
+            stack("minecraft:stone", 1),
+            stack("minecraft:dirt", 64),
+        ]),
+    )
+    .unwrap();
+    root.insert(
+        "EnderItems",
+        Value::list_from_iter(vec![stack("minecraft:dirt", 64)]),
+    )
+    .unwrap();
+
+    let shared = root.dedup_shared();
+    assert_eq!(shared.len(), 1);
+
+    let paths: Vec<String> = shared[0].paths.iter().map(ToString::to_string).collect();
+    assert_eq!(paths, ["EnderItems[0]", "Inventory[0]", "Inventory[2]"]);
+    assert_eq!(
+        shared[0].hash,
+        stack("minecraft:dirt", 64).structural_hash()
+    );
+    assert_ne!(
+        stack("minecraft:dirt", 64).structural_hash(),
+        stack("minecraft:dirt", 63).structural_hash()
+    );
+}
+
+#[test]
+fn hashed_caches_until_mutated() {
+    use bnbt::dedup::Hashed;
+
+    let a = Hashed::new(stack("minecraft:dirt", 64));
+    let mut b = Hashed::from(stack("minecraft:dirt", 64));
+    assert!(!a.is_cached());
+
+    assert_eq!(a, b);
+    assert!(a.is_cached() && b.is_cached());
+    assert_eq!(a.structural_hash(), a.value().structural_hash());
+
+    b.value_mut().insert("Count", Value::Byte(1)).unwrap();
+    assert!(!b.is_cached());
+    assert_ne!(a, b);
+    assert_eq!(
+        b.structural_hash(),
+        stack("minecraft:dirt", 1).structural_hash()
+    );
+
+    let clone = a.clone();
+    assert!(clone.is_cached());
+    assert_eq!(clone, a);
+}
+
+#[test]
+fn hashed_compares_floats_bitwise() {
+    use bnbt::dedup::Hashed;
+
+    let motion = |x: f64| {
+        let mut root = Value::compound();
+        root.insert("Motion", Value::list_from_iter(vec![Value::Double(x)]))
+            .unwrap();
+        Hashed::new(root)
+    };
+
+    assert_eq!(motion(f64::NAN), motion(f64::NAN));
+    assert_ne!(motion(0.0), motion(-0.0));
+    assert_ne!(
+        motion(0.0).structural_hash(),
+        motion(-0.0).structural_hash()
+    );
+    assert_eq!(
+        Hashed::new(Value::Float(f32::NAN)),
+        Hashed::new(Value::Float(f32::NAN))
+    );
+}
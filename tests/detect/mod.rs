@@ -0,0 +1,79 @@
+use bnbt::{
+    codec::{Endian, NBTCodec, NBTCodecTrait},
+    detect::{Compression, detect_flavor},
+    file::{BedrockHeader, NbtFile},
+    value::Value,
+};
+
+fn sample() -> Value<'static> {
+    let mut root = Value::compound();
+    root.insert("LevelName", "My World").unwrap();
+    root.insert("SpawnY", Value::Int(64)).unwrap();
+    root
+}
+
+#[test]
+fn detect_flavor_identifies_plain_and_headered_files() {
+    for endian in [Endian::Big, Endian::Little] {
+        let codec = NBTCodec::new(endian);
+        let mut bytes = Vec::new();
+        codec
+            .write_tag(&mut bytes, Some("".into()), &sample())
+            .unwrap();
+
+        let format = detect_flavor(&bytes).unwrap();
+        assert_eq!(format.endian, Some(endian));
+        assert_eq!(format.payload_offset, 0);
+        assert!(!format.network);
+
+        let codec = format.codec().unwrap();
+        let (_, value) = codec.read_tag(&mut &bytes[..]).unwrap();
+        assert_eq!(value, sample());
+    }
+
+    let mut file = NbtFile::new(sample(), Endian::Little);
+    file.header = Some(BedrockHeader { version: 10 });
+    let bytes = file.to_bytes().unwrap();
+
+    let format = detect_flavor(&bytes).unwrap();
+    assert_eq!(format.header, Some(BedrockHeader { version: 10 }));
+    assert_eq!(format.payload_offset, 8);
+    assert!(format.codec().is_some());
+}
+
+#[test]
+fn detect_flavor_identifies_compression_and_network_framing() {
+    let gzip = detect_flavor(&[0x1F, 0x8B, 8, 0]).unwrap();
+    assert_eq!(gzip.compression, Compression::Gzip);
+    assert!(gzip.codec().is_none());
+    assert_eq!(
+        detect_flavor(&[0x78, 0x9C, 0]).unwrap().compression,
+        Compression::Zlib
+    );
+
+    let mut network = vec![10, 0, 3, 6];
+    network.extend_from_slice(b"SpawnY");
+    network.extend_from_slice(&[0xD8, 0x04, 0]);
+
+    let format = detect_flavor(&network).unwrap();
+    assert!(format.network);
+    assert_eq!(format.endian, Some(Endian::Little));
+    assert!(format.codec().is_none());
+
+    assert!(detect_flavor(&[0xFF, 0x00]).is_err());
+}
+
+#[test]
+fn detect_flavor_rejects_deeply_nested_input() {
+    let mut bytes = vec![9, 0];
+    for _ in 0..200_000 {
+        bytes.extend_from_slice(&[9, 2]);
+    }
+    bytes.extend_from_slice(&[0, 0]);
+
+    let detect = std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || detect_flavor(&bytes).is_err())
+        .unwrap();
+    assert!(detect.join().unwrap());
+}
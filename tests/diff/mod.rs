@@ -0,0 +1,62 @@
+use bnbt::value::Value;
+
+fn player(y: f64, name: &str) -> Value<'static> {
+    let mut root = Value::compound();
+    root.insert(
+        "Pos",
+        Value::list_from_iter(vec![Value::Double(0.5), Value::Double(y)]),
+    )
+    .unwrap();
+    root.insert("Name", name.to_owned()).unwrap();
+    root
+}
+
+#[test]
+fn diff_reports_paths_of_changes() {
+    let left = player(64.0, "Steve");
+    let mut right = player(64.0000001, "Alex");
+    right.insert("Health", 20).unwrap();
+
+    let differences: Vec<String> = left
+        .diff(&right, 1e-3)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        differences,
+        [
+            "Name: String(\"Steve\") != String(\"Alex\")",
+            "Health: Int(20) only on the right"
+        ]
+    );
+
+    assert_eq!(left.diff(&right, 0.0).len(), 3);
+    assert!(left.diff(&left, 0.0).is_empty());
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn assert_nbt_eq_accepts_tolerance() {
+    bnbt::assert_nbt_eq!(
+        player(1.0, "Steve"),
+        player(1.0 + 1e-9, "Steve"),
+        epsilon = 1e-6
+    );
+
+    let result = std::panic::catch_unwind(|| {
+        bnbt::assert_nbt_eq!(player(1.0, "Steve"), player(2.0, "Steve"));
+    });
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("Pos[1]: Double(1.0) != Double(2.0)"));
+}
+
+#[test]
+fn approx_eq_tolerates_float_noise_only() {
+    let left = player(64.0, "Steve");
+
+    assert!(left.approx_eq(&player(64.0000001, "Steve"), 1e-6));
+    assert!(!left.approx_eq(&player(64.0000001, "Steve"), 0.0));
+    assert!(!left.approx_eq(&player(64.0, "Alex"), 1.0));
+    assert!(Value::Float(f32::NAN).approx_eq(&Value::Float(f32::NAN), 0.0));
+    assert!(!Value::Float(1.0).approx_eq(&Value::Double(1.0), 1.0));
+}
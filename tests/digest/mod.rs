@@ -0,0 +1,60 @@
+use bnbt::{
+    codec::{NBTCodec, NBTCodecTrait},
+    digest::HashAlgorithm,
+    encoding::to_hex,
+    value::Value,
+};
+
+fn chunk(x: i32) -> Value<'static> {
+    let mut root = Value::compound();
+    root.insert("zPos", Value::Int(0)).unwrap();
+    root.insert("xPos", Value::Int(x)).unwrap();
+    root.insert("Sections", Value::list_from_iter(vec![Value::compound()]))
+        .unwrap();
+    root
+}
+
+#[test]
+fn content_hash_covers_canonical_encoding() {
+    let bytes = chunk(1).canonical_bytes().unwrap();
+    let mut decoded = bytes.as_slice();
+    let codec = NBTCodec::big_endian();
+    let (name, value) = codec.read_tag(&mut decoded).unwrap();
+    assert_eq!(name, None);
+    assert_eq!(value, chunk(1));
+
+    let algorithms = [
+        HashAlgorithm::Fnv1a64,
+        #[cfg(feature = "digest")]
+        HashAlgorithm::Crc32,
+        #[cfg(feature = "digest")]
+        HashAlgorithm::Sha256,
+    ];
+    for algorithm in algorithms {
+        assert_eq!(
+            chunk(1).content_hash(algorithm).unwrap(),
+            chunk(1).content_hash(algorithm).unwrap()
+        );
+        assert_ne!(
+            chunk(1).content_hash(algorithm).unwrap(),
+            chunk(2).content_hash(algorithm).unwrap()
+        );
+    }
+
+    let empty = Value::compound();
+    assert_eq!(empty.canonical_bytes().unwrap(), [10, 0, 0, 0]);
+    assert_eq!(
+        to_hex(&empty.content_hash(HashAlgorithm::Fnv1a64).unwrap()),
+        "8cf02ed2fbe7719f"
+    );
+    #[cfg(feature = "digest")]
+    assert_eq!(
+        to_hex(&empty.content_hash(HashAlgorithm::Crc32).unwrap()),
+        "4ef93f78"
+    );
+    #[cfg(feature = "digest")]
+    assert_eq!(
+        to_hex(&empty.content_hash(HashAlgorithm::Sha256).unwrap()),
+        "075de2b906dbd7066da008cab735bee896370154603579a50122f9b88545bd45"
+    );
+}
@@ -0,0 +1,131 @@
+use bnbt::{
+    editor::{ChangeKind, Editor},
+    path::NbtPath,
+    value::Value,
+};
+
+fn path(s: &str) -> NbtPath {
+    s.parse().unwrap()
+}
+
+fn level() -> Value<'static> {
+    let mut data = Value::compound();
+    data.insert("LevelName", "World").unwrap();
+    data.insert("Players", Value::list_from_iter(vec!["Alex", "Steve"]))
+        .unwrap();
+
+    let mut root = Value::compound();
+    root.insert("Data", data).unwrap();
+    root
+}
+
+#[test]
+fn undo_and_redo_single_edits() {
+    let original = level();
+    let mut editor = Editor::new(original.clone());
+    assert!(!editor.can_undo());
+
+    editor.set(&path("Data.LevelName"), "Renamed").unwrap();
+    editor
+        .set(&path("Data.GameRules.keepInventory"), "true")
+        .unwrap();
+    editor.remove(&path("Data.Players[0]")).unwrap();
+    editor.insert(&path("Data.Players[1]"), "Notch").unwrap();
+
+    let edited = editor.value().clone();
+    assert_eq!(
+        edited.get_path(&path("Data.Players")),
+        Some(&Value::list_from_iter(vec!["Steve", "Notch"]))
+    );
+
+    while editor.undo().unwrap() {}
+    assert_eq!(editor.value(), &original);
+    assert!(editor.can_redo());
+
+    while editor.redo().unwrap() {}
+    assert_eq!(editor.value(), &edited);
+
+    editor.undo().unwrap();
+    editor.set(&path("Data.LevelName"), "Other").unwrap();
+    assert!(!editor.can_redo());
+}
+
+#[test]
+fn transactions_group_and_roll_back() {
+    let original = level();
+    let mut editor = Editor::new(original.clone());
+
+    editor.begin();
+    editor.set(&path("Data.LevelName"), "A").unwrap();
+    editor.set(&path("Data.Seed"), 42i64).unwrap();
+    assert!(editor.undo().is_err());
+    editor.commit();
+
+    assert!(editor.undo().unwrap());
+    assert_eq!(editor.value(), &original);
+    assert!(!editor.can_undo());
+
+    editor.begin();
+    editor.remove(&path("Data.Players")).unwrap();
+    assert!(editor.insert(&path("Data.Players[0]"), "x").is_err());
+    editor.rollback().unwrap();
+    assert_eq!(editor.value(), &original);
+    assert!(!editor.can_undo());
+
+    assert!(editor.set(&path("Data.Missing[0].x"), 1).is_err());
+    assert!(editor.insert(&path("Data.Players[0]"), 1).is_err());
+    assert!(editor.remove(&path("Data.Nope")).is_err());
+    assert_eq!(editor.into_inner(), original);
+}
+
+#[test]
+fn inserts_respect_empty_typed_lists() {
+    let mut root = level();
+    root.insert("Items", Value::EmptyList(bnbt::tag::Tag::Compound))
+        .unwrap();
+    let mut editor = Editor::new(root);
+
+    assert!(editor.insert(&path("Items[0]"), "x").is_err());
+    assert!(editor.insert(&path("Items[1]"), Value::compound()).is_err());
+    assert_eq!(
+        editor.value().get("Items"),
+        Some(&Value::EmptyList(bnbt::tag::Tag::Compound))
+    );
+
+    editor.insert(&path("Items[0]"), Value::compound()).unwrap();
+    assert_eq!(
+        editor.value().get("Items"),
+        Some(&Value::list_from_iter(vec![Value::compound()]))
+    );
+}
+
+#[test]
+fn observers_see_edits_undo_and_redo() {
+    use std::sync::mpsc;
+
+    let (tx, rx) = mpsc::channel();
+    let mut editor = Editor::new(level());
+    editor.on_change(move |path, kind| tx.send((path.to_string(), kind)).unwrap());
+
+    editor.set(&path("Data.LevelName"), "Renamed").unwrap();
+    editor
+        .set(&path("Data.GameRules.doFireTick"), "false")
+        .unwrap();
+    editor.remove(&path("Data.Players[1]")).unwrap();
+    editor.undo().unwrap();
+    editor.undo().unwrap();
+    editor.redo().unwrap();
+
+    let changes: Vec<_> = rx.try_iter().collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("Data.LevelName".to_owned(), ChangeKind::Replaced),
+            ("Data.GameRules".to_owned(), ChangeKind::Added),
+            ("Data.Players[1]".to_owned(), ChangeKind::Removed),
+            ("Data.Players[1]".to_owned(), ChangeKind::Added),
+            ("Data.GameRules".to_owned(), ChangeKind::Removed),
+            ("Data.GameRules".to_owned(), ChangeKind::Added),
+        ]
+    );
+}
@@ -0,0 +1,62 @@
+use bnbt::{
+    codec::{NBTCodec, NBTCodecTrait},
+    error::NBTError,
+    path::NbtPath,
+    value::Value,
+};
+
+#[test]
+fn display_is_compact() {
+    let codec = NBTCodec::big_endian();
+    let truncated = [0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00];
+    let err = codec.read_tag(&mut truncated.as_slice()).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "unexpected end of input while reading 8 bytes of IntArray at byte 8"
+    );
+    assert!(!format!("{:?}", err).contains("Backtrace"));
+    assert!(format!("{:#?}", err).contains("Backtrace"));
+
+    let err = NBTError::invalid_fields(vec!["x: missing".into(), "y: missing".into()]);
+    assert_eq!(err.to_string(), "invalid fields: x: missing; y: missing");
+    assert_eq!(err.path(), None);
+    let _ = err.backtrace();
+}
+
+#[test]
+fn decode_errors_report_the_nbt_path() {
+    let codec = NBTCodec::big_endian();
+
+    let mut section = Value::compound();
+    section
+        .insert("BlockStates", Value::LongArray(vec![1, 2]))
+        .unwrap();
+    let mut level = Value::compound();
+    level
+        .insert(
+            "Sections",
+            Value::list_from_iter(vec![Value::compound(), section]),
+        )
+        .unwrap();
+    let mut root = Value::compound();
+    root.insert("Level", level).unwrap();
+
+    let mut bytes = Vec::new();
+    codec.write_tag(&mut bytes, None, &root).unwrap();
+    let truncated = &bytes[..bytes.len() - 8];
+
+    let expected: NbtPath = "Level.Sections[1].BlockStates".parse().unwrap();
+    for err in [
+        codec.read_tag(&mut &truncated[..]).unwrap_err(),
+        codec.read_tag_buffered(&mut &truncated[..]).unwrap_err(),
+    ] {
+        assert_eq!(err.path(), Some(&expected));
+        assert!(
+            err.to_string()
+                .ends_with("of LongArray at byte 57 in Level.Sections[1].BlockStates"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use bnbt::{extract_nbt, value::Value};
+
+#[test]
+fn extract_fields_from_compound() {
+    let mut chunk = Value::compound();
+    chunk.insert("xPos", 3).unwrap();
+    chunk.insert("zPos", -7).unwrap();
+    chunk.insert("Status", "minecraft:full").unwrap();
+    chunk.insert("sections", Value::list(0)).unwrap();
+
+    let (x, z, status, sections, light) = extract_nbt!(chunk => {
+        x: i32 = "xPos",
+        z: i32 = "zPos",
+        status: &str = "Status",
+        sections: &Value = "sections",
+        light: Option<bool> = "isLightOn",
+    })
+    .unwrap();
+
+    assert_eq!(x, 3);
+    assert_eq!(z, -7);
+    assert_eq!(status, "minecraft:full");
+    assert_eq!(sections, &Value::list(0));
+    assert_eq!(light, None);
+}
+
+#[test]
+fn extract_reports_every_failing_field() {
+    let mut chunk = Value::compound();
+    chunk.insert("xPos", 3i64).unwrap();
+
+    let err = extract_nbt!(chunk => {
+        x: i32 = "xPos",
+        z: i32 = "zPos",
+    })
+    .unwrap_err();
+
+    let bnbt::error::NBTErrorKind::InvalidFields(errors) = err.kind else {
+        panic!("unexpected error kind: {:?}", err.kind);
+    };
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn ignore_case_lookups() {
+    let mut actor = Value::compound();
+    actor.insert("Id", "minecraft:cow").unwrap();
+    actor.insert("UniqueID", 42i64).unwrap();
+    actor.insert("uniqueid", 7i64).unwrap();
+
+    assert_eq!(actor.get("id"), None);
+    assert_eq!(
+        actor.get_ignore_case("id"),
+        Some(&Value::from("minecraft:cow"))
+    );
+    assert_eq!(actor.get_ignore_case("uniqueid"), Some(&Value::Long(7)));
+    assert_eq!(actor.get_ignore_case("UNIQUEID"), Some(&Value::Long(42)));
+
+    *actor.get_ignore_case_mut("ID").unwrap() = Value::from("minecraft:pig");
+    assert_eq!(actor.get("Id"), Some(&Value::from("minecraft:pig")));
+
+    let (id, unique) = extract_nbt!(actor, ignore_case => {
+        id: &str = "id",
+        unique: i64 = "UniqueId",
+    })
+    .unwrap();
+    assert_eq!(id, "minecraft:pig");
+    assert_eq!(unique, 42);
+
+    assert!(extract_nbt!(actor => { id: &str = "id" }).is_err());
+}
@@ -0,0 +1,122 @@
+use bnbt::{codec::Endian, detect::Compression, file::NbtFile, value::Value};
+use std::{env::current_dir, fs};
+
+#[test]
+fn leveldat_round_trip_preserves_layout() {
+    let path = current_dir().unwrap().join("tests/resources/level.dat");
+    let original = fs::read(&path).unwrap();
+
+    let mut file = NbtFile::open(&path).unwrap();
+    assert_eq!(file.endian, Endian::Little);
+    assert!(file.header.is_some());
+    assert_eq!(file.to_bytes().unwrap(), original);
+
+    file.value.insert("LevelName", "Renamed").unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("level.dat");
+    file.save(&out).unwrap();
+
+    let reopened = NbtFile::open(&out).unwrap();
+    assert_eq!(reopened.header, file.header);
+    assert_eq!(
+        reopened.value.get("LevelName"),
+        Some(&Value::from("Renamed"))
+    );
+
+    file.save(&out).unwrap();
+    let entries: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(entries, ["level.dat"]);
+
+    assert!(file.save(dir.path().join("missing/level.dat")).is_err());
+    assert_eq!(fs::read(&out).unwrap(), file.to_bytes().unwrap());
+}
+
+#[test]
+fn headerless_big_endian_file() {
+    let mut root = Value::compound();
+    root.insert("DataVersion", 3465).unwrap();
+
+    let mut file = NbtFile::new(root, Endian::Big);
+    file.root_name = Some("Data".to_owned());
+
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(NbtFile::from_bytes(&bytes).unwrap(), file);
+}
+
+#[test]
+fn compressed_files_are_rejected() {
+    let mut file = NbtFile::new(Value::compound(), Endian::Big);
+    assert_eq!(file.compression, Compression::None);
+
+    file.compression = Compression::Gzip;
+    assert!(file.to_bytes().is_err());
+    assert!(NbtFile::from_bytes(&[0x1F, 0x8B, 0x08, 0x00]).is_err());
+    assert!(NbtFile::from_bytes(&[0x78, 0x9C, 0x03, 0x00]).is_err());
+}
+
+#[test]
+fn headerless_little_endian_file_with_empty_root_name() {
+    let mut root = Value::compound();
+    root.insert("SpawnY", 64).unwrap();
+    root.insert("LevelName", "world").unwrap();
+
+    let file = NbtFile::new(root, Endian::Little);
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(NbtFile::from_bytes(&bytes).unwrap(), file);
+}
+
+#[test]
+fn headerless_little_endian_file_with_long_payload() {
+    let mut root = Value::compound();
+    root.insert("data", Value::ByteArray(vec![7; 2048]))
+        .unwrap();
+
+    let mut file = NbtFile::new(root, Endian::Little);
+    file.root_name = Some("level".to_owned());
+
+    let bytes = file.to_bytes().unwrap();
+    assert!(u16::from_be_bytes([bytes[1], bytes[2]]) as usize <= bytes.len() - 3);
+    assert_eq!(NbtFile::from_bytes(&bytes).unwrap(), file);
+}
+
+#[test]
+fn deeply_nested_files_are_rejected() {
+    use bnbt::{
+        codec::{DEFAULT_MAX_DEPTH, NBTCodec, NBTCodecTrait},
+        error::NBTErrorKind,
+    };
+
+    let nested = |depth: usize| {
+        let mut bytes = vec![10, 0, 0];
+        for _ in 0..depth {
+            bytes.extend_from_slice(&[10, 0, 1, b'a']);
+        }
+        bytes.resize(bytes.len() + depth + 1, 0);
+        bytes
+    };
+
+    let checks = std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || {
+            assert!(NbtFile::from_bytes(&nested(100_000)).is_err());
+
+            let codec = NBTCodec::big_endian();
+            let err = codec.read_tag(&mut &nested(100_000)[..]).unwrap_err();
+            assert!(matches!(err.kind, NBTErrorKind::TooDeep(DEFAULT_MAX_DEPTH)));
+            assert!(codec.document_ranges(&nested(100_000)).is_err());
+            codec
+                .read_tag(&mut &nested(DEFAULT_MAX_DEPTH - 1)[..])
+                .unwrap();
+
+            let unbounded = codec.without_max_depth();
+            unbounded
+                .read_tag(&mut &nested(DEFAULT_MAX_DEPTH + 1)[..])
+                .unwrap();
+        })
+        .unwrap();
+    checks.join().unwrap();
+}
@@ -0,0 +1,90 @@
+use bnbt::{
+    gamerules::{GameRules, RuleKind, RuleStorage, RuleValue, rule_kind},
+    value::Value,
+};
+
+fn java_level() -> Value<'static> {
+    let mut rules = Value::compound();
+    rules.insert("keepInventory", "false").unwrap();
+    rules.insert("randomTickSpeed", "3").unwrap();
+    rules.insert("myDatapackRule", "on").unwrap();
+    let mut data = Value::compound();
+    data.insert("GameRules", rules).unwrap();
+    let mut root = Value::compound();
+    root.insert("Data", data).unwrap();
+    root
+}
+
+#[test]
+fn java_rules_are_typed_strings() {
+    let mut root = java_level();
+    let mut rules = GameRules::from_level_dat(&mut root).unwrap();
+    assert_eq!(rules.storage(), RuleStorage::Java);
+
+    assert_eq!(rules.get_bool("keepInventory"), Some(false));
+    assert_eq!(rules.get_int("randomtickspeed"), Some(3));
+    assert_eq!(rules.get("myDatapackRule"), Some(RuleValue::from("on")));
+    assert_eq!(rules.get("doFireTick"), None);
+
+    rules.set_bool("keepinventory", true).unwrap();
+    rules.set_int("spawnRadius", 0).unwrap();
+    assert!(rules.set("randomTickSpeed", true).is_err());
+
+    let stored = root
+        .get("Data")
+        .and_then(|data| data.get("GameRules"))
+        .unwrap();
+    assert_eq!(stored.get("keepInventory"), Some(&Value::from("true")));
+    assert_eq!(stored.get("spawnRadius"), Some(&Value::from("0")));
+}
+
+#[test]
+fn bedrock_rules_live_at_the_root() {
+    let mut root = Value::compound();
+    root.insert("LevelName", "World").unwrap();
+    root.insert("dodaylightcycle", Value::Byte(1)).unwrap();
+    root.insert("randomtickspeed", Value::Int(1)).unwrap();
+
+    let mut rules = GameRules::from_level_dat(&mut root).unwrap();
+    assert_eq!(rules.storage(), RuleStorage::Bedrock);
+    assert_eq!(rules.get_bool("doDaylightCycle"), Some(true));
+    assert_eq!(rules.names(), ["dodaylightcycle", "randomtickspeed"]);
+
+    assert_eq!(rules.get("LevelName"), None);
+    assert!(rules.set("LevelName", "Other").is_err());
+
+    rules.set("showCoordinates", true).unwrap();
+    rules.set_int("randomTickSpeed", 0).unwrap();
+    rules.force_set("newBedrockRule", true).unwrap();
+    assert_eq!(root.get("showcoordinates"), Some(&Value::Byte(1)));
+    assert_eq!(root.get("randomtickspeed"), Some(&Value::Int(0)));
+    assert_eq!(root.get("LevelName"), Some(&Value::from("World")));
+    assert_eq!(root.get("newbedrockrule"), Some(&Value::Byte(1)));
+
+    assert_eq!(rule_kind("PVP"), Some(RuleKind::Bool));
+    assert!(GameRules::from_level_dat(&mut Value::Int(0)).is_err());
+}
+
+#[test]
+fn java_game_rules_are_created_only_on_set() {
+    let mut data = Value::compound();
+    data.insert("LevelName", "World").unwrap();
+    let mut root = Value::compound();
+    root.insert("Data", data).unwrap();
+    let original = root.clone();
+
+    let rules = GameRules::from_level_dat(&mut root).unwrap();
+    assert_eq!(rules.storage(), RuleStorage::Java);
+    assert_eq!(rules.get("keepInventory"), None);
+    assert!(rules.names().is_empty());
+    assert_eq!(root, original);
+
+    let mut rules = GameRules::from_level_dat(&mut root).unwrap();
+    rules.set_bool("keepInventory", true).unwrap();
+    assert_eq!(
+        root.get("Data")
+            .and_then(|data| data.get("GameRules"))
+            .and_then(|rules| rules.get("keepInventory")),
+        Some(&Value::from("true"))
+    );
+}
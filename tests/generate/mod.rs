@@ -0,0 +1,30 @@
+use bnbt::{
+    codec::{NBTCodec, NBTCodecTrait},
+    generate::{GenOptions, Generator, random_compound},
+};
+
+#[test]
+fn generated_trees_are_deterministic_and_encodable() {
+    let options = GenOptions {
+        max_depth: 3,
+        max_len: 6,
+        max_string_len: 8,
+    };
+
+    assert_eq!(random_compound(42, options), random_compound(42, options));
+    assert_ne!(random_compound(42, options), random_compound(43, options));
+
+    let codec = NBTCodec::big_endian();
+    let mut generator = Generator::with_options(7, options);
+    for _ in 0..50 {
+        let value = generator.compound();
+
+        let mut buf = Vec::new();
+        codec.write_tag(&mut buf, None, &value).unwrap();
+        let (_, decoded) = codec.read_tag(&mut buf.as_slice()).unwrap();
+        assert_eq!(decoded, value);
+
+        let (_, _, stats) = codec.read_tag_with_stats(&mut buf.as_slice()).unwrap();
+        assert!(stats.max_depth <= options.max_depth + 1);
+    }
+}
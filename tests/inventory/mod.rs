@@ -0,0 +1,60 @@
+use bnbt::{
+    inventory::{Inventory, ItemLayout, ItemStack},
+    snbt,
+    value::Value,
+};
+
+#[test]
+fn item_stacks_round_trip_each_layout() {
+    let cases = [
+        (
+            r#"{Slot:3b,id:"minecraft:diamond_sword",Count:1b,Damage:5s,tag:{Unbreakable:1b}}"#,
+            ItemLayout::JavaLegacy,
+        ),
+        (
+            r#"{Slot:0b,id:"minecraft:apple",count:12,components:{"minecraft:rarity":"epic"}}"#,
+            ItemLayout::JavaComponents,
+        ),
+        (
+            r#"{Slot:1b,Name:"minecraft:torch",Count:64b,Damage:0s,WasPickedUp:0b}"#,
+            ItemLayout::Bedrock,
+        ),
+    ];
+
+    for (text, layout) in cases {
+        let value = snbt::from_str(text).unwrap();
+        let stack = ItemStack::from_value(value.clone()).unwrap();
+        assert_eq!(stack.layout, layout);
+        assert_eq!(stack.to_value(), value);
+    }
+
+    let modern =
+        ItemStack::from_value(snbt::from_str(r#"{id:"minecraft:stick",components:{}}"#).unwrap());
+    assert_eq!(modern.unwrap().count, 1);
+    assert!(ItemStack::from_value(snbt::from_str("{Count:1b}").unwrap()).is_err());
+}
+
+#[test]
+fn inventory_edits_by_slot() {
+    let value =
+        snbt::from_str(r#"[{Slot:0b,Name:"minecraft:dirt",Count:3b},{Slot:1b,Name:"",Count:0b}]"#)
+            .unwrap();
+    let mut inventory = Inventory::from_value(value).unwrap();
+
+    assert_eq!(inventory.get(0).map(|item| item.count), Some(3));
+    assert_eq!(inventory.first_free_slot(36), Some(1));
+
+    let torch = ItemStack::new(ItemLayout::Bedrock, "minecraft:torch", 16);
+    assert!(inventory.set(1, torch).is_some());
+    assert_eq!(inventory.first_free_slot(36), Some(2));
+    assert_eq!(
+        inventory.remove(0).map(|item| item.id),
+        Some("minecraft:dirt".into())
+    );
+
+    assert_eq!(
+        inventory.to_value(),
+        snbt::from_str(r#"[{Slot:1b,Name:"minecraft:torch",Count:16b}]"#).unwrap()
+    );
+    assert!(Inventory::from_value(Value::Int(0)).is_err());
+}
@@ -0,0 +1,472 @@
+use bnbt::{
+    bitpack,
+    java::{
+        chunk::{
+            block_light, block_light_mut, is_light_on, section_ys, set_light_on, set_status,
+            sky_light, sky_light_mut, status,
+        },
+        data::{IdCounts, Raids, RandomSequences},
+        entities::{
+            EntityLocation, entity_uuid, find_entity, format_uuid, parse_uuid, uuid_to_ints,
+        },
+        map::{MAP_PIXELS, MapItem, color_to_rgba, rgba_to_color},
+        poi::{GlobalPos, PoiChunk, set_villager_memory, villager_memories, villager_memory},
+        scoreboard::{Objective, Scoreboard},
+        section::{BiomePalette, ChunkSection, block_state},
+        storage::{CommandStorage, namespace_from_file_name},
+        structures::{LegacyStructures, kind_from_file_name, pack_chunk_pos, unpack_chunk_pos},
+        version::DataVersion,
+    },
+    nibble::NibbleArray,
+    snbt,
+    value::Value,
+};
+
+#[test]
+fn bitpack_round_trip() {
+    assert_eq!(bitpack::bits_for(1, 4), 0);
+    assert_eq!(bitpack::bits_for(2, 4), 4);
+    assert_eq!(bitpack::bits_for(33, 4), 6);
+
+    let values: Vec<u32> = (0..4096).map(|i| i % 33).collect();
+    let packed = bitpack::pack(&values, 6);
+    assert_eq!(packed.len(), bitpack::packed_len(4096, 6));
+    assert_eq!(bitpack::unpack(&packed, 6, 4096).unwrap(), values);
+    assert!(bitpack::unpack(&packed[1..], 6, 4096).is_err());
+}
+
+#[test]
+fn chunk_section_get_and_set_blocks() {
+    let mut section = ChunkSection::new(-4);
+    assert_eq!(section.get_block(0, 0, 0), None);
+
+    section
+        .set_block(1, 2, 3, block_state("minecraft:stone"))
+        .unwrap();
+    assert!(
+        section
+            .set_block(0, 16, 0, block_state("minecraft:stone"))
+            .is_err()
+    );
+    section.biomes = Some(BiomePalette::filled("minecraft:plains".to_owned()));
+    section
+        .biomes
+        .as_mut()
+        .unwrap()
+        .set_biome(3, 3, 3, "minecraft:desert")
+        .unwrap();
+
+    let value = section.to_value();
+    let Some(Value::LongArray(data)) = value.get_path(&"block_states.data".parse().unwrap()) else {
+        panic!("missing packed block data");
+    };
+    assert_eq!(data.len(), 256);
+
+    let decoded = ChunkSection::from_value(&value).unwrap();
+    assert_eq!(decoded, section);
+    assert_eq!(
+        decoded.get_block(1, 2, 3),
+        Some(&block_state("minecraft:stone"))
+    );
+    assert_eq!(
+        decoded.get_block(0, 0, 0),
+        Some(&block_state("minecraft:air"))
+    );
+    assert_eq!(decoded.get_biome(3, 3, 3), Some("minecraft:desert"));
+    assert_eq!(decoded.get_biome(0, 0, 0), Some("minecraft:plains"));
+    assert_eq!(decoded.get_block(0, 0, 16), None);
+    assert_eq!(decoded.get_biome(0, 4, 0), None);
+}
+
+#[test]
+fn data_version_lookup_and_ordering() {
+    let mut data = Value::compound();
+    data.insert("DataVersion", 3465).unwrap();
+    let mut level = Value::compound();
+    level.insert("Data", data).unwrap();
+
+    let version = DataVersion::from_value(&level).unwrap();
+    assert_eq!(version.release(), Some("1.20.1"));
+    assert_eq!(version.to_string(), "1.20.1 (3465)");
+    assert!(version.is_at_least("1.20"));
+    assert!(!version.is_at_least("1.20.2"));
+    assert!(!version.is_at_least("not a release"));
+
+    let mut chunk = Value::compound();
+    chunk.insert("DataVersion", 3466).unwrap();
+    let snapshot = DataVersion::from_value(&chunk).unwrap();
+    assert_eq!(snapshot.release(), None);
+    assert_eq!(snapshot.nearest_release(), Some("1.20.1"));
+    assert_eq!(snapshot.to_string(), "after 1.20.1 (3466)");
+    assert!(snapshot > version);
+
+    assert_eq!(DataVersion::from_release("1.18"), Some(DataVersion(2860)));
+    assert_eq!(DataVersion(100).nearest_release(), None);
+    assert!(DataVersion::from_value(&Value::compound()).is_err());
+}
+
+#[test]
+fn scoreboard_round_trip_and_edits() {
+    let value = snbt::from_str(
+        r#"{DataVersion:3955,data:{
+            Objectives:[{Name:"kills",CriteriaName:"playerKillCount",DisplayName:'{"text":"Kills"}',RenderType:"integer",display_auto_update:0b}],
+            PlayerScores:[{Name:"Alex",Objective:"kills",Score:4,Locked:0b}],
+            Teams:[{Name:"red",DisplayName:'{"text":"Red"}',Players:["Alex"],TeamColor:"red",AllowFriendlyFire:1b}],
+            DisplaySlots:{slot_1:"kills"}
+        }}"#,
+    )
+    .unwrap();
+
+    let mut scoreboard = Scoreboard::from_value(value.clone()).unwrap();
+    assert_eq!(scoreboard.to_value(), value);
+    assert_eq!(scoreboard.score("Alex", "kills"), Some(4));
+    assert_eq!(
+        scoreboard.team_of("Alex").map(|team| team.name.as_str()),
+        Some("red")
+    );
+
+    scoreboard.set_score("Steve", "kills", 9).unwrap();
+    assert!(scoreboard.set_score("Steve", "deaths", 1).is_err());
+    scoreboard
+        .objectives
+        .push(Objective::new("deaths", "deathCount"));
+    scoreboard.set_score("Steve", "deaths", 1).unwrap();
+
+    let reparsed = Scoreboard::from_value(scoreboard.to_value()).unwrap();
+    assert_eq!(reparsed.score("Steve", "kills"), Some(9));
+    assert_eq!(reparsed.score("Steve", "deaths"), Some(1));
+
+    scoreboard.remove_objective("kills");
+    scoreboard.remove_holder("Alex");
+    assert!(scoreboard.display_slots.is_empty());
+    assert_eq!(scoreboard.scores.len(), 1);
+    assert!(scoreboard.team("red").unwrap().players.is_empty());
+}
+
+#[test]
+fn map_colors_convert_to_rgba_and_back() {
+    assert_eq!(color_to_rgba(0), [0, 0, 0, 0]);
+    assert_eq!(color_to_rgba(4 * 8 + 2), [255, 255, 255, 255]);
+    assert_eq!(color_to_rgba(4 * 8), [180, 180, 180, 255]);
+    assert_eq!(rgba_to_color([255, 255, 255, 255]), 34);
+    assert_eq!(rgba_to_color([9, 9, 9, 0]), 0);
+    assert!((4..248).all(|color| {
+        let rgba = color_to_rgba(color);
+        color_to_rgba(rgba_to_color(rgba)) == rgba
+    }));
+
+    let value = snbt::from_str(
+        r#"{DataVersion:3955,data:{scale:2b,xCenter:64,zCenter:-64,dimension:"minecraft:overworld"}}"#,
+    )
+    .unwrap();
+    let mut map = MapItem::from_value(value.clone()).unwrap();
+    assert_eq!(map.colors, None);
+    assert_eq!(map.x_center, Some(64));
+    assert_eq!(map.locked, None);
+    assert_eq!(map.color_at(0, 0), None);
+    assert_eq!(map.to_value(), value);
+
+    map.set_color(3, 1, 4 * 18 + 2).unwrap();
+    assert_eq!(map.colors.as_ref().map(Vec::len), Some(MAP_PIXELS));
+    assert!(map.set_color(128, 0, 4).is_err());
+    assert_eq!(map.color_at(0, 128), None);
+    let rgba = map.to_rgba();
+    assert_eq!(&rgba[(128 + 3) * 4..(128 + 4) * 4], [229, 229, 51, 255]);
+
+    let mut copy = map.clone();
+    copy.colors = None;
+    copy.set_rgba(&rgba).unwrap();
+    assert_eq!(copy.color_at(3, 1), Some(4 * 18 + 2));
+    assert!(copy.set_rgba(&rgba[4..]).is_err());
+
+    let reparsed = MapItem::from_value(map.to_value()).unwrap();
+    assert_eq!(reparsed, map);
+    assert_eq!(reparsed.locked, None);
+    assert_eq!(
+        reparsed.data_extra.get("dimension"),
+        Some(&Value::from("minecraft:overworld"))
+    );
+
+    let truncated = snbt::from_str("{data:{colors:[B;1b,2b]}}").unwrap();
+    assert!(MapItem::from_value(truncated).is_err());
+}
+
+#[test]
+fn poi_records_correlate_with_villager_memories() {
+    let chunk = snbt::from_str(
+        r#"{DataVersion:3955,Sections:{"4":{Valid:1b,Records:[
+            {type:"minecraft:home",pos:[I;10,70,-3],free_tickets:0},
+            {type:"minecraft:armorer",pos:[I;12,64,-1],free_tickets:1}
+        ]},"-1":{Valid:0b,Records:[]}}}"#,
+    )
+    .unwrap();
+    let mut poi = PoiChunk::from_value(chunk.clone()).unwrap();
+    assert_eq!(poi.to_value(), chunk);
+    assert_eq!(poi.records().count(), 2);
+
+    let mut villager = snbt::from_str(
+        r#"{id:"minecraft:villager",Brain:{memories:{
+            "minecraft:home":{value:{pos:[I;10,70,-3],dimension:"minecraft:overworld"}},
+            "minecraft:job_site":{pos:{X:12,Y:64,Z:-1},dimension:"minecraft:overworld"}
+        }}}"#,
+    )
+    .unwrap();
+    let memories = villager_memories(&villager);
+    assert_eq!(memories.len(), 2);
+
+    let job = &memories["minecraft:job_site"];
+    assert_eq!(
+        poi.record_at(job.pos).map(|r| r.kind.as_str()),
+        Some("minecraft:armorer")
+    );
+    poi.record_at_mut(job.pos).unwrap().free_tickets = 0;
+
+    let meeting = GlobalPos {
+        dimension: "minecraft:overworld".into(),
+        pos: [0, 64, 0],
+    };
+    set_villager_memory(&mut villager, "minecraft:meeting_point", Some(&meeting)).unwrap();
+    set_villager_memory(&mut villager, "minecraft:home", None).unwrap();
+    assert_eq!(
+        villager_memory(&villager, "minecraft:meeting_point"),
+        Some(meeting)
+    );
+    assert_eq!(villager_memory(&villager, "minecraft:home"), None);
+}
+
+#[test]
+fn data_folder_files_tolerate_older_layouts() {
+    let raids = snbt::from_str(
+        r#"{DataVersion:3955,data:{NextAvailableID:3,Tick:1200,Raids:[
+            {Id:2,Status:"ongoing",CX:10,CY:64,CZ:-5,BadOmenLevel:2,GroupsSpawned:1,
+             TicksActive:300L,HeroesOfTheVillage:[[I;1,2,3,4]],PreRaidTicks:0}
+        ]}}"#,
+    )
+    .unwrap();
+    let mut parsed = Raids::from_value(raids.clone()).unwrap();
+    assert_eq!(parsed.to_value(), raids);
+    assert_eq!(parsed.raids[0].omen_level, 2);
+    assert_eq!(parsed.raids[0].heroes, vec![vec![1, 2, 3, 4]]);
+    parsed.raids[0].omen_level = 4;
+    assert_eq!(
+        parsed
+            .to_value()
+            .get_path(&"data.Raids[0].BadOmenLevel".parse().unwrap()),
+        Some(&Value::Int(4))
+    );
+
+    let mut counts =
+        IdCounts::from_value(snbt::from_str("{DataVersion:3955,data:{map:7}}").unwrap()).unwrap();
+    assert!(!counts.legacy);
+    assert_eq!(counts.allocate_map_id(), 8);
+    assert_eq!(
+        counts.to_value(),
+        snbt::from_str("{DataVersion:3955,data:{map:8}}").unwrap()
+    );
+
+    let legacy = snbt::from_str("{map:4s}").unwrap();
+    let mut counts = IdCounts::from_value(legacy).unwrap();
+    assert!(counts.legacy);
+    assert_eq!(counts.last_map_id(), Some(4));
+    counts.allocate_map_id();
+    assert_eq!(counts.to_value(), snbt::from_str("{map:5s}").unwrap());
+
+    let sequences = snbt::from_str(
+        r#"{DataVersion:3955,data:{salt:0L,include_world_seed:1b,include_sequence_id:1b,
+            sequences:{"minecraft:chests/simple_dungeon":{source:[L;5L,-9L]}}}}"#,
+    )
+    .unwrap();
+    let parsed = RandomSequences::from_value(sequences.clone()).unwrap();
+    assert_eq!(parsed.to_value(), sequences);
+    assert_eq!(
+        parsed.source("minecraft:chests/simple_dungeon"),
+        Some(&[5, -9][..])
+    );
+    assert!(
+        RandomSequences::from_value(snbt::from_str("{data:{}}").unwrap())
+            .unwrap()
+            .include_world_seed
+    );
+}
+
+#[test]
+fn chunk_status_and_light_nibbles() {
+    let mut chunk = snbt::from_str(
+        r#"{DataVersion:3955,Status:"minecraft:full",isLightOn:1b,sections:[{Y:-4b},{Y:0b}]}"#,
+    )
+    .unwrap();
+    assert_eq!(status(&chunk), Some("minecraft:full"));
+    assert!(is_light_on(&chunk));
+    assert_eq!(section_ys(&chunk), vec![-4, 0]);
+    assert!(block_light(&chunk, 0).unwrap().is_none());
+
+    let mut light = block_light_mut(&mut chunk, 0).unwrap();
+    light.set_at(1, 2, 3, 15).unwrap();
+    light.set_at(0, 2, 3, 7).unwrap();
+    assert!(light.set_at(16, 2, 3, 1).is_err());
+    assert!(light.set(NibbleArray::NIBBLES, 1).is_err());
+    assert_eq!(light.get_at(1, 2, 3), Some(15));
+    let bytes = block_light(&chunk, 0).unwrap().unwrap().as_bytes();
+    assert_eq!(bytes[(2 * 256 + 3 * 16) / 2], 0xf7);
+
+    sky_light_mut(&mut chunk, 5).unwrap().fill(15);
+    assert_eq!(
+        sky_light(&chunk, 5).unwrap().unwrap().get_at(9, 9, 9),
+        Some(15)
+    );
+    assert_eq!(section_ys(&chunk), vec![-4, 0, 5]);
+
+    set_status(&mut chunk, "minecraft:light").unwrap();
+    set_light_on(&mut chunk, false).unwrap();
+    assert_eq!(status(&chunk), Some("minecraft:light"));
+    assert!(!is_light_on(&chunk));
+
+    let legacy =
+        snbt::from_str(r#"{Level:{Status:"full",Sections:[{Y:1b,SkyLight:[B;1b,2b]}]}}"#).unwrap();
+    assert_eq!(status(&legacy), Some("full"));
+    assert!(sky_light(&legacy, 1).is_err());
+}
+
+#[test]
+fn command_storage_per_namespace_contents() {
+    let root = snbt::from_str(
+        r#"{DataVersion:3955,data:{contents:{settings:{enabled:1b},"player/state":{count:3}}}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        namespace_from_file_name("world/data/command_storage_mypack.dat"),
+        Some("mypack")
+    );
+
+    let mut storage = CommandStorage::from_value(root.clone()).unwrap();
+    assert_eq!(storage.to_value(), root);
+    storage.namespace = Some("mypack".into());
+    let mut ids = storage.ids();
+    ids.sort();
+    assert_eq!(ids, vec!["mypack:player/state", "mypack:settings"]);
+    assert_eq!(
+        storage
+            .get("mypack:settings")
+            .and_then(|v| v.get("enabled")),
+        Some(&Value::Byte(1))
+    );
+    assert!(storage.get("other:settings").is_none());
+
+    storage
+        .set("mypack:new", snbt::from_str("{a:1}").unwrap())
+        .unwrap();
+    assert!(storage.set("other:new", Value::compound()).is_err());
+    assert!(storage.set("mypack:bad", Value::Int(1)).is_err());
+    assert!(storage.remove("player/state").is_some());
+    assert_eq!(
+        storage.file_name().as_deref(),
+        Some("command_storage_mypack.dat")
+    );
+    assert_eq!(
+        storage.to_value(),
+        snbt::from_str(r#"{DataVersion:3955,data:{contents:{settings:{enabled:1b},new:{a:1}}}}"#)
+            .unwrap()
+    );
+}
+
+#[test]
+fn legacy_structures_migrate_to_modern_starts() {
+    let root = snbt::from_str(
+        r#"{data:{Features:{
+            "[2,-1]":{id:"Fortress",ChunkX:2,ChunkZ:-1,BB:[I;32,48,-16,63,70,-1],Children:[
+                {id:"NeStart",BB:[I;32,48,-16,50,60,-1],GD:0,O:2},
+                {id:"NeBCr",BB:[I;40,48,-16,63,70,-1],GD:1,O:2}
+            ]},
+            "[0,0]":{id:"Temple",ChunkX:0,ChunkZ:0,Children:[{id:"TeDP",BB:[I;0,64,0,20,80,20]}]}
+        }}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        kind_from_file_name("world/data/Fortress.dat"),
+        Some("Fortress")
+    );
+    assert_eq!(kind_from_file_name("world/data/raids.dat"), None);
+
+    let structures = LegacyStructures::from_value(root.clone()).unwrap();
+    assert_eq!(structures.to_value(), root);
+
+    let fortress = structures.start_at(2, -1).unwrap();
+    assert_eq!(fortress.children.len(), 2);
+    assert_eq!(fortress.bounds().unwrap().max, [63, 70, -1]);
+
+    let modern = fortress.to_modern().unwrap();
+    assert_eq!(modern.get("id"), Some(&Value::from("minecraft:fortress")));
+    assert_eq!(modern.get("references"), Some(&Value::Int(0)));
+    assert_eq!(
+        structures.start_at(0, 0).unwrap().modern_id(),
+        Some("minecraft:desert_pyramid")
+    );
+
+    let references = structures.references();
+    assert_eq!(references[&(3, -1)].len(), 1);
+    assert_eq!(references[&(1, 1)].len(), 1);
+    assert_eq!(
+        structures.modern_references(3, -1),
+        snbt::from_str(&format!(
+            "{{\"minecraft:fortress\":[L;{}L]}}",
+            pack_chunk_pos(2, -1)
+        ))
+        .unwrap()
+    );
+    assert_eq!(unpack_chunk_pos(pack_chunk_pos(-5, 7)), (-5, 7));
+}
+
+#[test]
+fn find_entity_by_uuid_across_chunks() {
+    use bnbt::codec::{NBTCodec, NBTCodecTrait};
+
+    let uuid = parse_uuid("f81d4fae-7dec-11d0-a765-00a0c91e6bf6").unwrap();
+    assert_eq!(format_uuid(uuid), "f81d4fae-7dec-11d0-a765-00a0c91e6bf6");
+    let ints = uuid_to_ints(uuid);
+
+    let encode = |value: &Value| {
+        let mut bytes = Vec::new();
+        NBTCodec::big_endian()
+            .write_tag(&mut bytes, None, value)
+            .unwrap();
+        bytes
+    };
+    let modern = snbt::from_str(&format!(
+        r#"{{DataVersion:3955,Position:[I;4,-9],Entities:[
+            {{id:"minecraft:pig",UUID:[I;1,2,3,4]}},
+            {{id:"minecraft:horse",UUID:[I;5,6,7,8],Passengers:[
+                {{id:"minecraft:zombie",UUID:[I;{},{},{},{}]}}
+            ]}}
+        ]}}"#,
+        ints[0], ints[1], ints[2], ints[3]
+    ))
+    .unwrap();
+    let legacy = snbt::from_str(
+        r#"{Level:{xPos:-1,zPos:2,Entities:[{id:"Cow",UUIDMost:-1L,UUIDLeast:5L}]}}"#,
+    )
+    .unwrap();
+    let chunks = [encode(&legacy), encode(&modern)];
+
+    assert_eq!(
+        find_entity(&chunks, uuid).unwrap(),
+        Some(EntityLocation {
+            chunk: Some((4, -9)),
+            path: "Entities[1].Passengers[0]".parse().unwrap(),
+        })
+    );
+
+    let legacy_uuid = entity_uuid(
+        &legacy
+            .get("Level")
+            .unwrap()
+            .get("Entities")
+            .unwrap()
+            .as_list()
+            .unwrap()[0],
+    )
+    .unwrap();
+    let found = find_entity(&chunks, legacy_uuid).unwrap().unwrap();
+    assert_eq!(found.chunk, Some((-1, 2)));
+    assert_eq!(found.path, "Level.Entities[0]".parse().unwrap());
+    assert!(find_entity(&chunks, 1).unwrap().is_none());
+}
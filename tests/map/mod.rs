@@ -0,0 +1,93 @@
+use bnbt::{
+    codec::{NBTCodec, NBTCodecTrait},
+    map::{SMALL_MAP_THRESHOLD, SmallMap},
+    value::Value,
+};
+
+#[test]
+fn small_map_stays_sorted_across_promotion() {
+    let mut map = SmallMap::new();
+    for i in (0..SMALL_MAP_THRESHOLD as i32).rev() {
+        assert_eq!(map.insert(i, i * 10), None);
+    }
+    assert!(map.is_inline());
+    assert_eq!(map.insert(3, 33), Some(30));
+
+    *map.entry(100).or_insert(0) += 1;
+    assert!(!map.is_inline());
+    assert_eq!(map.len(), SMALL_MAP_THRESHOLD + 1);
+    assert_eq!(map.get(&3), Some(&33));
+    assert_eq!(map[&100], 1);
+
+    let keys: Vec<i32> = map.keys().copied().collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted);
+
+    assert_eq!(map.remove(&100), Some(1));
+    let inline: SmallMap<i32, i32> = map.clone().into_iter().collect();
+    assert!(inline.is_inline());
+    assert_eq!(inline, map);
+}
+
+#[test]
+fn compound_round_trips_through_small_map() {
+    let mut root = Value::compound();
+    for i in 0..20 {
+        root.insert(format!("key{:02}", i), Value::Int(i)).unwrap();
+    }
+
+    let codec = NBTCodec::little_endian();
+    let mut bytes = Vec::new();
+    codec
+        .write_tag(&mut bytes, Some("root".into()), &root)
+        .unwrap();
+    let (_, decoded) = codec.read_tag(&mut bytes.as_slice()).unwrap();
+
+    assert_eq!(decoded, root);
+    assert_eq!(decoded.get("key07"), Some(&Value::Int(7)));
+}
+
+#[cfg(feature = "preserve-order")]
+#[test]
+fn ordered_backend_keeps_file_order() {
+    use bnbt::map::{Ordered, OrderedMap};
+
+    let mut root = Value::<Ordered>::Compound(OrderedMap::new());
+    root.insert("zeta", Value::Byte(1)).unwrap();
+    root.insert("alpha", Value::Byte(2)).unwrap();
+    root.insert("mid", Value::List(vec![Value::Compound(OrderedMap::new())]))
+        .unwrap();
+
+    let codec = NBTCodec::little_endian();
+    let mut bytes = Vec::new();
+    codec
+        .write_tag_as(&mut bytes, Some("".into()), &root)
+        .unwrap();
+
+    let (_, ordered) = codec
+        .read_tag_as::<Ordered, _>(&mut bytes.as_slice())
+        .unwrap();
+    let Value::Compound(map) = &ordered else {
+        unreachable!()
+    };
+    let keys: Vec<&str> = map.keys().map(|k| k.as_ref()).collect();
+    assert_eq!(keys, ["zeta", "alpha", "mid"]);
+    assert_eq!(ordered, root);
+
+    let mut target = Value::<Ordered>::Compound(OrderedMap::new());
+    target.insert("stale", Value::Byte(0)).unwrap();
+    codec
+        .read_tag_into_as(&mut bytes.as_slice(), &mut target)
+        .unwrap();
+    assert_eq!(target, root);
+
+    let (_, sorted) = codec.read_tag(&mut bytes.as_slice()).unwrap();
+    let Value::Compound(map) = &sorted else {
+        unreachable!()
+    };
+    let keys: Vec<&str> = map.keys().map(|k| k.as_ref()).collect();
+    assert_eq!(keys, ["alpha", "mid", "zeta"]);
+    assert_eq!(ordered.clone().into_backend(), sorted);
+    assert_eq!(sorted.into_backend::<Ordered>(), ordered);
+}
@@ -0,0 +1,63 @@
+use bnbt::{migrate::MigrationRegistry, path::NbtPath, value::Value};
+
+#[test]
+fn migrate_applies_converters_in_version_order() {
+    let mut registry = MigrationRegistry::new();
+    registry
+        .register(2000..=2100, "Data.Name".parse().unwrap(), |value| {
+            if let Value::String(name) = value {
+                *value = Value::from(format!("{}!", name));
+            }
+            Ok(())
+        })
+        .register(1500..=1500, NbtPath::root().key("Data").into(), |value| {
+            value.insert("Name", "renamed")?;
+            Ok(())
+        })
+        .register(3000.., NbtPath::root().into(), |value| {
+            value.insert("Late", true)?;
+            Ok(())
+        });
+    assert_eq!(registry.len(), 3);
+
+    let mut data = Value::compound();
+    data.insert("Name", "old").unwrap();
+    let mut root = Value::compound();
+    root.insert("Data", data).unwrap();
+    root.insert("DataVersion", 1400).unwrap();
+
+    assert_eq!(registry.migrate(&mut root, 1400, 2500).unwrap(), 2);
+    assert_eq!(
+        root.get_path(&"Data.Name".parse().unwrap()),
+        Some(&Value::from("renamed!"))
+    );
+    assert_eq!(root.get("DataVersion"), Some(&Value::Int(2500)));
+    assert_eq!(root.get("Late"), None);
+}
+
+#[test]
+fn migrate_converts_every_match_of_a_pattern() {
+    let mut registry = MigrationRegistry::new();
+    registry.register(..2000, "Items[*].Count".parse().unwrap(), |value| {
+        if let Value::Byte(count) = value {
+            *value = Value::Int(*count as i32);
+        }
+        Ok(())
+    });
+
+    let items = (1..=3).map(|count| {
+        let mut item = Value::compound();
+        item.insert("Count", Value::Byte(count)).unwrap();
+        item
+    });
+    let mut root = Value::compound();
+    root.insert("Items", Value::list_from_iter(items)).unwrap();
+
+    assert_eq!(registry.migrate(&mut root, 1900, 2100).unwrap(), 3);
+    let counts: Vec<_> = root
+        .query(&"Items[*].Count".parse().unwrap())
+        .into_iter()
+        .map(|(_, value)| value.clone())
+        .collect();
+    assert_eq!(counts, [Value::Int(1), Value::Int(2), Value::Int(3)]);
+}
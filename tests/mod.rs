@@ -1 +1,31 @@
+mod bedrock;
+#[cfg(feature = "cli")]
+mod cli;
+mod codec;
+mod codegen;
+mod dedup;
+mod detect;
+mod diff;
+mod digest;
+mod editor;
+mod error;
+mod extract;
+mod file;
+mod gamerules;
+mod generate;
+mod inventory;
+mod java;
 mod leveldat;
+mod map;
+mod migrate;
+mod nibble;
+mod path;
+mod registry;
+mod schema;
+mod shared;
+mod snbt;
+mod tag;
+#[cfg(feature = "serde")]
+mod transcode;
+mod value;
+mod xml;
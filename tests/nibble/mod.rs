@@ -0,0 +1,34 @@
+use bnbt::{
+    nibble::{NIBBLE_ARRAY_LEN, NibbleArray, nibble_index},
+    value::Value,
+};
+
+#[test]
+fn nibble_array_get_set_and_convert() {
+    let mut array = NibbleArray::new();
+    assert_eq!(array.as_bytes().len(), NIBBLE_ARRAY_LEN);
+    assert!(array.iter().all(|v| v == 0));
+
+    array.set(0, 3).unwrap();
+    array.set(1, 12).unwrap();
+    array.set_at(15, 15, 15, 9).unwrap();
+    assert_eq!(array.as_bytes()[0], 0xc3);
+    assert_eq!(array.get(1), Some(12));
+    assert_eq!(array.get(nibble_index(15, 15, 15).unwrap()), Some(9));
+    assert_eq!(nibble_index(16, 0, 0), None);
+    assert_eq!(array.get_at(0, 16, 0), None);
+    assert!(array.set_at(0, 0, 16, 1).is_err());
+    assert_eq!(array.get(NibbleArray::NIBBLES), None);
+    assert!(array.set(NibbleArray::NIBBLES, 1).is_err());
+    assert!(array.set(2, 16).is_err());
+
+    let value: Value = array.clone().into();
+    assert_eq!(NibbleArray::from_value(&value).unwrap(), array);
+    assert_eq!(array.view().get_at(15, 15, 15), Some(9));
+    assert_eq!(array.view().get(NibbleArray::NIBBLES), None);
+
+    let bytes: Vec<u8> = array.into();
+    assert_eq!(bytes.len(), NIBBLE_ARRAY_LEN);
+    assert!(NibbleArray::try_from(vec![0u8; 10]).is_err());
+    assert!(NibbleArray::filled(15).iter().all(|v| v == 15));
+}
@@ -0,0 +1,169 @@
+use bnbt::{
+    path::{NbtPath, PathPattern},
+    value::Value,
+};
+
+fn sample() -> Value<'static> {
+    let mut item = Value::compound();
+    item.insert("id", "minecraft:stone").unwrap();
+    item.insert("Count", Value::Byte(64)).unwrap();
+
+    let mut data = Value::compound();
+    data.insert("Inventory", Value::list_from_iter(vec![item]))
+        .unwrap();
+    data.insert(
+        "Pos",
+        Value::list_from_iter(vec![Value::Double(0.5), Value::Double(64.0)]),
+    )
+    .unwrap();
+    data.insert("odd.key", Value::compound()).unwrap();
+
+    let mut root = Value::compound();
+    root.insert("Data", data).unwrap();
+    root
+}
+
+#[test]
+fn path_parse_and_display_round_trip() {
+    let path: NbtPath = "Data.Inventory[0].id".parse().unwrap();
+    assert_eq!(
+        path,
+        NbtPath::root()
+            .key("Data")
+            .key("Inventory")
+            .index(0)
+            .key("id")
+    );
+    assert_eq!(path.to_string(), "Data.Inventory[0].id");
+
+    let quoted = NbtPath::root().key("Data").key("odd.key");
+    assert_eq!(quoted.to_string(), "Data.\"odd.key\"");
+    assert_eq!(quoted.to_string().parse::<NbtPath>().unwrap(), quoted);
+
+    assert!("Data..id".parse::<NbtPath>().is_err());
+    assert!("Data[x]".parse::<NbtPath>().is_err());
+}
+
+#[test]
+fn flatten_unflatten_round_trip() {
+    let root = sample();
+    let flat = root.flatten();
+
+    let id: NbtPath = "Data.Inventory[0].id".parse().unwrap();
+    assert_eq!(flat.get(&id), Some(&Value::from("minecraft:stone")));
+    assert_eq!(root.get_path(&id), Some(&Value::from("minecraft:stone")));
+    assert_eq!(flat.len(), 5);
+
+    assert_eq!(Value::unflatten(flat).unwrap(), root);
+}
+
+#[test]
+fn wildcard_queries_return_concrete_paths() {
+    let mut root = sample();
+    root.insert("*", "literal").unwrap();
+
+    let pattern: PathPattern = "**.Inventory[*].id".parse().unwrap();
+    let matches = root.query(&pattern);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].0.to_string(), "Data.Inventory[0].id");
+    assert_eq!(matches[0].1, &Value::from("minecraft:stone"));
+
+    let pattern: PathPattern = "Data.*".parse().unwrap();
+    assert_eq!(root.query(&pattern).len(), 3);
+
+    let pattern: PathPattern = "Data.Pos[*]".parse().unwrap();
+    let paths: Vec<String> = root
+        .query(&pattern)
+        .into_iter()
+        .map(|(path, _)| path.to_string())
+        .collect();
+    assert_eq!(paths, ["Data.Pos[0]", "Data.Pos[1]"]);
+
+    let literal = NbtPath::root().key("*");
+    assert_eq!(literal.to_string(), "\"*\"");
+    assert_eq!(root.get_path(&literal), Some(&Value::from("literal")));
+    assert!("Data.*".parse::<NbtPath>().is_err());
+}
+
+#[test]
+fn search_strings_and_numbers() {
+    let mut root = sample();
+    root.insert("UUID", Value::IntArray(vec![1, -2, 3, 4]))
+        .unwrap();
+
+    let paths = root.search_strings("stone");
+    assert_eq!(paths, ["Data.Inventory[0].id".parse::<NbtPath>().unwrap()]);
+    assert!(root.search_strings("diamond").is_empty());
+
+    let paths: Vec<String> = root
+        .search_numbers(|n| n == 64.0)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(paths, ["Data.Inventory[0].Count", "Data.Pos[1]"]);
+    assert_eq!(
+        root.search_numbers(|n| n == -2.0),
+        [NbtPath::root().key("UUID")]
+    );
+}
+
+#[test]
+fn set_path_creates_intermediate_compounds() {
+    let mut root = sample();
+    let path = |s: &str| s.parse::<NbtPath>().unwrap();
+
+    assert_eq!(
+        root.set_path(&path("Data.GameRules.keepInventory"), "true")
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        root.get_path(&path("Data.GameRules.keepInventory")),
+        Some(&Value::from("true"))
+    );
+
+    let old = root
+        .set_path(&path("Data.Inventory[0].Count"), Value::Byte(1))
+        .unwrap();
+    assert_eq!(old, Some(Value::Byte(64)));
+
+    let old = root
+        .set_path(&path("Data.Pos[1]"), Value::Double(70.0))
+        .unwrap();
+    assert_eq!(old, Some(Value::Double(64.0)));
+
+    let err = root
+        .set_path(&path("Data.Pos[0].x"), 1)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        err,
+        "invalid path: Data.Pos[0]: expected Compound, found Double"
+    );
+
+    let err = root
+        .set_path(&path("Data.Pos[5]"), Value::Double(1.0))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid path: Data.Pos[5]: index out of bounds for list of length 2"
+    );
+
+    assert!(root.set_path(&path("Data.Pos[0]"), "x").is_err());
+
+    let before = root.clone();
+    let err = root
+        .set_path(&path("Data.Motion.Speed[0]"), Value::Double(1.0))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid path: Data.Motion.Speed: expected List, found Compound"
+    );
+    assert_eq!(root, before);
+    assert!(root.set_path(&path("Data.Extra.Pos[0].x"), 1).is_err());
+    assert_eq!(root, before);
+
+    let old = root.set_path(&NbtPath::root(), Value::Int(1)).unwrap();
+    assert!(old.is_some());
+    assert_eq!(root, Value::Int(1));
+}
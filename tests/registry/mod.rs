@@ -0,0 +1,123 @@
+use std::borrow::Cow;
+
+use bnbt::{
+    java::section::{BiomePalette, BlockStatePalette, block_state},
+    registry::{RegistryKind, RegistryResolver, RegistryTable},
+};
+
+struct Offset;
+
+impl RegistryResolver for Offset {
+    fn numeric_id(&self, _: RegistryKind, name: &str) -> Option<u32> {
+        name.strip_prefix("minecraft:b")?.parse().ok()
+    }
+
+    fn name(&self, _: RegistryKind, id: u32) -> Option<Cow<'_, str>> {
+        Some(Cow::Owned(format!("minecraft:b{}", id)))
+    }
+}
+
+#[test]
+fn table_resolves_both_directions() {
+    let table = RegistryTable::new()
+        .with(RegistryKind::Block, "air", 0)
+        .with(RegistryKind::Block, "minecraft:stone", 1)
+        .with(RegistryKind::Biome, "plains", 1);
+
+    assert_eq!(table.numeric_id(RegistryKind::Block, "stone"), Some(1));
+    assert_eq!(
+        table.numeric_id(RegistryKind::Biome, "minecraft:plains"),
+        Some(1)
+    );
+    assert_eq!(table.numeric_id(RegistryKind::Item, "stone"), None);
+    assert_eq!(
+        table.name(RegistryKind::Block, 0).as_deref(),
+        Some("minecraft:air")
+    );
+    assert_eq!(table.len(RegistryKind::Block), 2);
+
+    let mut blocks = BlockStatePalette::filled(block_state("minecraft:air"));
+    blocks.set_block_id(1, 2, 3, 1, &table).unwrap();
+    assert_eq!(
+        blocks.get_block(1, 2, 3),
+        Some(&block_state("minecraft:stone"))
+    );
+    assert_eq!(blocks.get_block(16, 0, 0), None);
+    assert!(blocks.get_block_id(0, 16, 0, &table).is_err());
+    assert_eq!(blocks.get_block_id(1, 2, 3, &table).unwrap(), 1);
+    assert!(blocks.set_block_id(0, 0, 0, 9, &table).is_err());
+
+    let ids = blocks.to_ids(&table).unwrap();
+    assert_eq!(ids.iter().filter(|&&id| id == 1).count(), 1);
+    assert_eq!(BlockStatePalette::from_ids(&ids, &table).unwrap(), blocks);
+
+    blocks
+        .set_block(0, 0, 0, block_state("minecraft:dirt"))
+        .unwrap();
+    assert!(blocks.to_ids(&table).is_err());
+}
+
+#[test]
+fn block_state_ids_keep_properties() {
+    use bnbt::value::Value;
+
+    let table = RegistryTable::new()
+        .with(RegistryKind::Block, "air", 0)
+        .with(RegistryKind::Block, "oak_log[axis=x]", 1)
+        .with(RegistryKind::Block, "oak_log[axis=y]", 2);
+
+    let mut log = block_state("minecraft:oak_log");
+    let mut properties = Value::compound();
+    properties.insert("axis", "y").unwrap();
+    log.insert("Properties", properties).unwrap();
+
+    let mut blocks = BlockStatePalette::filled(block_state("minecraft:air"));
+    blocks.set_block(0, 0, 0, log.clone()).unwrap();
+    assert_eq!(blocks.get_block_id(0, 0, 0, &table).unwrap(), 2);
+
+    blocks.set_block_id(1, 0, 0, 1, &table).unwrap();
+    assert_eq!(
+        blocks
+            .get_block(1, 0, 0)
+            .and_then(|state| state.get("Properties"))
+            .and_then(|properties| properties.get("axis")),
+        Some(&Value::from("x"))
+    );
+
+    let ids = blocks.to_ids(&table).unwrap();
+    assert_eq!(&ids[..3], [2, 1, 0]);
+    assert_eq!(
+        BlockStatePalette::from_ids(&ids, &table)
+            .unwrap()
+            .get_block(0, 0, 0),
+        Some(&log)
+    );
+
+    let bare = RegistryTable::new().with(RegistryKind::Block, "oak_log", 5);
+    assert!(blocks.get_block_id(0, 0, 0, &bare).is_err());
+}
+
+#[test]
+fn palettes_accept_custom_resolvers() {
+    let mut biomes = BiomePalette::filled("minecraft:b7".to_owned());
+    assert_eq!(biomes.get_biome_id(3, 3, 3, &Offset).unwrap(), 7);
+    biomes.set_biome_id(0, 0, 0, 12, &Offset).unwrap();
+    assert_eq!(biomes.get_biome(0, 0, 0), Some("minecraft:b12"));
+    assert_eq!(biomes.get_biome(4, 0, 0), None);
+    assert!(biomes.set_biome_id(0, 0, 4, 12, &Offset).is_err());
+    assert!(BiomePalette::from_ids(&[1, 2], &Offset).is_err());
+}
+
+#[cfg(feature = "legacy-biome-ids")]
+#[test]
+fn legacy_biome_ids() {
+    let table = RegistryTable::legacy_biomes();
+    assert_eq!(
+        table.numeric_id(RegistryKind::Biome, "minecraft:plains"),
+        Some(1)
+    );
+    assert_eq!(
+        table.name(RegistryKind::Biome, 173).as_deref(),
+        Some("minecraft:basalt_deltas")
+    );
+}
@@ -0,0 +1,143 @@
+use bnbt::{
+    error::NBTErrorKind,
+    schema::{Field, Schema},
+    value::Value,
+};
+
+fn item(count: Value<'static>, tag: Option<Value<'static>>) -> Value<'static> {
+    let mut root = Value::compound();
+    root.insert("id", "minecraft:stone").unwrap();
+    root.insert("Count", count).unwrap();
+    if let Some(tag) = tag {
+        root.insert("tag", tag).unwrap();
+    }
+    root
+}
+
+#[test]
+fn infer_merges_samples() {
+    let mut tag = Value::compound();
+    tag.insert("Damage", 3).unwrap();
+
+    let schema = Schema::infer(&[
+        item(Value::Byte(1), None),
+        item(Value::Int(64), Some(tag)),
+        Value::End,
+    ]);
+
+    let Schema::Compound(fields) = &schema else {
+        panic!("expected a compound schema, got {:?}", schema);
+    };
+    assert_eq!(
+        fields["Count"],
+        Field {
+            schema: Schema::Int,
+            optional: false
+        }
+    );
+    assert!(fields["tag"].optional);
+    assert!(!fields["id"].optional);
+
+    assert_eq!(
+        Schema::infer(&[Value::list_from_iter(Vec::<i32>::new())]),
+        Schema::List(Box::new(Schema::Unknown))
+    );
+    assert_eq!(
+        Schema::infer(&[Value::Float(1.0), Value::Double(2.0)]),
+        Schema::Double
+    );
+    assert_eq!(
+        Schema::infer(&[Value::Int(1), Value::from("x")]),
+        Schema::Mixed
+    );
+}
+
+#[test]
+fn validate_reports_each_violation() {
+    let schema = Schema::infer(&[item(Value::Short(1), None)]);
+
+    assert!(schema.accepts(&item(Value::Byte(5), None)));
+
+    let mut bad = Value::compound();
+    bad.insert("Count", Value::Long(5)).unwrap();
+    bad.insert("tag", 0).unwrap();
+
+    let err = schema.validate(&bad).unwrap_err();
+    let NBTErrorKind::InvalidFields(errors) = err.kind else {
+        panic!("expected InvalidFields");
+    };
+    assert_eq!(
+        errors,
+        vec![
+            "Count: expected Short, found Long",
+            "id: missing",
+            "tag: unexpected key",
+        ]
+    );
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn json_schema_round_trips() {
+    let mut tag = Value::compound();
+    tag.insert("Damage", 3).unwrap();
+    tag.insert("Lore", Value::list_from_iter(vec!["a"]))
+        .unwrap();
+    tag.insert("Pos", Value::Float(1.0)).unwrap();
+    tag.insert("Data", Value::IntArray(vec![1])).unwrap();
+
+    let schema = Schema::infer(&[
+        item(Value::Byte(1), None),
+        item(Value::Short(64), Some(tag)),
+    ]);
+    let document = schema.to_json_schema();
+
+    assert_eq!(
+        document["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(document["required"], serde_json::json!(["Count", "id"]));
+    assert_eq!(document["properties"]["Count"]["maximum"], 32767);
+    assert_eq!(
+        document["properties"]["tag"]["properties"]["Lore"]["items"]["type"],
+        "string"
+    );
+    assert_eq!(Schema::from_json_schema(&document).unwrap(), schema);
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn json_schema_import_is_best_effort() {
+    let document = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "a": { "type": "integer" },
+            "b": { "type": "integer", "minimum": 0, "maximum": 100000 },
+            "c": { "type": "number" },
+            "d": { "anyOf": [{ "type": "string" }, { "type": "integer" }] },
+            "e": true,
+            "f": { "type": "array", "items": { "type": "integer", "minimum": 0, "maximum": 1 } },
+        },
+        "required": ["a"],
+    });
+
+    let Schema::Compound(fields) = Schema::from_json_schema(&document).unwrap() else {
+        panic!("expected a compound schema");
+    };
+    assert_eq!(
+        fields["a"],
+        Field {
+            schema: Schema::Long,
+            optional: false
+        }
+    );
+    assert_eq!(fields["b"].schema, Schema::Int);
+    assert!(fields["b"].optional);
+    assert_eq!(fields["c"].schema, Schema::Double);
+    assert_eq!(fields["d"].schema, Schema::Mixed);
+    assert_eq!(fields["e"].schema, Schema::Unknown);
+    assert_eq!(fields["f"].schema, Schema::List(Box::new(Schema::Byte)));
+
+    assert!(Schema::from_json_schema(&serde_json::json!({ "type": "null" })).is_err());
+    assert!(Schema::from_json_schema(&serde_json::json!(false)).is_err());
+}
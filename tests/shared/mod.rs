@@ -0,0 +1,28 @@
+use bnbt::{shared::ArcValue, value::Value};
+
+#[test]
+fn arc_value_clones_share_until_mutated() {
+    let mut section = Value::compound();
+    section.insert("Y", 4).unwrap();
+    section
+        .insert("palette", Value::list_from_iter(vec!["minecraft:stone"]))
+        .unwrap();
+    let mut root = Value::compound();
+    root.insert("section", section).unwrap();
+
+    let shared = ArcValue::from(root.clone());
+    let mut copy = shared.clone();
+    assert!(copy.ptr_eq(&shared));
+
+    copy.get_mut("section").unwrap().insert("Y", 5).unwrap();
+    assert!(!copy.ptr_eq(&shared));
+    assert_eq!(shared.to_value(), root);
+    assert_eq!(
+        copy.get("section").unwrap().get("Y"),
+        Some(&ArcValue::Int(5))
+    );
+
+    let palette = shared.get("section").unwrap().get("palette").unwrap();
+    let copied_palette = copy.get("section").unwrap().get("palette").unwrap();
+    assert!(palette.ptr_eq(copied_palette));
+}
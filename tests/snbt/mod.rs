@@ -0,0 +1,106 @@
+use bnbt::{snbt, value::Value};
+
+fn sample() -> Value<'static> {
+    let mut root = Value::compound();
+    root.insert("Name", "Steve \"the\" miner").unwrap();
+    root.insert("odd key", Value::Byte(1)).unwrap();
+    root.insert("Pos", Value::list_from_iter(vec![Value::Double(0.5)]))
+        .unwrap();
+    root.insert("Heights", Value::from(vec![255u8, 1])).unwrap();
+    root.insert("Seed", Value::LongArray(vec![-1, 2])).unwrap();
+    root
+}
+
+#[test]
+fn snbt_writes_canonical_text() {
+    assert_eq!(
+        snbt::to_string(&sample()),
+        r#"{Heights:[B;-1b,1b],Name:"Steve \"the\" miner",Pos:[0.5d],Seed:[L;-1L,2L],"odd key":1b}"#
+    );
+    assert_eq!(
+        snbt::to_string_pretty(&Value::list_from_iter(vec![1, 2])),
+        "[\n  1,\n  2\n]"
+    );
+    assert_eq!(snbt::to_string_pretty(&Value::compound()), "{}");
+}
+
+#[test]
+fn snbt_parses_its_own_output() {
+    let value = sample();
+    assert_eq!(snbt::from_str(&snbt::to_string(&value)).unwrap(), value);
+    assert_eq!(
+        snbt::from_str(&snbt::to_string_pretty(&value)).unwrap(),
+        value
+    );
+
+    assert_eq!(snbt::from_str("3s").unwrap(), Value::Short(3));
+    assert_eq!(snbt::from_str("1.5f").unwrap(), Value::Float(1.5));
+    assert_eq!(snbt::from_str("2.0").unwrap(), Value::Double(2.0));
+    assert_eq!(snbt::from_str("true").unwrap(), Value::Byte(1));
+    assert_eq!(snbt::from_str("stone").unwrap(), Value::from("stone"));
+    assert_eq!(
+        snbt::from_str("[I; 1, -2]").unwrap(),
+        Value::IntArray(vec![1, -2])
+    );
+    assert_eq!(
+        snbt::from_str("{'a b': 'it\\'s'}").unwrap().get("a b"),
+        Some(&Value::from("it's"))
+    );
+
+    assert!(snbt::from_str("[1, 2b]").is_err());
+    assert!(snbt::from_str("[B; 1]").is_err());
+    assert!(snbt::from_str("{a:1").is_err());
+    assert!(snbt::from_str("1 2").is_err());
+}
+
+#[test]
+fn snbt_keeps_unknown_tags_apart_from_byte_arrays() {
+    let unknown = Value::Unknown(13, vec![1, 0xff]);
+    let text = snbt::to_string(&unknown);
+    assert_eq!(text, "[U13;1b,-1b]");
+    assert_eq!(snbt::from_str(&text).unwrap(), unknown);
+    assert_eq!(
+        snbt::from_str("[B;1b,-1b]").unwrap(),
+        Value::ByteArray(vec![1, 0xff])
+    );
+
+    assert!(snbt::from_str("[U7;1b]").is_err());
+    assert!(snbt::from_str("[U300;1b]").is_err());
+}
+
+#[test]
+fn snbt_rejects_deep_nesting() {
+    let parse = std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(|| {
+            let deep = "[".repeat(100_000) + &"]".repeat(100_000);
+            assert!(snbt::from_str(&deep).is_err());
+
+            let deep = "{a:".repeat(100_000) + "1" + &"}".repeat(100_000);
+            assert!(snbt::from_str(&deep).is_err());
+
+            let nested = "[".repeat(100) + &"]".repeat(100);
+            snbt::from_str(&nested).unwrap();
+        })
+        .unwrap();
+    parse.join().unwrap();
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn snapshots_are_written_then_compared() {
+    use bnbt::snapshot::assert_matches_snapshot_in;
+
+    let dir = tempfile::tempdir().unwrap();
+    assert_matches_snapshot_in(dir.path(), "player", &sample());
+    assert!(dir.path().join("player.snbt").exists());
+    assert_matches_snapshot_in(dir.path(), "player", &sample());
+
+    let mut changed = sample();
+    changed.insert("Name", "Alex").unwrap();
+    let result = std::panic::catch_unwind(|| {
+        assert_matches_snapshot_in(dir.path(), "player", &changed);
+    });
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("+   Name: \"Alex\","));
+}
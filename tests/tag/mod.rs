@@ -0,0 +1,20 @@
+use bnbt::tag::Tag;
+
+#[test]
+fn tag_payload_metadata() {
+    assert_eq!(Tag::End.fixed_payload_size(), Some(0));
+    assert_eq!(Tag::Short.fixed_payload_size(), Some(2));
+    assert_eq!(Tag::Float.fixed_payload_size(), Some(4));
+    assert_eq!(Tag::Double.fixed_payload_size(), Some(8));
+    assert_eq!(Tag::String.fixed_payload_size(), None);
+    assert_eq!(Tag::IntArray.fixed_payload_size(), None);
+
+    assert_eq!(Tag::ByteArray.array_element_tag(), Some(Tag::Byte));
+    assert_eq!(Tag::LongArray.array_element_tag(), Some(Tag::Long));
+    assert_eq!(Tag::List.array_element_tag(), None);
+
+    let element = Tag::IntArray.array_element_tag().unwrap();
+    assert_eq!(element.fixed_payload_size(), Some(4));
+    assert!(Tag::Compound.is_container());
+    assert!(!Tag::ByteArray.is_container());
+}
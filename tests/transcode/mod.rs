@@ -0,0 +1,73 @@
+use bnbt::{
+    codec::{NBTCodec, NBTCodecTrait},
+    transcode::{transcode, transcode_to_serde},
+    value::Value,
+};
+
+#[test]
+fn json_round_trips_through_nbt() {
+    let json = r#"{"LevelName":"World","SpawnY":64,"Time":9000000000,"flags":[true,false],"pos":[0.5,1.5],"missing":null,"nested":{"empty":[]}}"#;
+
+    let codec = NBTCodec::little_endian();
+    let mut buf = Vec::new();
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    transcode(&codec, &mut deserializer, &mut buf, "").unwrap();
+
+    let (_, value) = codec.read_tag(&mut buf.as_slice()).unwrap();
+    assert_eq!(value.get("SpawnY"), Some(&Value::Int(64)));
+    assert_eq!(value.get("Time"), Some(&Value::Long(9000000000)));
+    assert_eq!(
+        value.get("flags"),
+        Some(&Value::List(vec![Value::Byte(1), Value::Byte(0)]))
+    );
+    assert_eq!(value.get("missing"), None);
+
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::new(&mut out);
+    transcode_to_serde(&codec, &mut buf.as_slice(), &mut serializer).unwrap();
+
+    let back: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let mut expected: serde_json::Value = serde_json::from_str(json).unwrap();
+    expected.as_object_mut().unwrap().remove("missing");
+    expected["flags"] = serde_json::json!([1, 0]);
+    assert_eq!(back, expected);
+}
+
+#[test]
+fn mixed_json_arrays_are_rejected() {
+    let codec = NBTCodec::big_endian();
+    let mut deserializer = serde_json::Deserializer::from_str(r#"{"a":[1,"x"]}"#);
+    assert!(transcode(&codec, &mut deserializer, &mut Vec::new(), "").is_err());
+}
+
+#[test]
+fn extension_tags_transcode_as_bytes() {
+    use bnbt::{codec::ConfiguredCodec, error::Result};
+    use std::io::Read;
+
+    fn four_bytes(_: &ConfiguredCodec, reader: &mut dyn Read) -> Result<Vec<u8>> {
+        let mut payload = vec![0; 4];
+        reader.read_exact(&mut payload)?;
+        Ok(payload)
+    }
+
+    let mut bytes = vec![10, 0, 0];
+    bytes.extend_from_slice(&[13, 0, 3, b'r', b'g', b'b', 0xde, 0xad, 0xbe, 0xef]);
+    bytes.extend_from_slice(&[9, 0, 1, b'l', 13, 0, 0, 0, 1, 5, 6, 7, 8]);
+    bytes.push(0);
+
+    let codec = NBTCodec::big_endian();
+    let mut serializer = serde_json::Serializer::new(Vec::new());
+    assert!(transcode_to_serde(&codec, &mut bytes.as_slice(), &mut serializer).is_err());
+
+    let codec = codec.with_tag_extension(13, four_bytes);
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::new(&mut out);
+    transcode_to_serde(&codec, &mut bytes.as_slice(), &mut serializer).unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"rgb": [0xde, 0xad, 0xbe, 0xef], "l": [[5, 6, 7, 8]]})
+    );
+}
@@ -0,0 +1,200 @@
+use bnbt::value::{Value, compare_values};
+
+#[test]
+fn insert_returns_displaced_value() {
+    let mut map = Value::compound();
+
+    assert_eq!(map.insert("LevelName", "old").unwrap(), None);
+    assert_eq!(
+        map.insert("LevelName", "new").unwrap(),
+        Some(Value::from("old"))
+    );
+    assert_eq!(map.get("LevelName"), Some(&Value::from("new")));
+
+    assert!(Value::list(0).insert("LevelName", "new").is_err());
+}
+
+#[test]
+fn typed_list_iterates_scalars() {
+    let pos = Value::list_from_iter(vec![Value::Double(1.5), Value::Double(64.0)]);
+
+    let list = pos.as_typed_list::<f64>().unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.iter().sum::<f64>(), 65.5);
+
+    assert!(pos.as_typed_list::<f32>().is_err());
+    assert!(Value::list(0).as_typed_list::<i64>().unwrap().is_empty());
+}
+
+#[test]
+fn byte_array_views_share_storage() {
+    let heightmap = Value::from(vec![0u8, 127, 128, 255]);
+
+    assert_eq!(heightmap.as_bytes(), Some(&[0u8, 127, 128, 255][..]));
+    assert_eq!(heightmap.as_i8_slice(), Some(&[0i8, 127, -128, -1][..]));
+    assert_eq!(heightmap.as_i32_slice(), None);
+
+    let bytes = bnbt::value::i8_vec_into_bytes(vec![-1, 1]);
+    assert_eq!(bytes, vec![255, 1]);
+    assert_eq!(bnbt::value::bytes_into_i8_vec(bytes), vec![-1, 1]);
+
+    assert_eq!(Value::from(vec![-1i8, 1]), Value::ByteArray(vec![255, 1]));
+    let mut heightmap = heightmap;
+    heightmap.as_bytes_mut().unwrap().push(7);
+    assert_eq!(heightmap.as_i8_slice().unwrap().len(), 5);
+}
+
+#[test]
+fn byte_array_base64_and_hex() {
+    let value = Value::byte_array_from_base64("AP9/gA==").unwrap();
+    assert_eq!(value.as_bytes(), Some(&[0x00u8, 0xFF, 0x7F, 0x80][..]));
+    assert_eq!(value.to_base64().as_deref(), Some("AP9/gA=="));
+    assert_eq!(value.to_hex().as_deref(), Some("00ff7f80"));
+    assert_eq!(Value::byte_array_from_hex("00FF7F80").unwrap(), value);
+
+    for len in 0..8 {
+        let bytes: Vec<u8> = (0..len).map(|i| i * 37).collect();
+        let encoded = bnbt::encoding::to_base64(&bytes);
+        assert_eq!(bnbt::encoding::from_base64(&encoded).unwrap(), bytes);
+    }
+
+    assert!(Value::byte_array_from_hex("abc").is_err());
+    assert!(Value::byte_array_from_base64("A$==").is_err());
+    assert_eq!(Value::Int(1).to_base64(), None);
+}
+
+#[test]
+fn sort_list_by_key_orders_compounds() {
+    let slot = |slot: i8, id: &str| {
+        let mut item = Value::compound();
+        item.insert("Slot", Value::Byte(slot)).unwrap();
+        item.insert("id", id.to_owned()).unwrap();
+        item
+    };
+
+    let mut inventory = Value::list_from_iter(vec![
+        slot(9, "minecraft:dirt"),
+        Value::compound(),
+        slot(-1, "minecraft:shield"),
+        slot(2, "minecraft:stone"),
+    ]);
+    inventory.sort_list_by_key("Slot").unwrap();
+
+    let slots: Vec<Option<&Value>> = inventory
+        .as_list()
+        .unwrap()
+        .iter()
+        .map(|item| item.get("Slot"))
+        .collect();
+    assert_eq!(
+        slots,
+        [
+            Some(&Value::Byte(-1)),
+            Some(&Value::Byte(2)),
+            Some(&Value::Byte(9)),
+            None
+        ]
+    );
+
+    inventory
+        .sort_list(|a, b| {
+            let end = Value::End;
+            compare_values(b.get("id").unwrap_or(&end), a.get("id").unwrap_or(&end))
+        })
+        .unwrap();
+    assert_eq!(
+        inventory.as_list().unwrap()[0].get("id"),
+        Some(&Value::from("minecraft:stone"))
+    );
+    assert!(Value::compound().sort_list_by_key("Slot").is_err());
+}
+
+#[test]
+fn promoted_lists_widen_numeric_tags() {
+    let list =
+        Value::list_from_iter_promoted(vec![Value::Byte(1), Value::Short(300), Value::Int(-7)])
+            .unwrap();
+    assert_eq!(
+        list,
+        Value::List(vec![Value::Int(1), Value::Int(300), Value::Int(-7)])
+    );
+
+    let mut list = Value::list_from_iter(vec![Value::Short(2)]);
+    list.push_promoted(Value::Float(0.5)).unwrap();
+    assert_eq!(
+        list,
+        Value::List(vec![Value::Float(2.0), Value::Float(0.5)])
+    );
+    list.push_promoted(Value::Long(3)).unwrap();
+    assert_eq!(list.list_tag(), Some(bnbt::tag::Tag::Double.into()));
+
+    assert!(list.push_promoted("three").is_err());
+    assert_eq!(list.as_list().unwrap().len(), 3);
+    assert!(Value::list_from_iter_promoted(Vec::<Value>::new()).is_ok());
+}
+
+#[test]
+fn heterogeneous_lists_round_trip_through_wrapping() {
+    let mut nested = Value::compound();
+    nested.insert("", 5).unwrap();
+
+    let mut root = Value::compound();
+    root.insert(
+        "args",
+        Value::List(vec![Value::Int(1), Value::from("two"), nested.clone()]),
+    )
+    .unwrap();
+    root.insert("plain", Value::list_from_iter(vec![1, 2]))
+        .unwrap();
+    let original = root.clone();
+
+    root.wrap_heterogeneous_lists();
+    let args = root.get("args").unwrap().as_list().unwrap();
+    assert!(args.iter().all(|v| v.get("").is_some()));
+    assert_eq!(args[2].get(""), Some(&nested));
+    assert_eq!(root.get("plain"), original.get("plain"));
+
+    let codec = bnbt::codec::NBTCodec::big_endian();
+    let mut buf = Vec::new();
+    bnbt::codec::NBTCodecTrait::write_tag(&codec, &mut buf, None, &root).unwrap();
+
+    root.unwrap_heterogeneous_lists();
+    assert_eq!(root, original);
+}
+
+#[test]
+fn unsigned_views_reinterpret_signed_storage() {
+    let values: [Value; 3] = [Value::Byte(-1), Value::Short(-2), Value::Int(1)];
+    assert_eq!(values[0].as_u8(), Some(255));
+    assert_eq!(values[1].as_u16(), Some(65534));
+    assert_eq!(Value::from_u32_as_int(u32::MAX), Value::Int(-1));
+    assert_eq!(
+        Value::from_u32_as_int(0xdead_beef).as_u32(),
+        Some(0xdead_beef)
+    );
+    assert_eq!(Value::from_u64_as_long(u64::MAX).as_u64(), Some(u64::MAX));
+    assert_eq!(Value::from_u8_as_byte(200), Value::Byte(-56));
+    assert_eq!(Value::from_u16_as_short(40000).as_u16(), Some(40000));
+    assert_eq!(values[2].as_u64(), None);
+}
+
+#[test]
+fn type_predicates_filter_values() {
+    let values = [
+        Value::Byte(1),
+        Value::Double(0.5),
+        Value::from("name"),
+        Value::ByteArray(vec![1]),
+        Value::LongArray(vec![1]),
+        Value::list_from_iter(vec![1, 2]),
+        Value::compound(),
+        Value::End,
+    ];
+
+    assert_eq!(values.iter().filter(|v| v.is_numeric()).count(), 2);
+    assert_eq!(values.iter().filter(|v| v.is_array()).count(), 2);
+    assert_eq!(values.iter().filter(|v| v.is_string()).count(), 1);
+    assert!(values[5].is_list() && !values[5].is_compound());
+    assert!(values[6].is_compound() && !values[6].is_list());
+    assert!(!values[7].is_numeric());
+}
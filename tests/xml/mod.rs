@@ -0,0 +1,91 @@
+use bnbt::{
+    codec::{NBTCodec, NBTCodecTrait},
+    tag::Tag,
+    value::Value,
+    xml,
+};
+use std::{env::current_dir, fs};
+
+#[test]
+fn xml_round_trip_leveldat() {
+    let codec = NBTCodec::little_endian();
+    let data = fs::read(current_dir().unwrap().join("tests/resources/level.dat")).unwrap();
+
+    let (name, value) = codec.read_tag(&mut &data[8..]).unwrap();
+    let exported = xml::to_string(name.as_deref(), &value);
+
+    let (imported_name, imported) = xml::from_str(&exported).unwrap();
+    assert_eq!(imported_name.as_deref(), name.as_deref());
+    assert_eq!(imported, value);
+}
+
+#[test]
+fn xml_import_editor_output() {
+    let input = r#"<?xml version="1.0"?>
+<!-- exported -->
+<TAG_Compound name="">
+  <TAG_String name="LevelName">A &amp; B</TAG_String>
+  <TAG_List name="Pos" type="TAG_Double">
+    <TAG_Double>0.5</TAG_Double>
+    <TAG_Double>-3</TAG_Double>
+  </TAG_List>
+  <TAG_Int_Array name="ids">1 2 3</TAG_Int_Array>
+</TAG_Compound>"#;
+
+    let (_, value) = xml::from_str(input).unwrap();
+    assert_eq!(value.get("LevelName"), Some(&Value::from("A & B")));
+    assert_eq!(
+        value.get("Pos"),
+        Some(&Value::List(vec![Value::Double(0.5), Value::Double(-3.0)]))
+    );
+    assert_eq!(value.get("ids"), Some(&Value::IntArray(vec![1, 2, 3])));
+
+    assert!(xml::from_str("<TAG_Int>1</TAG_Short>").is_err());
+}
+
+#[test]
+fn xml_keeps_the_element_type_of_empty_lists() {
+    let mut root = Value::compound();
+    root.insert(
+        "Items",
+        Value::typed_list(Tag::Compound, Vec::new()).unwrap(),
+    )
+    .unwrap();
+
+    let exported = xml::to_string(None, &root);
+    assert!(exported.contains("type=\"TAG_Compound\""));
+    let (_, imported) = xml::from_str(&exported).unwrap();
+    assert_eq!(imported, root);
+}
+
+#[test]
+fn xml_keeps_unknown_tags_apart_from_byte_arrays() {
+    let mut root = Value::compound();
+    root.insert("rgb", Value::Unknown(13, vec![1, 0xff]))
+        .unwrap();
+    root.insert("raw", Value::ByteArray(vec![1, 0xff])).unwrap();
+
+    let exported = xml::to_string(None, &root);
+    assert!(exported.contains("<TAG_Unknown name=\"rgb\" id=\"13\">1 -1</TAG_Unknown>"));
+    let (_, imported) = xml::from_str(&exported).unwrap();
+    assert_eq!(imported, root);
+
+    assert!(xml::from_str("<TAG_Unknown id=\"7\">1</TAG_Unknown>").is_err());
+    assert!(xml::from_str("<TAG_Unknown>1</TAG_Unknown>").is_err());
+}
+
+#[test]
+fn xml_rejects_deep_nesting() {
+    let parse = std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(|| {
+            let deep =
+                "<TAG_Compound name=\"a\">".repeat(100_000) + &"</TAG_Compound>".repeat(100_000);
+            assert!(xml::from_str(&deep).is_err());
+
+            let nested = "<TAG_Compound name=\"a\">".repeat(100) + &"</TAG_Compound>".repeat(100);
+            xml::from_str(&nested).unwrap();
+        })
+        .unwrap();
+    parse.join().unwrap();
+}